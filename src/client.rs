@@ -1,26 +1,55 @@
+#[cfg(any(feature = "tokio-rustls", feature = "tokio-native-tls"))]
 use std::io;
+#[cfg(feature = "tokio-rustls")]
+use std::iter;
+use std::str;
+#[cfg(feature = "tokio-rustls")]
 use std::sync::Arc;
-use std::time::Duration;
+#[cfg(feature = "tokio-rustls")]
+use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
+#[cfg(any(feature = "tokio-rustls", feature = "tokio-native-tls"))]
 use async_trait::async_trait;
+use chrono::Utc;
+#[cfg(feature = "tokio-rustls")]
+use pkcs8::{EncryptedPrivateKeyInfo, SecretDocument};
+#[cfg(feature = "tokio-rustls")]
+use rustls_pemfile::Item;
 #[cfg(feature = "tokio-rustls")]
+use sha2::{Digest, Sha256};
+#[cfg(any(feature = "tokio-rustls", feature = "tokio-native-tls"))]
 use tokio::net::lookup_host;
+#[cfg(any(feature = "tokio-rustls", feature = "tokio-native-tls"))]
 use tokio::net::TcpStream;
+#[cfg(feature = "tokio-native-tls")]
+use tokio_native_tls::native_tls;
 #[cfg(feature = "tokio-rustls")]
 use tokio_rustls::client::TlsStream;
 #[cfg(feature = "tokio-rustls")]
-use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use tokio_rustls::rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+#[cfg(feature = "tokio-rustls")]
+use tokio_rustls::rustls::{self, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
 #[cfg(feature = "tokio-rustls")]
 use tokio_rustls::TlsConnector;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
+#[cfg(feature = "tokio-rustls")]
+use x509_cert::der::{Decode, Encode};
 
-use crate::common::{Certificate, NoExtension, PrivateKey};
-pub use crate::connection::Connector;
-use crate::connection::{self, EppConnection};
+use crate::common::NoExtension;
+#[cfg(any(feature = "tokio-rustls", feature = "tokio-native-tls"))]
+use crate::common::{Certificate, PrivateKey};
+#[cfg(any(feature = "tokio-rustls", feature = "tokio-native-tls"))]
+use crate::connection;
+use crate::connection::EppConnection;
+pub use crate::connection::{Connector, StreamConnector};
 use crate::error::Error;
+use crate::extensions::login_sec::LoginSec;
 use crate::hello::{Greeting, GreetingDocument, HelloDocument};
+use crate::login::Login;
+use crate::redact::Redactor;
 use crate::request::{Command, CommandDocument, Extension, Transaction};
-use crate::response::{Response, ResponseDocument, ResponseStatus};
+use crate::response::{Exchange, Response, ResponseDocument, ResponseStatus};
 use crate::xml;
 
 /// An `EppClient` provides an interface to sending EPP requests to a registry
@@ -69,6 +98,18 @@ use crate::xml;
 /// ```
 pub struct EppClient<C: Connector> {
     connection: EppConnection<C>,
+    keepalive: Option<KeepAlive>,
+    redactor: Redactor,
+    // Whether the connection was replaced after a timeout without logging in again, so that
+    // commands fail with a clear error until the next login
+    login_required: bool,
+}
+
+/// Keepalive settings, see `EppClient::set_keepalive()`
+struct KeepAlive {
+    interval: Duration,
+    login: Login<'static>,
+    login_sec: Option<LoginSec<'static>>,
 }
 
 #[cfg(feature = "tokio-rustls")]
@@ -78,10 +119,12 @@ impl EppClient<RustlsConnector> {
     /// The `registry` is used as a name in internal logging; `host` provides the host name
     /// and port to connect to), `hostname` is sent as the TLS server name indication and
     /// `identity` provides optional TLS client authentication (using) rustls as the TLS
-    /// implementation. The `timeout` limits the time spent on any underlying network operations.
+    /// implementation. The `timeout` limits the time spent on any underlying network operations,
+    /// including each request and response cycle.
     ///
     /// Alternatively, use `EppClient::new()` with any established `AsyncRead + AsyncWrite + Unpin`
-    /// implementation.
+    /// implementation, e.g. a connector from [`RustlsConnector::builder()`] for custom trust
+    /// roots, certificate pinning or a separate server name.
     pub async fn connect(
         registry: String,
         server: (String, u16),
@@ -95,23 +138,148 @@ impl EppClient<RustlsConnector> {
 
 impl<C: Connector> EppClient<C> {
     /// Create an `EppClient` from an already established connection
+    ///
+    /// The `timeout` limits the time spent on connecting and on each request and response cycle.
     pub async fn new(connector: C, registry: String, timeout: Duration) -> Result<Self, Error> {
         Ok(Self {
             connection: EppConnection::new(connector, registry, timeout).await?,
+            keepalive: None,
+            redactor: Redactor::new(),
+            login_required: false,
         })
     }
 
+    /// Use `redactor` to hide secrets in the requests and responses that are logged
+    ///
+    /// By default, passwords and authorization information are hidden.
+    pub fn set_redactor(&mut self, redactor: Redactor) {
+        self.redactor = redactor;
+    }
+
+    /// Keep the session alive by sending a hello once it has been idle for `interval`
+    ///
+    /// The hello is sent before the next command when the session has been idle for too long,
+    /// or when `keepalive()` is called. If the registry has dropped the session, the client
+    /// reconnects and logs in again with `login` before the command runs. If `login` changes
+    /// the password, the new password is used to log in again.
+    pub fn set_keepalive(&mut self, interval: Duration, login: Login<'_>) {
+        self.set_keepalive_login(interval, login, None);
+    }
+
+    /// Keep the session alive, logging in again with the login security extension if given
+    pub(crate) fn set_keepalive_login(
+        &mut self,
+        interval: Duration,
+        login: Login<'_>,
+        login_sec: Option<LoginSec<'_>>,
+    ) {
+        self.keepalive = Some(KeepAlive {
+            interval,
+            login: login.into_relogin(),
+            login_sec: login_sec.map(LoginSec::into_relogin),
+        });
+    }
+
+    /// Turns off keepalive
+    pub fn clear_keepalive(&mut self) {
+        self.keepalive = None;
+    }
+
+    /// Sends a hello if keepalive is on and the session has been idle for the keepalive interval
+    ///
+    /// Call this periodically (e.g. from a `tokio::time::interval()`) to keep otherwise idle
+    /// sessions from being dropped by the registry.
+    pub async fn keepalive(&mut self) -> Result<(), Error> {
+        let interval = match &self.keepalive {
+            Some(keepalive) => keepalive.interval,
+            None => return Ok(()),
+        };
+
+        if self.connection.idle_time() < interval {
+            return Ok(());
+        }
+
+        debug!("{}: sending keepalive", self.connection.registry);
+        if let Err(e) = self.hello().await {
+            warn!("{}: keepalive failed: {}", self.connection.registry, e);
+            self.connection.reconnect().await?;
+            self.relogin().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Reconnects if a request timed out, as its late response could otherwise be read as the
+    /// response to the next request
+    ///
+    /// The new connection starts a new session, which is logged in again with the keepalive
+    /// login, like a session dropped by the registry. Without a keepalive login, commands fail
+    /// until the client logs in again.
+    async fn recover(&mut self) -> Result<(), Error> {
+        if !self.connection.is_poisoned() {
+            return Ok(());
+        }
+
+        warn!(
+            "{}: reconnecting after a request timed out",
+            self.connection.registry
+        );
+        self.connection.reconnect().await?;
+        match self.keepalive.is_some() {
+            true => self.relogin().await,
+            false => {
+                self.login_required = true;
+                Ok(())
+            }
+        }
+    }
+
+    async fn relogin(&mut self) -> Result<(), Error> {
+        let login = match self.keepalive.take() {
+            Some(keepalive) => keepalive,
+            None => return Ok(()),
+        };
+
+        info!("{}: logging in again", self.connection.registry);
+        let id = format!("{}:login", self.connection.registry);
+        let result = match &login.login_sec {
+            Some(login_sec) => self
+                .send((&login.login, login_sec).into(), &id, None)
+                .await
+                .map(|_| ()),
+            None => self
+                .send((&login.login).into(), &id, None)
+                .await
+                .map(|_| ()),
+        };
+        self.keepalive = Some(login);
+        result
+    }
+
     /// Executes an EPP Hello call and returns the response as a `Greeting`
     pub async fn hello(&mut self) -> Result<Greeting, Error> {
+        self.recover().await?;
         let xml = xml::serialize(&HelloDocument::default())?;
 
-        debug!("{}: hello: {}", self.connection.registry, &xml);
-        let response = self.connection.transact(&xml)?.await?;
-        debug!("{}: greeting: {}", self.connection.registry, &response);
+        debug!(
+            "{}: hello: {}",
+            self.connection.registry,
+            self.redactor.display(&xml)
+        );
+        let response = self.connection.transact(&xml, None).await?;
+        debug!(
+            "{}: greeting: {}",
+            self.connection.registry,
+            self.redactor.display(&response)
+        );
 
         Ok(xml::deserialize::<GreetingDocument>(&response)?.data)
     }
 
+    /// Executes an EPP command and returns the response
+    ///
+    /// The request and response together are limited by the timeout given when the client was
+    /// created; use `transact_with_timeout()` to override it for a single command.
     pub async fn transact<'c, 'e, Cmd, Ext>(
         &mut self,
         data: impl Into<RequestData<'c, 'e, Cmd, Ext>>,
@@ -121,40 +289,194 @@ impl<C: Connector> EppClient<C> {
         Cmd: Transaction<Ext> + Command + 'c,
         Ext: Extension + 'e,
     {
-        let data = data.into();
+        let (response, _) = self.execute(data.into(), id, None).await?;
+        Ok(response)
+    }
+
+    /// Executes an EPP command and returns the response along with the XML exchanged for it
+    ///
+    /// The [`Exchange`] holds the request and response with timing and transaction IDs, for
+    /// keeping an audit trail. Both are kept exactly as they were sent and received, passwords
+    /// included; use [`Exchange::redacted()`] to hide them before the trail is stored. When the
+    /// registry returns an error, the exchange is kept in the `ResponseStatus` of
+    /// `Error::Command`.
+    pub async fn transact_with_exchange<'c, 'e, Cmd, Ext>(
+        &mut self,
+        data: impl Into<RequestData<'c, 'e, Cmd, Ext>>,
+        id: &str,
+    ) -> Result<(Response<Cmd::Response, Ext::Response>, Exchange), Error>
+    where
+        Cmd: Transaction<Ext> + Command + 'c,
+        Ext: Extension + 'e,
+    {
+        self.execute(data.into(), id, None).await
+    }
+
+    /// Executes an EPP command, failing with `Error::Timeout` if it does not complete in time
+    ///
+    /// After a timeout, the connection is reconnected before the next request is sent, so a
+    /// late response to the abandoned request is never mistaken for the next one. This ends
+    /// the session: with keepalive on, the client logs in again by itself, otherwise commands
+    /// other than a login fail until the client has logged in again.
+    pub async fn transact_with_timeout<'c, 'e, Cmd, Ext>(
+        &mut self,
+        data: impl Into<RequestData<'c, 'e, Cmd, Ext>>,
+        id: &str,
+        timeout: Duration,
+    ) -> Result<Response<Cmd::Response, Ext::Response>, Error>
+    where
+        Cmd: Transaction<Ext> + Command + 'c,
+        Ext: Extension + 'e,
+    {
+        let (response, _) = self.execute(data.into(), id, Some(timeout)).await?;
+        Ok(response)
+    }
+
+    async fn execute<'c, 'e, Cmd, Ext>(
+        &mut self,
+        data: RequestData<'c, 'e, Cmd, Ext>,
+        id: &str,
+        timeout: Option<Duration>,
+    ) -> Result<(Response<Cmd::Response, Ext::Response>, Exchange), Error>
+    where
+        Cmd: Transaction<Ext> + Command + 'c,
+        Ext: Extension + 'e,
+    {
+        self.recover().await?;
+        self.keepalive().await?;
+        if self.login_required && data.command.negotiated_extensions().is_none() {
+            return Err(Error::Other(
+                "the connection was reset after a request timed out, login required".into(),
+            ));
+        }
+
+        self.send(data, id, timeout).await
+    }
+
+    async fn send<'c, 'e, Cmd, Ext>(
+        &mut self,
+        data: RequestData<'c, 'e, Cmd, Ext>,
+        id: &str,
+        timeout: Option<Duration>,
+    ) -> Result<(Response<Cmd::Response, Ext::Response>, Exchange), Error>
+    where
+        Cmd: Transaction<Ext> + Command + 'c,
+        Ext: Extension + 'e,
+    {
+        if let Some(ext) = data.extension {
+            self.check_extension(data.command, ext)?;
+        }
+
         let document = CommandDocument::new(data.command, data.extension, id);
         let xml = xml::serialize(&document)?;
 
-        debug!("{}: request: {}", self.connection.registry, &xml);
-        let response = self.connection.transact(&xml)?.await?;
-        debug!("{}: response: {}", self.connection.registry, &response);
+        debug!(
+            "{}: request: {}",
+            self.connection.registry,
+            self.redactor.display(&xml)
+        );
+        let sent_at = Utc::now();
+        let start = Instant::now();
+        let response = self.connection.transact(&xml, timeout).await?;
+        let elapsed = start.elapsed();
+        debug!(
+            "{}: response: {}",
+            self.connection.registry,
+            self.redactor.display(&response)
+        );
 
         let rsp =
             match xml::deserialize::<ResponseDocument<Cmd::Response, Ext::Response>>(&response) {
                 Ok(rsp) => rsp,
                 Err(e) => {
+                    let response = self.redactor.display(&response);
                     error!(%response, "failed to deserialize response for transaction: {e}");
                     return Err(e);
                 }
             };
 
-        if rsp.data.result.code.is_success() {
-            return Ok(rsp.data);
+        let success = rsp.data.result.code.is_success();
+        if !success {
+            let message = &rsp.data.result.message;
+            let response = self.redactor.display(&response);
+            error!(%response, "Failed to deserialize response for transaction: {}", message);
+        }
+
+        let exchange = Exchange {
+            request: xml,
+            response,
+            sent_at,
+            elapsed,
+            client_tr_id: id.to_owned(),
+            server_tr_id: rsp.data.tr_ids.server_tr_id.to_string(),
+        };
+
+        if success {
+            if let Some(ext_uris) = data.command.negotiated_extensions() {
+                let ext_uris = ext_uris.into_iter().map(String::from).collect();
+                self.connection.ext_uris = Some(ext_uris);
+                self.login_required = false;
+            }
+            return Ok((rsp.data, exchange));
         }
 
-        let err = crate::error::Error::Command(Box::new(ResponseStatus {
+        Err(Error::Command(Box::new(ResponseStatus {
             result: rsp.data.result,
             tr_ids: rsp.data.tr_ids,
-        }));
+            exchange: Some(Box::new(exchange)),
+        })))
+    }
+
+    /// Checks that the namespaces of `ext` were negotiated at login
+    ///
+    /// Before logging in, and for the login command itself, the extension URIs announced by
+    /// the command are used instead; if there are none, the check is skipped.
+    fn check_extension<Cmd: Command, Ext: Extension>(
+        &self,
+        command: &Cmd,
+        ext: &Ext,
+    ) -> Result<(), Error> {
+        let negotiated = match command.negotiated_extensions() {
+            Some(uris) => uris,
+            None => match &self.connection.ext_uris {
+                Some(uris) => uris.iter().map(|uri| uri.as_str()).collect(),
+                None => return Ok(()),
+            },
+        };
 
-        error!(%response, "Failed to deserialize response for transaction: {}", err);
-        Err(err)
+        for namespace in ext.namespaces() {
+            if !negotiated.contains(&namespace) {
+                return Err(Error::Other(
+                    format!("extension {namespace} was not negotiated at login").into(),
+                ));
+            }
+        }
+
+        Ok(())
     }
 
     /// Accepts raw EPP XML and returns the raw EPP XML response to it.
     /// Not recommended for direct use but sometimes can be useful for debugging
     pub async fn transact_xml(&mut self, xml: &str) -> Result<String, Error> {
-        self.connection.transact(xml)?.await
+        self.recover().await?;
+        self.keepalive().await?;
+        debug!(
+            "{}: request: {}",
+            self.connection.registry,
+            self.redactor.display(xml)
+        );
+        let response = self.connection.transact(xml, None).await?;
+        debug!(
+            "{}: response: {}",
+            self.connection.registry,
+            self.redactor.display(&response)
+        );
+        Ok(response)
+    }
+
+    /// Returns the name of the registry, as used in internal logging
+    pub fn registry(&self) -> &str {
+        &self.connection.registry
     }
 
     /// Returns the greeting received on establishment of the connection in raw xml form
@@ -203,88 +525,800 @@ impl<'c, 'e, C: Command, E: Extension> From<(&'c C, &'e E)> for RequestData<'c,
 // Manual impl because this does not depend on whether `C` and `E` are `Clone`
 impl<'c, 'e, C, E> Clone for RequestData<'c, 'e, C, E> {
     fn clone(&self) -> Self {
-        Self {
-            command: self.command,
-            extension: self.extension,
-        }
+        *self
     }
 }
 
 // Manual impl because this does not depend on whether `C` and `E` are `Copy`
 impl<'c, 'e, C, E> Copy for RequestData<'c, 'e, C, E> {}
 
+/// A [`Connector`] that connects over TLS using rustls
 #[cfg(feature = "tokio-rustls")]
+#[derive(Clone)]
 pub struct RustlsConnector {
     inner: TlsConnector,
     domain: ServerName,
     server: (String, u16),
 }
 
+#[cfg(feature = "tokio-rustls")]
 impl RustlsConnector {
+    /// Create a connector for `server` that trusts the webpki roots
+    ///
+    /// The `identity` provides optional TLS client authentication. Use
+    /// [`RustlsConnector::builder()`] for other trust roots, pinning or a separate server name.
     pub async fn new(
         server: (String, u16),
         identity: Option<(Vec<Certificate>, PrivateKey)>,
     ) -> Result<Self, Error> {
+        let mut builder = Self::builder(server);
+        if let Some((certs, key)) = identity {
+            builder = builder.identity(certs, key);
+        }
+        builder.build()
+    }
+
+    /// Start building a connector for `server`
+    pub fn builder(server: (String, u16)) -> RustlsConnectorBuilder {
+        RustlsConnectorBuilder {
+            server,
+            server_name: None,
+            root_certs: Vec::new(),
+            root_pems: Vec::new(),
+            webpki_roots: true,
+            #[cfg(feature = "rustls-native-certs")]
+            native_roots: false,
+            spki_pins: Vec::new(),
+            identity: None,
+        }
+    }
+}
+
+/// Builds a [`RustlsConnector`]
+///
+/// By default, the connector trusts the webpki roots, sends the host name it connects to
+/// as the TLS server name and does not authenticate itself.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use epp_client::client::RustlsConnector;
+/// use epp_client::EppClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let ca = std::fs::read("ote-ca.pem").unwrap();
+/// let cert = std::fs::read("client.pem").unwrap();
+/// let key = std::fs::read("client.key").unwrap();
+///
+/// let connector = RustlsConnector::builder(("10.0.0.1".to_owned(), 700))
+///     .server_name("epp.ote.example.com".to_owned())
+///     .webpki_roots(false)
+///     .root_certificates_pem(&ca)
+///     .identity_pem(&cert, &key, Some("key password"))
+///     .build()
+///     .unwrap();
+///
+/// let timeout = Duration::from_secs(5);
+/// let client = EppClient::new(connector, "registry_name".to_owned(), timeout).await.unwrap();
+/// # }
+/// ```
+#[cfg(feature = "tokio-rustls")]
+pub struct RustlsConnectorBuilder {
+    server: (String, u16),
+    server_name: Option<String>,
+    root_certs: Vec<Certificate>,
+    root_pems: Vec<Vec<u8>>,
+    webpki_roots: bool,
+    #[cfg(feature = "rustls-native-certs")]
+    native_roots: bool,
+    spki_pins: Vec<[u8; 32]>,
+    identity: Option<Identity>,
+}
+
+#[cfg(feature = "tokio-rustls")]
+enum Identity {
+    Der(Vec<Certificate>, PrivateKey),
+    Pem {
+        certs: Vec<u8>,
+        key: Vec<u8>,
+        password: Option<String>,
+    },
+}
+
+#[cfg(feature = "tokio-rustls")]
+impl RustlsConnectorBuilder {
+    /// Send `name` as the TLS server name and verify the certificate against it, instead of
+    /// the host name that is connected to
+    pub fn server_name(mut self, name: String) -> Self {
+        self.server_name = Some(name);
+        self
+    }
+
+    /// Trust a DER-encoded root certificate
+    pub fn root_certificate(mut self, cert: Certificate) -> Self {
+        self.root_certs.push(cert);
+        self
+    }
+
+    /// Trust all root certificates in a PEM file
+    pub fn root_certificates_pem(mut self, pem: &[u8]) -> Self {
+        self.root_pems.push(pem.to_vec());
+        self
+    }
+
+    /// Whether to trust the webpki roots, which is the default
+    ///
+    /// Turn this off to only trust the root certificates added to the builder, e.g. for an
+    /// OT&E environment with a private CA.
+    pub fn webpki_roots(mut self, enabled: bool) -> Self {
+        self.webpki_roots = enabled;
+        self
+    }
+
+    /// Whether to trust the root certificates of the operating system
+    #[cfg(feature = "rustls-native-certs")]
+    pub fn native_roots(mut self, enabled: bool) -> Self {
+        self.native_roots = enabled;
+        self
+    }
+
+    /// Only accept certificate chains containing a public key with the given SHA-256 digest
+    ///
+    /// The `digest` is taken over the DER-encoded SubjectPublicKeyInfo, as in HTTP public key
+    /// pinning. Pinning is done in addition to the usual certificate verification; when pins
+    /// are added, any of them may match.
+    pub fn pin_spki_sha256(mut self, digest: [u8; 32]) -> Self {
+        self.spki_pins.push(digest);
+        self
+    }
+
+    /// Authenticate with a DER-encoded certificate chain and private key
+    pub fn identity(mut self, certs: Vec<Certificate>, key: PrivateKey) -> Self {
+        self.identity = Some(Identity::Der(certs, key));
+        self
+    }
+
+    /// Authenticate with a PEM-encoded certificate chain and private key
+    ///
+    /// The key can be in PKCS#8, PKCS#1 or SEC1 format. If a `password` is given, the key must
+    /// be an encrypted PKCS#8 key.
+    pub fn identity_pem(mut self, certs: &[u8], key: &[u8], password: Option<&str>) -> Self {
+        self.identity = Some(Identity::Pem {
+            certs: certs.to_vec(),
+            key: key.to_vec(),
+            password: password.map(ToOwned::to_owned),
+        });
+        self
+    }
+
+    /// Build the connector, loading and checking all certificates and keys
+    pub fn build(self) -> Result<RustlsConnector, Error> {
         let mut roots = RootCertStore::empty();
-        roots.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
-            OwnedTrustAnchor::from_subject_spki_name_constraints(
-                ta.subject,
-                ta.spki,
-                ta.name_constraints,
-            )
-        }));
+        if self.webpki_roots {
+            roots.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
+                OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    ta.subject,
+                    ta.spki,
+                    ta.name_constraints,
+                )
+            }));
+        }
+
+        #[cfg(feature = "rustls-native-certs")]
+        if self.native_roots {
+            let certs = rustls_native_certs::load_native_certs()?
+                .into_iter()
+                .map(|cert| cert.0)
+                .collect::<Vec<_>>();
+            let (_, ignored) = roots.add_parsable_certificates(&certs);
+            if ignored > 0 {
+                warn!("ignored {} unparsable native root certificates", ignored);
+            }
+        }
+
+        let mut root_certs = self.root_certs;
+        for pem in self.root_pems {
+            let certs = rustls_pemfile::certs(&mut pem.as_slice())?;
+            root_certs.extend(certs.into_iter().map(Certificate));
+        }
+
+        for cert in root_certs {
+            roots
+                .add(&rustls::Certificate(cert.0))
+                .map_err(|e| Error::Other(format!("invalid root certificate: {e}").into()))?;
+        }
+
+        if roots.is_empty() {
+            return Err(Error::Other("no trusted root certificates".into()));
+        }
+
+        let verifier = WebPkiVerifier::new(roots, None);
+        let verifier: Arc<dyn ServerCertVerifier> = match self.spki_pins.is_empty() {
+            true => Arc::new(verifier),
+            false => Arc::new(PinnedVerifier {
+                inner: verifier,
+                pins: self.spki_pins,
+            }),
+        };
 
         let builder = ClientConfig::builder()
             .with_safe_defaults()
-            .with_root_certificates(roots);
+            .with_custom_certificate_verifier(verifier);
 
-        let config = match identity {
-            Some((certs, key)) => {
+        let config = match self.identity {
+            Some(identity) => {
+                let (certs, key) = identity.load()?;
                 let certs = certs
                     .into_iter()
-                    .map(|cert| tokio_rustls::rustls::Certificate(cert.0))
+                    .map(|cert| rustls::Certificate(cert.0))
                     .collect();
                 builder
-                    .with_single_cert(certs, tokio_rustls::rustls::PrivateKey(key.0))
+                    .with_single_cert(certs, rustls::PrivateKey(key.0))
                     .map_err(|e| Error::Other(e.into()))?
             }
             None => builder.with_no_client_auth(),
         };
 
-        let domain = server.0.as_str().try_into().map_err(|_| {
+        let name = self.server_name.as_ref().unwrap_or(&self.server.0);
+        let domain = name.as_str().try_into().map_err(|_| {
             io::Error::new(
                 io::ErrorKind::InvalidInput,
-                format!("Invalid domain: {}", server.0),
+                format!("Invalid domain: {}", name),
             )
         })?;
 
-        Ok(Self {
+        Ok(RustlsConnector {
             inner: TlsConnector::from(Arc::new(config)),
             domain,
-            server,
+            server: self.server,
         })
     }
 }
 
+#[cfg(feature = "tokio-rustls")]
+impl Identity {
+    fn load(self) -> Result<(Vec<Certificate>, PrivateKey), Error> {
+        let (certs, key, password) = match self {
+            Self::Der(certs, key) => return Ok((certs, key)),
+            Self::Pem {
+                certs,
+                key,
+                password,
+            } => (certs, key, password),
+        };
+
+        let certs = rustls_pemfile::certs(&mut certs.as_slice())?
+            .into_iter()
+            .map(Certificate)
+            .collect::<Vec<_>>();
+        if certs.is_empty() {
+            return Err(Error::Other("no certificates found in PEM".into()));
+        }
+
+        let key = match password {
+            Some(password) => {
+                let pem = str::from_utf8(&key).map_err(|e| Error::Other(e.into()))?;
+                let (label, doc) =
+                    SecretDocument::from_pem(pem).map_err(|e| Error::Other(e.into()))?;
+                if label != "ENCRYPTED PRIVATE KEY" {
+                    return Err(Error::Other(
+                        format!("expected an encrypted private key, found {label}").into(),
+                    ));
+                }
+
+                EncryptedPrivateKeyInfo::try_from(doc.as_bytes())
+                    .and_then(|info| info.decrypt(password))
+                    .map_err(|e| {
+                        Error::Other(format!("failed to decrypt private key: {e}").into())
+                    })?
+                    .as_bytes()
+                    .to_vec()
+            }
+            None => rustls_pemfile::read_all(&mut key.as_slice())?
+                .into_iter()
+                .find_map(|item| match item {
+                    Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(key),
+                    _ => None,
+                })
+                .ok_or_else(|| Error::Other("no private key found in PEM".into()))?,
+        };
+
+        Ok((certs, PrivateKey(key)))
+    }
+}
+
+/// Verifies certificates as usual, and additionally requires a pinned public key in the chain
+#[cfg(feature = "tokio-rustls")]
+struct PinnedVerifier {
+    inner: WebPkiVerifier,
+    pins: Vec<[u8; 32]>,
+}
+
+#[cfg(feature = "tokio-rustls")]
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        intermediates: &[rustls::Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            scts,
+            ocsp_response,
+            now,
+        )?;
+
+        let pinned = iter::once(end_entity)
+            .chain(intermediates)
+            .filter_map(|cert| spki_sha256(&cert.0))
+            .any(|digest| self.pins.contains(&digest));
+        match pinned {
+            true => Ok(verified),
+            false => Err(rustls::Error::General(
+                "no pinned public key in certificate chain".into(),
+            )),
+        }
+    }
+}
+
+/// The SHA-256 digest of the DER-encoded SubjectPublicKeyInfo of a certificate
+#[cfg(feature = "tokio-rustls")]
+fn spki_sha256(cert: &[u8]) -> Option<[u8; 32]> {
+    let cert = x509_cert::Certificate::from_der(cert).ok()?;
+    let spki = cert.tbs_certificate.subject_public_key_info.to_vec().ok()?;
+    Some(Sha256::digest(&spki).into())
+}
+
 #[cfg(feature = "tokio-rustls")]
 #[async_trait]
 impl Connector for RustlsConnector {
     type Connection = TlsStream<TcpStream>;
 
     async fn connect(&self, timeout: Duration) -> Result<Self::Connection, Error> {
-        info!("Connecting to server: {}:{}", self.server.0, self.server.1);
-        let addr = match lookup_host(&self.server).await?.next() {
-            Some(addr) => addr,
-            None => {
-                return Err(Error::Io(io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!("Invalid host: {}", &self.server.0),
-                )))
-            }
-        };
+        let stream = connect_tcp(&self.server).await?;
+        self.connect_stream(stream, timeout).await
+    }
+}
+
+#[cfg(feature = "tokio-rustls")]
+#[async_trait]
+impl StreamConnector for RustlsConnector {
+    fn server(&self) -> &(String, u16) {
+        &self.server
+    }
 
-        let stream = TcpStream::connect(addr).await?;
+    async fn connect_stream(
+        &self,
+        stream: TcpStream,
+        timeout: Duration,
+    ) -> Result<Self::Connection, Error> {
         let future = self.inner.connect(self.domain.clone(), stream);
         connection::timeout(timeout, future).await
     }
 }
+
+/// A [`Connector`] that connects over TLS using the platform's TLS implementation
+///
+/// This uses OpenSSL on most Unix platforms, which can be needed for registries that require
+/// cipher suites or client certificate types that rustls does not support.
+#[cfg(feature = "tokio-native-tls")]
+#[derive(Clone)]
+pub struct NativeTlsConnector {
+    inner: tokio_native_tls::TlsConnector,
+    domain: String,
+    server: (String, u16),
+}
+
+#[cfg(feature = "tokio-native-tls")]
+impl NativeTlsConnector {
+    /// Create a connector for `server` that trusts the platform's root certificates
+    ///
+    /// The `identity` provides optional TLS client authentication. The private key can be in
+    /// PKCS#8, PKCS#1 or SEC1 format; the latter two are converted to PKCS#8 for native-tls.
+    pub fn new(
+        server: (String, u16),
+        identity: Option<(Vec<Certificate>, PrivateKey)>,
+    ) -> Result<Self, Error> {
+        let mut builder = native_tls::TlsConnector::builder();
+        if let Some((certs, key)) = identity {
+            let mut pem = String::new();
+            for cert in certs {
+                pem.push_str(&encode_pem("CERTIFICATE", &cert.0)?);
+            }
+
+            let key = encode_pem("PRIVATE KEY", &pkcs8_key(&key.0)?)?;
+            let identity = native_tls::Identity::from_pkcs8(pem.as_bytes(), key.as_bytes())
+                .map_err(|e| Error::Other(e.into()))?;
+            builder.identity(identity);
+        }
+
+        let connector = builder.build().map_err(|e| Error::Other(e.into()))?;
+        Ok(Self::from_native(server, connector))
+    }
+
+    /// Create a connector for `server` from a configured `native_tls::TlsConnector`
+    pub fn from_native(server: (String, u16), connector: native_tls::TlsConnector) -> Self {
+        Self {
+            inner: connector.into(),
+            domain: server.0.clone(),
+            server,
+        }
+    }
+}
+
+#[cfg(feature = "tokio-native-tls")]
+#[async_trait]
+impl Connector for NativeTlsConnector {
+    type Connection = tokio_native_tls::TlsStream<TcpStream>;
+
+    async fn connect(&self, timeout: Duration) -> Result<Self::Connection, Error> {
+        let stream = connect_tcp(&self.server).await?;
+        self.connect_stream(stream, timeout).await
+    }
+}
+
+#[cfg(feature = "tokio-native-tls")]
+#[async_trait]
+impl StreamConnector for NativeTlsConnector {
+    fn server(&self) -> &(String, u16) {
+        &self.server
+    }
+
+    async fn connect_stream(
+        &self,
+        stream: TcpStream,
+        timeout: Duration,
+    ) -> Result<Self::Connection, Error> {
+        let future = async {
+            self.inner
+                .connect(&self.domain, stream)
+                .await
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+        };
+        connection::timeout(timeout, future).await
+    }
+}
+
+#[cfg(feature = "tokio-native-tls")]
+fn encode_pem(label: &'static str, der: &[u8]) -> Result<String, Error> {
+    pkcs8::der::pem::encode_string(label, pkcs8::LineEnding::LF, der)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()).into())
+}
+
+/// Wraps a DER-encoded PKCS#1 RSA or SEC1 EC private key in a PKCS#8 structure, which is the
+/// only format native-tls accepts; PKCS#8 keys are returned as they are
+#[cfg(feature = "tokio-native-tls")]
+fn pkcs8_key(der: &[u8]) -> Result<Vec<u8>, Error> {
+    use pkcs8::der::asn1::{AnyRef, ContextSpecific, OctetStringRef, UIntRef};
+    use pkcs8::der::{Encode, Reader, SliceReader, Tag, TagNumber};
+    use pkcs8::{AlgorithmIdentifier, ObjectIdentifier, PrivateKeyInfo};
+
+    const RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+    const EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+
+    if PrivateKeyInfo::try_from(der).is_ok() {
+        return Ok(der.to_vec());
+    }
+
+    // Both start with a version; an RSAPrivateKey (RFC 8017) continues with the modulus, an
+    // ECPrivateKey (RFC 5915) with the private key and the named curve
+    let parsed = SliceReader::new(der).and_then(|mut reader| {
+        reader.sequence(|seq| {
+            seq.decode::<UIntRef<'_>>()?;
+            let (oid, curve) = match seq.peek_tag()? {
+                Tag::Integer => (RSA_ENCRYPTION, None),
+                _ => {
+                    seq.decode::<OctetStringRef<'_>>()?;
+                    let curve =
+                        ContextSpecific::<ObjectIdentifier>::decode_explicit(seq, TagNumber::N0)?;
+                    (EC_PUBLIC_KEY, curve.map(|curve| curve.value))
+                }
+            };
+
+            while !seq.is_finished() {
+                seq.decode::<AnyRef<'_>>()?;
+            }
+            Ok((oid, curve))
+        })
+    });
+
+    let (oid, parameters) = match &parsed {
+        Ok((RSA_ENCRYPTION, _)) => (RSA_ENCRYPTION, AnyRef::NULL),
+        Ok((oid, Some(curve))) => (*oid, curve.into()),
+        _ => {
+            return Err(Error::Other(
+                "unsupported private key, expected PKCS#8, PKCS#1 or SEC1 with a named curve"
+                    .into(),
+            ))
+        }
+    };
+    let algorithm = AlgorithmIdentifier {
+        oid,
+        parameters: Some(parameters),
+    };
+
+    PrivateKeyInfo::new(algorithm, der)
+        .to_vec()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e).into())
+}
+
+#[cfg(any(feature = "tokio-rustls", feature = "tokio-native-tls"))]
+async fn connect_tcp(server: &(String, u16)) -> Result<TcpStream, Error> {
+    info!("Connecting to server: {}:{}", server.0, server.1);
+    let addr = match lookup_host(server).await?.next() {
+        Some(addr) => addr,
+        None => {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid host: {}", &server.0),
+            )))
+        }
+    };
+
+    Ok(TcpStream::connect(addr).await?)
+}
+
+#[cfg(all(test, any(feature = "tokio-rustls", feature = "tokio-native-tls")))]
+mod tests {
+    use std::fs;
+
+    fn tls_file(name: &str) -> Vec<u8> {
+        fs::read(format!("tests/resources/tls/{name}")).unwrap()
+    }
+
+    #[cfg(feature = "tokio-rustls")]
+    mod rustls_connector {
+        use std::io;
+        use std::sync::Arc;
+
+        use tokio_rustls::rustls::{self, ServerConfig};
+        use tokio_rustls::TlsAcceptor;
+
+        use super::super::{spki_sha256, RustlsConnector, RustlsConnectorBuilder};
+        use super::tls_file;
+        use crate::common::{Certificate, PrivateKey};
+        use crate::Error;
+
+        fn builder() -> RustlsConnectorBuilder {
+            RustlsConnector::builder(("127.0.0.1".to_owned(), 700))
+                .server_name("epp.example.com".to_owned())
+                .webpki_roots(false)
+        }
+
+        fn build_error(builder: RustlsConnectorBuilder) -> String {
+            match builder.build() {
+                Ok(_) => panic!("expected an error"),
+                Err(e) => e.to_string(),
+            }
+        }
+
+        /// Runs a TLS handshake with a server for epp.example.com, whose certificate is signed by
+        /// the test CA
+        async fn handshake(connector: RustlsConnector) -> io::Result<()> {
+            let certs = rustls_pemfile::certs(&mut tls_file("server.pem").as_slice())
+                .unwrap()
+                .into_iter()
+                .map(rustls::Certificate)
+                .collect();
+            let key = rustls_pemfile::pkcs8_private_keys(&mut tls_file("server.key").as_slice())
+                .unwrap()
+                .remove(0);
+            let config = ServerConfig::builder()
+                .with_safe_defaults()
+                .with_no_client_auth()
+                .with_single_cert(certs, rustls::PrivateKey(key))
+                .unwrap();
+
+            let (client, server) = tokio::io::duplex(16384);
+            let acceptor = TlsAcceptor::from(Arc::new(config));
+            tokio::spawn(async move { acceptor.accept(server).await });
+            let client = connector.inner.connect(connector.domain.clone(), client);
+            client.await.map(|_| ())
+        }
+
+        fn server_spki_sha256() -> [u8; 32] {
+            let certs = rustls_pemfile::certs(&mut tls_file("server.pem").as_slice()).unwrap();
+            spki_sha256(&certs[0]).unwrap()
+        }
+
+        #[tokio::test]
+        async fn custom_root() {
+            let connector = builder()
+                .root_certificates_pem(&tls_file("ca.pem"))
+                .build()
+                .unwrap();
+            handshake(connector).await.unwrap();
+
+            let err = build_error(builder());
+            assert_eq!(err, "error: no trusted root certificates");
+
+            let err = build_error(builder().root_certificate(Certificate(vec![0; 32])));
+            assert!(err.starts_with("error: invalid root certificate"), "{err}");
+
+            // The server certificate is not signed by any of the webpki roots
+            let connector = RustlsConnector::builder(("127.0.0.1".to_owned(), 700))
+                .server_name("epp.example.com".to_owned())
+                .build()
+                .unwrap();
+            handshake(connector).await.unwrap_err();
+        }
+
+        #[tokio::test]
+        async fn spki_pinning() {
+            let connector = builder()
+                .root_certificates_pem(&tls_file("ca.pem"))
+                .pin_spki_sha256([0; 32])
+                .pin_spki_sha256(server_spki_sha256())
+                .build()
+                .unwrap();
+            handshake(connector).await.unwrap();
+
+            let connector = builder()
+                .root_certificates_pem(&tls_file("ca.pem"))
+                .pin_spki_sha256([0; 32])
+                .build()
+                .unwrap();
+            let err = handshake(connector).await.unwrap_err();
+            assert!(err.to_string().contains("no pinned public key"), "{err}");
+        }
+
+        #[test]
+        fn identity_pem() {
+            let ca = tls_file("ca.pem");
+            let cert = tls_file("client.pem");
+            for (key, password) in [
+                ("client.key", None),
+                ("client_sec1.key", None),
+                ("client_encrypted.key", Some("password")),
+            ] {
+                builder()
+                    .root_certificates_pem(&ca)
+                    .identity_pem(&cert, &tls_file(key), password)
+                    .build()
+                    .unwrap_or_else(|e| panic!("{key}: {e}"));
+            }
+
+            builder()
+                .root_certificates_pem(&ca)
+                .identity_pem(
+                    &tls_file("client_rsa.pem"),
+                    &tls_file("client_rsa.key"),
+                    None,
+                )
+                .build()
+                .unwrap();
+
+            let err = build_error(builder().root_certificates_pem(&ca).identity_pem(
+                &cert,
+                &tls_file("client_encrypted.key"),
+                Some("wrong"),
+            ));
+            assert!(
+                err.starts_with("error: failed to decrypt private key"),
+                "{err}"
+            );
+
+            let err = build_error(builder().root_certificates_pem(&ca).identity_pem(
+                &cert,
+                &tls_file("client.key"),
+                Some("password"),
+            ));
+            assert_eq!(
+                err,
+                "error: expected an encrypted private key, found PRIVATE KEY"
+            );
+
+            let err = build_error(builder().root_certificates_pem(&ca).identity_pem(
+                &cert,
+                &tls_file("client_encrypted.key"),
+                None,
+            ));
+            assert_eq!(err, "error: no private key found in PEM");
+
+            let err = build_error(
+                builder()
+                    .root_certificates_pem(&ca)
+                    .identity_pem(&ca, &cert, None),
+            );
+            assert_eq!(err, "error: no private key found in PEM");
+
+            let err = build_error(builder().root_certificates_pem(&ca).identity_pem(
+                &tls_file("client.key"),
+                &tls_file("client.key"),
+                None,
+            ));
+            assert_eq!(err, "error: no certificates found in PEM");
+        }
+
+        #[test]
+        fn identity_der() {
+            let cert = rustls_pemfile::certs(&mut tls_file("client.pem").as_slice()).unwrap();
+            let key = rustls_pemfile::pkcs8_private_keys(&mut tls_file("client.key").as_slice())
+                .unwrap()
+                .remove(0);
+
+            builder()
+                .root_certificates_pem(&tls_file("ca.pem"))
+                .identity(cert.into_iter().map(Certificate).collect(), PrivateKey(key))
+                .build()
+                .unwrap();
+
+            assert!(matches!(
+                builder()
+                    .root_certificates_pem(&tls_file("ca.pem"))
+                    .identity(vec![], PrivateKey(vec![0; 32]))
+                    .build()
+                    .map(|_| ()),
+                Err(Error::Other(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "tokio-native-tls")]
+    mod native_tls_connector {
+        use tokio_native_tls::native_tls::Identity;
+
+        use super::super::{pkcs8_key, NativeTlsConnector};
+        use super::tls_file;
+        use crate::common::{Certificate, PrivateKey};
+
+        fn der_key(name: &str) -> Vec<u8> {
+            match rustls_pemfile::read_one(&mut tls_file(name).as_slice()).unwrap() {
+                Some(rustls_pemfile::Item::PKCS8Key(key))
+                | Some(rustls_pemfile::Item::RSAKey(key))
+                | Some(rustls_pemfile::Item::ECKey(key)) => key,
+                _ => panic!("no private key in {name}"),
+            }
+        }
+
+        fn identity(cert: &str, key: &[u8]) -> Result<Identity, String> {
+            let key = pkcs8_key(key).map_err(|e| e.to_string())?;
+            let key =
+                pkcs8::der::pem::encode_string("PRIVATE KEY", pkcs8::LineEnding::LF, &key).unwrap();
+            Identity::from_pkcs8(&tls_file(cert), key.as_bytes()).map_err(|e| e.to_string())
+        }
+
+        #[test]
+        fn pkcs8_conversion() {
+            // PKCS#8 keys are passed through unchanged
+            let key = der_key("client.key");
+            assert_eq!(pkcs8_key(&key).unwrap(), key);
+            identity("client.pem", &key).unwrap();
+
+            // A SEC1 key ends up with the same algorithm as the PKCS#8 version of the same key
+            let sec1 = pkcs8_key(&der_key("client_sec1.key")).unwrap();
+            let info = pkcs8::PrivateKeyInfo::try_from(sec1.as_slice()).unwrap();
+            let expected = pkcs8::PrivateKeyInfo::try_from(key.as_slice()).unwrap();
+            assert_eq!(info.algorithm, expected.algorithm);
+            identity("client.pem", &der_key("client_sec1.key")).unwrap();
+
+            let rsa = pkcs8_key(&der_key("client_rsa.key")).unwrap();
+            let info = pkcs8::PrivateKeyInfo::try_from(rsa.as_slice()).unwrap();
+            assert_eq!(info.algorithm.oid.to_string(), "1.2.840.113549.1.1.1");
+            identity("client_rsa.pem", &der_key("client_rsa.key")).unwrap();
+
+            let err = pkcs8_key(&[0; 32]).unwrap_err().to_string();
+            assert!(err.starts_with("error: unsupported private key"), "{err}");
+        }
+
+        #[test]
+        fn pkcs1_identity() {
+            let certs = rustls_pemfile::certs(&mut tls_file("client_rsa.pem").as_slice())
+                .unwrap()
+                .into_iter()
+                .map(Certificate)
+                .collect();
+            let key = PrivateKey(der_key("client_rsa.key"));
+            NativeTlsConnector::new(("127.0.0.1".to_owned(), 700), Some((certs, key))).unwrap();
+        }
+    }
+}
@@ -35,6 +35,13 @@ impl Display for StringValue<'_> {
     }
 }
 
+impl StringValue<'_> {
+    /// Converts into a `StringValue` that owns its data
+    pub fn into_owned(self) -> StringValue<'static> {
+        StringValue(Cow::Owned(self.0.into_owned()))
+    }
+}
+
 impl<'a> From<&'a str> for StringValue<'a> {
     fn from(s: &'a str) -> Self {
         Self(s.into())
@@ -123,7 +130,7 @@ impl From<DeserializedCheckResponse> for CheckResponse {
 }
 
 /// The <option> type in EPP XML login requests
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(rename = "options")]
 pub struct Options<'a> {
     /// The EPP version being used
@@ -140,10 +147,18 @@ impl<'a> Options<'a> {
             lang: lang.into(),
         }
     }
+
+    /// Converts into `Options` that own their data
+    pub fn into_owned(self) -> Options<'static> {
+        Options {
+            version: self.version.into_owned(),
+            lang: self.lang.into_owned(),
+        }
+    }
 }
 
 /// The <svcExtension> type in EPP XML
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(rename = "svcExtension")]
 pub struct ServiceExtension<'a> {
     /// The service extension URIs being represented by <extURI> in EPP XML
@@ -151,8 +166,19 @@ pub struct ServiceExtension<'a> {
     pub ext_uris: Option<Vec<StringValue<'a>>>,
 }
 
+impl ServiceExtension<'_> {
+    /// Converts into a `ServiceExtension` that owns its data
+    pub fn into_owned(self) -> ServiceExtension<'static> {
+        ServiceExtension {
+            ext_uris: self
+                .ext_uris
+                .map(|uris| uris.into_iter().map(StringValue::into_owned).collect()),
+        }
+    }
+}
+
 /// The <svcs> type in EPP XML
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub struct Services<'a> {
     /// The service URIs being used by this EPP session represented by <objURI> in EPP XML
     #[serde(rename = "objURI")]
@@ -162,6 +188,20 @@ pub struct Services<'a> {
     pub svc_ext: Option<ServiceExtension<'a>>,
 }
 
+impl Services<'_> {
+    /// Converts into `Services` that own their data
+    pub fn into_owned(self) -> Services<'static> {
+        Services {
+            obj_uris: self
+                .obj_uris
+                .into_iter()
+                .map(StringValue::into_owned)
+                .collect(),
+            svc_ext: self.svc_ext.map(ServiceExtension::into_owned),
+        }
+    }
+}
+
 /// The &lt;hostAddr&gt; types domain or host transactions
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct HostAddr<'a> {
@@ -202,12 +242,179 @@ where
     seq.end()
 }
 
-/// The &lt;status&gt; type on contact transactions
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ObjectStatus<'a> {
-    /// The status name, represented by the 's' attr on &lt;status&gt; tags
+/// Serializes a primitive value as the text of an element named after the field
+///
+/// Plain primitive fields are serialized as attributes, which doesn't work for numeric or
+/// boolean elements like `<secDNS:keyTag>`.
+pub(crate) fn serialize_element<T: Serialize, S>(value: &T, ser: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    ser.serialize_newtype_struct("element", value)
+}
+
+pub(crate) fn serialize_element_option<T: Serialize, S>(
+    value: &Option<T>,
+    ser: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    match value {
+        Some(value) => serialize_element(value, ser),
+        None => ser.serialize_none(),
+    }
+}
+
+/// The &lt;status&gt; type on domain, contact and host transactions
+///
+/// `S` is the enum of statuses for the object, such as [`DomainStatus`].
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct ObjectStatus<S> {
+    /// The status, represented by the 's' attr on &lt;status&gt; tags
     #[serde(rename = "s")]
-    pub status: Cow<'a, str>,
+    pub status: S,
+    /// The language of the reason, represented by the 'lang' attr on &lt;status&gt; tags
+    pub lang: Option<String>,
+    /// The reason for the status, represented by the inner text of &lt;status&gt; tags
+    #[serde(rename = "$value")]
+    pub reason: Option<String>,
+}
+
+impl<S> ObjectStatus<S> {
+    /// Creates a new ObjectStatus instance without a reason
+    pub fn new(status: S) -> Self {
+        Self {
+            status,
+            lang: None,
+            reason: None,
+        }
+    }
+
+    /// Sets the reason for the status, and optionally the language it is written in
+    pub fn reason(mut self, reason: &str, lang: Option<&str>) -> Self {
+        self.reason = Some(reason.to_owned());
+        self.lang = lang.map(str::to_owned);
+        self
+    }
+}
+
+impl<S> From<S> for ObjectStatus<S> {
+    fn from(status: S) -> Self {
+        Self::new(status)
+    }
+}
+
+/// Defines an enum of statuses from a table of variants and the values of their 's' attr
+///
+/// The enum gets an `Other` variant for statuses that are not in the table, so that statuses
+/// added by later RFCs or by a registry are kept rather than failing the response.
+macro_rules! status_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident ($rfc:literal) {
+            $($variant:ident => $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+        pub enum $name {
+            $($variant,)*
+            #[doc = concat!("A status not described in ", $rfc, ", kept as it was received")]
+            Other(String),
+        }
+
+        impl $name {
+            /// The value of the 's' attr
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Other(status) => status,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(s: &str) -> Self {
+                match s {
+                    $($value => Self::$variant,)*
+                    other => Self::Other(other.to_owned()),
+                }
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let status = <String as serde::Deserialize>::deserialize(deserializer)?;
+                Ok(Self::from(status.as_str()))
+            }
+        }
+    };
+}
+
+pub(crate) use status_enum;
+
+status_enum! {
+    /// Domain statuses, as described in section 2.3 of RFC 5731
+    pub enum DomainStatus ("RFC 5731") {
+        ClientDeleteProhibited => "clientDeleteProhibited",
+        ClientHold => "clientHold",
+        ClientRenewProhibited => "clientRenewProhibited",
+        ClientTransferProhibited => "clientTransferProhibited",
+        ClientUpdateProhibited => "clientUpdateProhibited",
+        Inactive => "inactive",
+        Ok => "ok",
+        PendingCreate => "pendingCreate",
+        PendingDelete => "pendingDelete",
+        PendingRenew => "pendingRenew",
+        PendingTransfer => "pendingTransfer",
+        PendingUpdate => "pendingUpdate",
+        ServerDeleteProhibited => "serverDeleteProhibited",
+        ServerHold => "serverHold",
+        ServerRenewProhibited => "serverRenewProhibited",
+        ServerTransferProhibited => "serverTransferProhibited",
+        ServerUpdateProhibited => "serverUpdateProhibited",
+    }
+}
+
+status_enum! {
+    /// Contact statuses, as described in section 2.2 of RFC 5733
+    pub enum ContactStatus ("RFC 5733") {
+        ClientDeleteProhibited => "clientDeleteProhibited",
+        ClientTransferProhibited => "clientTransferProhibited",
+        ClientUpdateProhibited => "clientUpdateProhibited",
+        Linked => "linked",
+        Ok => "ok",
+        PendingCreate => "pendingCreate",
+        PendingDelete => "pendingDelete",
+        PendingTransfer => "pendingTransfer",
+        PendingUpdate => "pendingUpdate",
+        ServerDeleteProhibited => "serverDeleteProhibited",
+        ServerTransferProhibited => "serverTransferProhibited",
+        ServerUpdateProhibited => "serverUpdateProhibited",
+    }
+}
+
+status_enum! {
+    /// Host statuses, as described in section 2.3 of RFC 5732
+    pub enum HostStatus ("RFC 5732") {
+        ClientDeleteProhibited => "clientDeleteProhibited",
+        ClientUpdateProhibited => "clientUpdateProhibited",
+        Linked => "linked",
+        Ok => "ok",
+        PendingCreate => "pendingCreate",
+        PendingDelete => "pendingDelete",
+        PendingTransfer => "pendingTransfer",
+        PendingUpdate => "pendingUpdate",
+        ServerDeleteProhibited => "serverDeleteProhibited",
+        ServerUpdateProhibited => "serverUpdateProhibited",
+    }
 }
 
 /// This type contains a single DER-encoded X.509 certificate.
@@ -4,11 +4,13 @@ use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::Duration;
-use std::{io, mem, str, u32};
+use std::{io, mem, str};
 
 use async_trait::async_trait;
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
-use tracing::{debug, info};
+use tokio::net::TcpStream;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
 
 use crate::error::Error;
 
@@ -29,6 +31,15 @@ pub(crate) struct EppConnection<C: Connector> {
     // If we get a request while another request is in flight (because its future was dropped),
     // we will store it here until the current request is finished.
     next: Option<RequestState>,
+    // Whether a request timed out
+    //
+    // The response to a timed out request might still arrive, so the connection has to be
+    // reconnected before it can be used again.
+    poisoned: bool,
+    // When the last response was read
+    last_activity: Instant,
+    // The extension URIs negotiated by a successful login on this connection
+    pub ext_uris: Option<Vec<String>>,
 }
 
 impl<C: Connector> EppConnection<C> {
@@ -45,6 +56,9 @@ impl<C: Connector> EppConnection<C> {
             timeout,
             current: None,
             next: None,
+            poisoned: false,
+            last_activity: Instant::now(),
+            ext_uris: None,
         };
 
         this.read_greeting().await?;
@@ -58,21 +72,49 @@ impl<C: Connector> EppConnection<C> {
             buf: vec![0; 256],
         });
 
-        self.greeting = RequestFuture { conn: self }.await?;
+        self.greeting = timeout(self.timeout, RequestFuture { conn: self }).await?;
+        self.last_activity = Instant::now();
         Ok(())
     }
 
+    /// How long the connection has been idle since the last response
+    pub(crate) fn idle_time(&self) -> Duration {
+        self.last_activity.elapsed()
+    }
+
     pub(crate) async fn reconnect(&mut self) -> Result<(), Error> {
         debug!("{}: reconnecting", self.registry);
         let _ = self.current.take();
         let _ = self.next.take();
         self.stream = self.connector.connect(self.timeout).await?;
+        self.poisoned = false;
+        self.ext_uris = None;
         self.read_greeting().await?;
         Ok(())
     }
 
+    /// Whether a request timed out, so the connection has to be reconnected before it is used
+    pub(crate) fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
     /// Sends an EPP XML request to the registry and returns the response
-    pub(crate) fn transact<'a>(&'a mut self, command: &str) -> Result<RequestFuture<'a, C>, Error> {
+    ///
+    /// Fails with `Error::Timeout` if the response has not been read within `timeout` (or the
+    /// connection's default timeout). The connection is then poisoned, and the client has to
+    /// reconnect it before the next request is sent; a request on a poisoned connection fails
+    /// without being sent.
+    pub(crate) async fn transact(
+        &mut self,
+        command: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String, Error> {
+        if self.poisoned {
+            return Err(Error::Other(
+                "the connection has to be reconnected after a request timed out".into(),
+            ));
+        }
+
         let new = RequestState::new(command)?;
 
         // If we have a request currently in flight, finish that first
@@ -88,7 +130,18 @@ impl<C: Connector> EppConnection<C> {
             false => self.current = Some(new),
         }
 
-        Ok(RequestFuture { conn: self })
+        let timeout = timeout.unwrap_or(self.timeout);
+        match tokio::time::timeout(timeout, RequestFuture { conn: self }).await {
+            Ok(result) => {
+                self.last_activity = Instant::now();
+                result
+            }
+            Err(_) => {
+                warn!("{}: request timed out after {:?}", self.registry, timeout);
+                self.poisoned = true;
+                Err(Error::Timeout)
+            }
+        }
     }
 
     /// Closes the socket and shuts down the connection
@@ -179,31 +232,34 @@ impl<C: Connector> EppConnection<C> {
                 buf,
                 expected,
             } => {
-                let mut read_buf = ReadBuf::new(&mut buf[read..]);
-                match Pin::new(&mut self.stream).poll_read(cx, &mut read_buf) {
-                    Poll::Ready(Ok(())) => {}
-                    Poll::Ready(Err(err)) => return Err(err.into()),
-                    Poll::Pending => return Ok(Transition::Pending(state)),
+                // The whole response may already have been read along with the frame header
+                if read < *expected {
+                    let mut read_buf = ReadBuf::new(&mut buf[read..]);
+                    match Pin::new(&mut self.stream).poll_read(cx, &mut read_buf) {
+                        Poll::Ready(Ok(())) => {}
+                        Poll::Ready(Err(err)) => return Err(err.into()),
+                        Poll::Pending => return Ok(Transition::Pending(state)),
+                    }
+
+                    let filled = read_buf.filled();
+                    if filled.is_empty() {
+                        return Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            format!("{}: Unexpected EOF while reading", self.registry),
+                        )
+                        .into());
+                    }
+
+                    read += filled.len();
+                    debug!(
+                        "{}: Read {} bytes, {} out of {} done",
+                        self.registry,
+                        filled.len(),
+                        read,
+                        expected
+                    );
                 }
 
-                let filled = read_buf.filled();
-                if filled.is_empty() {
-                    return Err(io::Error::new(
-                        io::ErrorKind::UnexpectedEof,
-                        format!("{}: Unexpected EOF while reading", self.registry),
-                    )
-                    .into());
-                }
-
-                read += filled.len();
-                debug!(
-                    "{}: Read {} bytes, {} out of {} done",
-                    self.registry,
-                    filled.len(),
-                    read,
-                    expected
-                );
-
                 //
 
                 Ok(if read < *expected {
@@ -325,3 +381,75 @@ pub trait Connector {
 
     async fn connect(&self, timeout: Duration) -> Result<Self::Connection, Error>;
 }
+
+/// A [`Connector`] that can set up its connection over an established TCP stream
+///
+/// This allows the TCP stream to be set up elsewhere, e.g. through a proxy by the
+/// [`ProxyConnector`](crate::proxy::ProxyConnector).
+#[async_trait]
+pub trait StreamConnector: Connector {
+    /// The host name and port that the connection is made to
+    fn server(&self) -> &(String, u16);
+
+    /// Set up the connection over `stream`, e.g. by doing the TLS handshake
+    async fn connect_stream(
+        &self,
+        stream: TcpStream,
+        timeout: Duration,
+    ) -> Result<Self::Connection, Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use tokio_test::io::{Builder, Mock};
+
+    use super::{Connector, EppConnection};
+    use crate::error::Error;
+
+    const GREETING: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?><epp xmlns="urn:ietf:params:xml:ns:epp-1.0"><greeting/></epp>"#;
+
+    fn frame(xml: &str) -> Vec<u8> {
+        let mut frame = ((xml.len() + 4) as u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(xml.as_bytes());
+        frame
+    }
+
+    struct FakeConnector(Vec<Vec<u8>>);
+
+    #[async_trait]
+    impl Connector for FakeConnector {
+        type Connection = Mock;
+
+        async fn connect(&self, _: Duration) -> Result<Self::Connection, Error> {
+            let mut builder = Builder::new();
+            for chunk in &self.0 {
+                builder.read(chunk);
+            }
+            Ok(builder.build())
+        }
+    }
+
+    #[tokio::test]
+    async fn frame_in_one_read() {
+        // The header and the whole greeting fit in the first read
+        let connector = FakeConnector(vec![frame(GREETING)]);
+        let conn = EppConnection::new(connector, "test".into(), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(conn.greeting, GREETING);
+    }
+
+    #[tokio::test]
+    async fn frame_in_pieces() {
+        let frame = frame(GREETING);
+        let (header, body) = frame.split_at(10);
+        let connector = FakeConnector(vec![header.to_vec(), body.to_vec()]);
+        let conn = EppConnection::new(connector, "test".into(), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(conn.greeting, GREETING);
+    }
+}
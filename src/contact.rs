@@ -1,27 +1,41 @@
 use std::borrow::Cow;
 use std::str::FromStr;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use crate::common::StringValue;
+use crate::Error;
 
 pub mod check;
-pub use check::ContactCheck;
+pub use check::{ContactCheck, OwnedContactCheck};
 
 pub mod create;
-pub use create::ContactCreate;
+pub use create::{ContactCreate, ContactCreateBuilder, OwnedContactCreate};
 
 pub mod delete;
-pub use delete::ContactDelete;
+pub use delete::{ContactDelete, OwnedContactDelete};
 
 pub mod info;
-pub use info::ContactInfo;
+pub use info::{ContactInfo, OwnedContactInfo};
+
+pub mod transfer;
+pub use transfer::{ContactTransfer, OwnedContactTransfer};
 
 pub mod update;
-pub use update::ContactUpdate;
+pub use update::{ContactUpdate, ContactUpdateBuilder, OwnedContactUpdate};
 
 pub const XMLNS: &str = "urn:ietf:params:xml:ns:contact-1.0";
 
+/// Checks a contact id for an owned command, which must be 3 to 16 characters long
+fn validate_id(id: &str) -> Result<(), Error> {
+    match id.chars().count() {
+        3..=16 => Ok(()),
+        _ => Err(Error::Other(
+            format!("contact id {id:?} must be 3 to 16 characters long").into(),
+        )),
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Country(celes::Country);
 
@@ -82,6 +96,14 @@ impl<'a> Phone<'a> {
     pub fn set_extension(&mut self, ext: &'a str) {
         self.extension = Some(ext.into());
     }
+
+    /// Converts into a `Phone` that owns its data
+    pub fn into_owned(self) -> Phone<'static> {
+        Phone {
+            number: self.number.into_owned().into(),
+            extension: self.extension.map(|ext| ext.into_owned().into()),
+        }
+    }
 }
 
 /// The &lt;addr&gt; type on contact transactions
@@ -123,6 +145,21 @@ impl<'a> Address<'a> {
             country,
         }
     }
+
+    /// Converts into an `Address` that owns its data
+    pub fn into_owned(self) -> Address<'static> {
+        Address {
+            street: self
+                .street
+                .into_iter()
+                .map(StringValue::into_owned)
+                .collect(),
+            city: self.city.into_owned(),
+            province: self.province.into_owned(),
+            postal_code: self.postal_code.into_owned(),
+            country: self.country,
+        }
+    }
 }
 
 /// The &lt;postalInfo&gt; type on contact transactions
@@ -157,4 +194,132 @@ impl<'a> PostalInfo<'a> {
             address,
         }
     }
+
+    /// Converts into a `PostalInfo` that owns its data
+    pub fn into_owned(self) -> PostalInfo<'static> {
+        PostalInfo {
+            info_type: self.info_type,
+            name: self.name.into_owned(),
+            organization: self.organization.into_owned(),
+            address: self.address.into_owned(),
+        }
+    }
+}
+
+/// The 'type' attr on &lt;postalInfo&gt; and on the &lt;name&gt;, &lt;org&gt; and &lt;addr&gt;
+/// tags under &lt;disclose&gt;
+#[derive(Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PostalInfoType {
+    /// Internationalized form, using only 7-bit ASCII characters
+    Int,
+    /// Localized form, which may use UTF-8 characters
+    Loc,
+}
+
+impl PostalInfoType {
+    /// The value of the 'type' attr
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Int => "int",
+            Self::Loc => "loc",
+        }
+    }
+}
+
+impl Serialize for PostalInfoType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// The &lt;name&gt;, &lt;org&gt; and &lt;addr&gt; tags under &lt;disclose&gt;
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DisclosePostalField {
+    /// The 'type' attr, selecting the form of postal info the preference applies to
+    #[serde(rename = "type")]
+    pub info_type: PostalInfoType,
+}
+
+/// The &lt;voice&gt;, &lt;fax&gt; and &lt;email&gt; tags under &lt;disclose&gt;
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct DiscloseField {}
+
+/// The &lt;disclose&gt; type on contact transactions
+///
+/// Lists the fields that the server should (`flag` is true) or should not (`flag` is false)
+/// disclose to third parties, departing from the server's data collection policy.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Disclose {
+    /// The 'flag' attr on &lt;disclose&gt;
+    pub flag: bool,
+    /// The &lt;name&gt; tags under &lt;disclose&gt;
+    #[serde(rename = "contact:name", alias = "name", default)]
+    pub name: Vec<DisclosePostalField>,
+    /// The &lt;org&gt; tags under &lt;disclose&gt;
+    #[serde(rename = "contact:org", alias = "org", default)]
+    pub organization: Vec<DisclosePostalField>,
+    /// The &lt;addr&gt; tags under &lt;disclose&gt;
+    #[serde(rename = "contact:addr", alias = "addr", default)]
+    pub address: Vec<DisclosePostalField>,
+    /// The &lt;voice&gt; tag under &lt;disclose&gt;
+    #[serde(rename = "contact:voice", alias = "voice")]
+    pub voice: Option<DiscloseField>,
+    /// The &lt;fax&gt; tag under &lt;disclose&gt;
+    #[serde(rename = "contact:fax", alias = "fax")]
+    pub fax: Option<DiscloseField>,
+    /// The &lt;email&gt; tag under &lt;disclose&gt;
+    #[serde(rename = "contact:email", alias = "email")]
+    pub email: Option<DiscloseField>,
+}
+
+impl Disclose {
+    /// Creates a new Disclose instance without any fields
+    pub fn new(flag: bool) -> Self {
+        Self {
+            flag,
+            name: Vec::new(),
+            organization: Vec::new(),
+            address: Vec::new(),
+            voice: None,
+            fax: None,
+            email: None,
+        }
+    }
+
+    /// Adds the &lt;name&gt; field of the given postal info type
+    pub fn name(mut self, info_type: PostalInfoType) -> Self {
+        self.name.push(DisclosePostalField { info_type });
+        self
+    }
+
+    /// Adds the &lt;org&gt; field of the given postal info type
+    pub fn organization(mut self, info_type: PostalInfoType) -> Self {
+        self.organization.push(DisclosePostalField { info_type });
+        self
+    }
+
+    /// Adds the &lt;addr&gt; field of the given postal info type
+    pub fn address(mut self, info_type: PostalInfoType) -> Self {
+        self.address.push(DisclosePostalField { info_type });
+        self
+    }
+
+    /// Adds the &lt;voice&gt; field
+    pub fn voice(mut self) -> Self {
+        self.voice = Some(DiscloseField {});
+        self
+    }
+
+    /// Adds the &lt;fax&gt; field
+    pub fn fax(mut self) -> Self {
+        self.fax = Some(DiscloseField {});
+        self
+    }
+
+    /// Adds the &lt;email&gt; field
+    pub fn email(mut self) -> Self {
+        self.email = Some(DiscloseField {});
+        self
+    }
 }
@@ -1,10 +1,11 @@
 use std::fmt::Debug;
 
 /// Types for EPP contact check request
-use super::XMLNS;
+use super::{validate_id, XMLNS};
 use crate::common::{CheckResponse, NoExtension, StringValue};
-use crate::request::{Command, Transaction};
-use serde::Serialize;
+use crate::request::{Command, Extension, Transaction};
+use crate::Error;
+use serde::{Serialize, Serializer};
 
 impl<'a> Transaction<NoExtension> for ContactCheck<'a> {}
 
@@ -13,6 +14,13 @@ impl<'a> Command for ContactCheck<'a> {
     const COMMAND: &'static str = "check";
 }
 
+impl<E: Extension> Transaction<E> for OwnedContactCheck where ContactCheck<'static>: Transaction<E> {}
+
+impl Command for OwnedContactCheck {
+    type Response = CheckResponse;
+    const COMMAND: &'static str = "check";
+}
+
 // Request
 
 /// Type that represents the &lt;check&gt; command for contact transactions
@@ -52,9 +60,51 @@ pub struct ContactCheck<'a> {
     pub contact_ids: &'a [&'a str],
 }
 
+/// An owned [`ContactCheck`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedContactCheck {
+    contact_ids: Vec<String>,
+}
+
+impl OwnedContactCheck {
+    /// Creates a check for `contact_ids`, failing if there are none or one of them is invalid
+    pub fn new<I>(contact_ids: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let contact_ids = contact_ids.into_iter().map(Into::into).collect::<Vec<_>>();
+        if contact_ids.is_empty() {
+            return Err(Error::Other("at least one contact must be checked".into()));
+        }
+
+        for id in &contact_ids {
+            validate_id(id)?;
+        }
+
+        Ok(Self { contact_ids })
+    }
+}
+
+impl Serialize for OwnedContactCheck {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeContactCheck {
+            list: ContactList {
+                xmlns: XMLNS,
+                contact_ids: self
+                    .contact_ids
+                    .iter()
+                    .map(|id| id.as_str().into())
+                    .collect(),
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ContactCheck;
+    use super::{ContactCheck, OwnedContactCheck};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -66,6 +116,15 @@ mod tests {
         assert_serialized("request/contact/check.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedContactCheck::new(["eppdev-contact-1", "eppdev-contact-2"]).unwrap();
+        assert_serialized("request/contact/check.xml", &object);
+
+        assert!(OwnedContactCheck::new(Vec::<String>::new()).is_err());
+        assert!(OwnedContactCheck::new(["eppdev-contact-with-a-long-id"]).is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<ContactCheck>("response/contact/check.xml");
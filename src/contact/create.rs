@@ -1,11 +1,12 @@
 //! Types for EPP contact create request
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-use super::{ContactAuthInfo, Phone, PostalInfo, XMLNS};
+use super::{validate_id, ContactAuthInfo, Disclose, Phone, PostalInfo, XMLNS};
 use crate::common::{NoExtension, StringValue};
-use crate::request::{Command, Transaction};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
 
 impl<'a> Transaction<NoExtension> for ContactCreate<'a> {}
 
@@ -14,6 +15,14 @@ impl<'a> Command for ContactCreate<'a> {
     const COMMAND: &'static str = "create";
 }
 
+impl<E: Extension> Transaction<E> for OwnedContactCreate where ContactCreate<'static>: Transaction<E>
+{}
+
+impl Command for OwnedContactCreate {
+    type Response = ContactCreateResponse;
+    const COMMAND: &'static str = "create";
+}
+
 // Request
 
 /// Type for elements under the contact &lt;create&gt; tag
@@ -40,6 +49,9 @@ pub struct Contact<'a> {
     /// Contact &lt;authInfo&gt; tag
     #[serde(rename = "contact:authInfo")]
     auth_info: ContactAuthInfo<'a>,
+    /// Contact &lt;disclose&gt; tag
+    #[serde(rename = "contact:disclose")]
+    disclose: Option<Disclose>,
 }
 
 #[derive(Serialize, Debug)]
@@ -67,6 +79,7 @@ impl<'a> ContactCreate<'a> {
                 fax: None,
                 email: email.into(),
                 auth_info: ContactAuthInfo::new(auth_password),
+                disclose: None,
             },
         }
     }
@@ -75,6 +88,132 @@ impl<'a> ContactCreate<'a> {
     pub fn set_fax(&mut self, fax: Phone<'a>) {
         self.contact.fax = Some(fax);
     }
+
+    /// Sets the &lt;disclose&gt; data for the request
+    pub fn set_disclose(&mut self, disclose: Disclose) {
+        self.contact.disclose = Some(disclose);
+    }
+}
+
+/// An owned [`ContactCreate`], which can be kept around or sent to another task
+///
+/// Use [`OwnedContactCreate::builder()`] to create one.
+#[derive(Clone, Debug)]
+pub struct OwnedContactCreate {
+    id: String,
+    email: String,
+    postal_info: PostalInfo<'static>,
+    voice: Phone<'static>,
+    fax: Option<Phone<'static>>,
+    auth_password: String,
+    disclose: Option<Disclose>,
+}
+
+impl OwnedContactCreate {
+    /// Starts building a create command for the contact `id`
+    pub fn builder(id: impl Into<String>) -> ContactCreateBuilder {
+        ContactCreateBuilder {
+            id: id.into(),
+            email: None,
+            postal_info: None,
+            voice: None,
+            fax: None,
+            auth_password: None,
+            disclose: None,
+        }
+    }
+}
+
+impl Serialize for OwnedContactCreate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut create = ContactCreate::new(
+            &self.id,
+            &self.email,
+            self.postal_info.clone(),
+            self.voice.clone(),
+            &self.auth_password,
+        );
+        create.contact.fax = self.fax.clone();
+        create.contact.disclose = self.disclose.clone();
+        create.serialize(serializer)
+    }
+}
+
+/// Builder for an [`OwnedContactCreate`]
+///
+/// The email, postal info, voice number and auth password are required.
+#[derive(Clone, Debug)]
+pub struct ContactCreateBuilder {
+    id: String,
+    email: Option<String>,
+    postal_info: Option<PostalInfo<'static>>,
+    voice: Option<Phone<'static>>,
+    fax: Option<Phone<'static>>,
+    auth_password: Option<String>,
+    disclose: Option<Disclose>,
+}
+
+impl ContactCreateBuilder {
+    /// Sets the email address
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Sets the postal info
+    pub fn postal_info(mut self, postal_info: PostalInfo<'_>) -> Self {
+        self.postal_info = Some(postal_info.into_owned());
+        self
+    }
+
+    /// Sets the voice number
+    pub fn voice(mut self, voice: Phone<'_>) -> Self {
+        self.voice = Some(voice.into_owned());
+        self
+    }
+
+    /// Sets the fax number
+    pub fn fax(mut self, fax: Phone<'_>) -> Self {
+        self.fax = Some(fax.into_owned());
+        self
+    }
+
+    /// Sets the password of the contact's auth info
+    pub fn auth_password(mut self, password: impl Into<String>) -> Self {
+        self.auth_password = Some(password.into());
+        self
+    }
+
+    /// Sets the disclosure preferences
+    pub fn disclose(mut self, disclose: Disclose) -> Self {
+        self.disclose = Some(disclose);
+        self
+    }
+
+    /// Checks the command and builds it
+    pub fn build(self) -> Result<OwnedContactCreate, Error> {
+        validate_id(&self.id)?;
+        let email = self
+            .email
+            .ok_or_else(|| Error::Other("email is required".into()))?;
+        required("email", &email)?;
+
+        Ok(OwnedContactCreate {
+            id: self.id,
+            email,
+            postal_info: self
+                .postal_info
+                .ok_or_else(|| Error::Other("postal info is required".into()))?,
+            voice: self
+                .voice
+                .ok_or_else(|| Error::Other("voice number is required".into()))?,
+            fax: self.fax,
+            auth_password: self
+                .auth_password
+                .ok_or_else(|| Error::Other("auth password is required".into()))?,
+            disclose: self.disclose,
+        })
+    }
 }
 
 // Response
@@ -101,8 +240,8 @@ pub struct ContactCreateResponse {
 mod tests {
     use chrono::{TimeZone, Utc};
 
-    use super::{ContactCreate, Phone, PostalInfo};
-    use crate::contact::Address;
+    use super::{ContactCreate, OwnedContactCreate, Phone, PostalInfo};
+    use crate::contact::{Address, Disclose, PostalInfoType};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -128,6 +267,74 @@ mod tests {
         assert_serialized("request/contact/create.xml", &object);
     }
 
+    #[test]
+    fn command_disclose() {
+        let street = &["58", "Orchid Road"];
+        let address = Address::new(street, "Paris", "Paris", "392374", "FR".parse().unwrap());
+        let postal_info = PostalInfo::new("int", "John Doe", "Acme Widgets", address);
+        let mut voice = Phone::new("+33.47237942");
+        voice.set_extension("123");
+        let mut fax = Phone::new("+33.86698799");
+        fax.set_extension("677");
+
+        let mut object = ContactCreate::new(
+            "eppdev-contact-3",
+            "contact@eppdev.net",
+            postal_info,
+            voice,
+            "eppdev-387323",
+        );
+        object.set_fax(fax);
+        object.set_disclose(
+            Disclose::new(false)
+                .name(PostalInfoType::Int)
+                .address(PostalInfoType::Int)
+                .address(PostalInfoType::Loc)
+                .voice()
+                .email(),
+        );
+
+        assert_serialized("request/contact/create_disclose.xml", &object);
+    }
+
+    #[test]
+    fn owned_command() {
+        let street = &["58", "Orchid Road"];
+        let address = Address::new(street, "Paris", "Paris", "392374", "FR".parse().unwrap());
+        let mut voice = Phone::new("+33.47237942");
+        voice.set_extension("123");
+        let mut fax = Phone::new("+33.86698799");
+        fax.set_extension("677");
+
+        let builder = OwnedContactCreate::builder("eppdev-contact-3")
+            .email("contact@eppdev.net")
+            .postal_info(PostalInfo::new("int", "John Doe", "Acme Widgets", address))
+            .voice(voice)
+            .fax(fax);
+        assert!(builder.clone().build().is_err());
+
+        let object = builder
+            .clone()
+            .auth_password("eppdev-387323")
+            .build()
+            .unwrap();
+        assert_serialized("request/contact/create.xml", &object);
+
+        let object = builder
+            .auth_password("eppdev-387323")
+            .disclose(
+                Disclose::new(false)
+                    .name(PostalInfoType::Int)
+                    .address(PostalInfoType::Int)
+                    .address(PostalInfoType::Loc)
+                    .voice()
+                    .email(),
+            )
+            .build()
+            .unwrap();
+        assert_serialized("request/contact/create_disclose.xml", &object);
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<ContactCreate>("response/contact/create.xml");
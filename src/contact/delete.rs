@@ -1,9 +1,10 @@
 //! Types for EPP contact delete request
 
-use super::XMLNS;
+use super::{validate_id, XMLNS};
 use crate::common::{NoExtension, StringValue};
-use crate::request::{Command, Transaction};
-use serde::Serialize;
+use crate::request::{Command, Extension, Transaction};
+use crate::Error;
+use serde::{Serialize, Serializer};
 
 impl<'a> Transaction<NoExtension> for ContactDelete<'a> {}
 
@@ -12,6 +13,14 @@ impl<'a> Command for ContactDelete<'a> {
     const COMMAND: &'static str = "delete";
 }
 
+impl<E: Extension> Transaction<E> for OwnedContactDelete where ContactDelete<'static>: Transaction<E>
+{}
+
+impl Command for OwnedContactDelete {
+    type Response = ();
+    const COMMAND: &'static str = "delete";
+}
+
 /// Type containing the data for the &lt;delete&gt; tag for contacts
 #[derive(Serialize, Debug)]
 pub struct ContactDeleteRequestData<'a> {
@@ -42,9 +51,29 @@ impl<'a> ContactDelete<'a> {
     }
 }
 
+/// An owned [`ContactDelete`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedContactDelete {
+    id: String,
+}
+
+impl OwnedContactDelete {
+    pub fn new(id: impl Into<String>) -> Result<Self, Error> {
+        let id = id.into();
+        validate_id(&id)?;
+        Ok(Self { id })
+    }
+}
+
+impl Serialize for OwnedContactDelete {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ContactDelete::new(&self.id).serialize(serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ContactDelete;
+    use super::{ContactDelete, OwnedContactDelete};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -54,6 +83,13 @@ mod tests {
         assert_serialized("request/contact/delete.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedContactDelete::new("eppdev-contact-3").unwrap();
+        assert_serialized("request/contact/delete.xml", &object);
+        assert!(OwnedContactDelete::new("").is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<ContactDelete>("response/contact/delete.xml");
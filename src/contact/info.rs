@@ -1,11 +1,12 @@
 //! Types for EPP contact info request
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-use super::{ContactAuthInfo, Phone, PostalInfo, XMLNS};
-use crate::common::{NoExtension, ObjectStatus, StringValue};
-use crate::request::{Command, Transaction};
+use super::{validate_id, ContactAuthInfo, Disclose, Phone, PostalInfo, XMLNS};
+use crate::common::{ContactStatus, NoExtension, ObjectStatus, StringValue};
+use crate::request::{Command, Extension, Transaction};
+use crate::Error;
 
 impl<'a> Transaction<NoExtension> for ContactInfo<'a> {}
 
@@ -14,6 +15,13 @@ impl<'a> Command for ContactInfo<'a> {
     const COMMAND: &'static str = "info";
 }
 
+impl<E: Extension> Transaction<E> for OwnedContactInfo where ContactInfo<'static>: Transaction<E> {}
+
+impl Command for OwnedContactInfo {
+    type Response = ContactInfoResponse;
+    const COMMAND: &'static str = "info";
+}
+
 // Request
 
 /// Type for elements under the contact &lt;info&gt; tag
@@ -50,6 +58,30 @@ impl<'a> ContactInfo<'a> {
     }
 }
 
+/// An owned [`ContactInfo`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedContactInfo {
+    id: String,
+    auth_password: String,
+}
+
+impl OwnedContactInfo {
+    pub fn new(id: impl Into<String>, auth_password: impl Into<String>) -> Result<Self, Error> {
+        let id = id.into();
+        validate_id(&id)?;
+        Ok(Self {
+            id,
+            auth_password: auth_password.into(),
+        })
+    }
+}
+
+impl Serialize for OwnedContactInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ContactInfo::new(&self.id, &self.auth_password).serialize(serializer)
+    }
+}
+
 // Response
 
 /// Type that represents the &lt;infData&gt; tag for contact check response
@@ -61,7 +93,7 @@ pub struct ContactInfoData<'a> {
     pub roid: StringValue<'a>,
     /// The list of contact statuses
     #[serde(rename = "status")]
-    pub statuses: Vec<ObjectStatus<'a>>,
+    pub statuses: Vec<ObjectStatus<ContactStatus>>,
     /// The postal info for the contact
     #[serde(rename = "postalInfo")]
     pub postal_info: PostalInfo<'a>,
@@ -92,6 +124,8 @@ pub struct ContactInfoData<'a> {
     /// The contact auth info
     #[serde(rename = "authInfo")]
     pub auth_info: Option<ContactAuthInfo<'a>>,
+    /// The contact disclosure preferences
+    pub disclose: Option<Disclose>,
 }
 
 /// Type that represents the &lt;resData&gt; tag for contact info response
@@ -106,7 +140,9 @@ pub struct ContactInfoResponse {
 mod tests {
     use chrono::{TimeZone, Utc};
 
-    use super::ContactInfo;
+    use super::{ContactInfo, OwnedContactInfo};
+    use crate::common::ContactStatus;
+    use crate::contact::{Disclose, PostalInfoType};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -116,6 +152,13 @@ mod tests {
         assert_serialized("request/contact/info.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedContactInfo::new("eppdev-contact-3", "eppdev-387323").unwrap();
+        assert_serialized("request/contact/info.xml", &object);
+        assert!(OwnedContactInfo::new("c1", "eppdev-387323").is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<ContactInfo>("response/contact/info.xml");
@@ -130,7 +173,7 @@ mod tests {
         assert_eq!(object.result.message, SUCCESS_MSG.into());
         assert_eq!(result.info_data.id, "eppdev-contact-3".into());
         assert_eq!(result.info_data.roid, "UNDEF-ROID".into());
-        assert_eq!(result.info_data.statuses[0].status, "ok".to_string());
+        assert_eq!(result.info_data.statuses[0].status, ContactStatus::Ok);
         assert_eq!(result.info_data.postal_info.info_type, "loc".to_string());
         assert_eq!(result.info_data.postal_info.name, "John Doe".into());
         assert_eq!(
@@ -175,4 +218,21 @@ mod tests {
         assert_eq!(object.tr_ids.client_tr_id.unwrap(), CLTRID.into());
         assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
     }
+
+    #[test]
+    fn response_disclose() {
+        let object = response_from_file::<ContactInfo>("response/contact/info_disclose.xml");
+
+        let result = object.res_data().unwrap();
+        assert_eq!(result.info_data.id, "eppdev-contact-3".into());
+        assert_eq!(
+            result.info_data.disclose,
+            Some(
+                Disclose::new(false)
+                    .name(PostalInfoType::Int)
+                    .voice()
+                    .email()
+            )
+        );
+    }
 }
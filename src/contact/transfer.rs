@@ -0,0 +1,296 @@
+//! Types for EPP contact transfer request
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::{validate_id, ContactAuthInfo, XMLNS};
+use crate::common::{NoExtension, StringValue};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
+
+impl<'a> Transaction<NoExtension> for ContactTransfer<'a> {}
+
+impl<'a> Command for ContactTransfer<'a> {
+    type Response = ContactTransferResponse;
+    const COMMAND: &'static str = "transfer";
+}
+
+impl<E: Extension> Transaction<E> for OwnedContactTransfer where
+    ContactTransfer<'static>: Transaction<E>
+{
+}
+
+impl Command for OwnedContactTransfer {
+    type Response = ContactTransferResponse;
+    const COMMAND: &'static str = "transfer";
+}
+
+impl<'a> ContactTransfer<'a> {
+    pub fn new(id: &'a str, auth_password: &'a str) -> Self {
+        Self::build("request", id, Some(ContactAuthInfo::new(auth_password)))
+    }
+
+    pub fn query(id: &'a str, auth_password: &'a str) -> Self {
+        Self::build("query", id, Some(ContactAuthInfo::new(auth_password)))
+    }
+
+    pub fn approve(id: &'a str) -> Self {
+        Self::build("approve", id, None)
+    }
+
+    pub fn reject(id: &'a str) -> Self {
+        Self::build("reject", id, None)
+    }
+
+    pub fn cancel(id: &'a str) -> Self {
+        Self::build("cancel", id, None)
+    }
+
+    fn build(operation: &'a str, id: &'a str, auth_info: Option<ContactAuthInfo<'a>>) -> Self {
+        Self {
+            operation,
+            contact: ContactTransferReqData {
+                xmlns: XMLNS,
+                id: id.into(),
+                auth_info,
+            },
+        }
+    }
+}
+
+/// An owned [`ContactTransfer`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedContactTransfer {
+    operation: &'static str,
+    id: String,
+    auth_password: Option<String>,
+}
+
+impl OwnedContactTransfer {
+    pub fn new(id: impl Into<String>, auth_password: impl Into<String>) -> Result<Self, Error> {
+        Self::build("request", id.into(), Some(auth_password.into()))
+    }
+
+    pub fn query(id: impl Into<String>, auth_password: impl Into<String>) -> Result<Self, Error> {
+        Self::build("query", id.into(), Some(auth_password.into()))
+    }
+
+    pub fn approve(id: impl Into<String>) -> Result<Self, Error> {
+        Self::build("approve", id.into(), None)
+    }
+
+    pub fn reject(id: impl Into<String>) -> Result<Self, Error> {
+        Self::build("reject", id.into(), None)
+    }
+
+    pub fn cancel(id: impl Into<String>) -> Result<Self, Error> {
+        Self::build("cancel", id.into(), None)
+    }
+
+    fn build(
+        operation: &'static str,
+        id: String,
+        auth_password: Option<String>,
+    ) -> Result<Self, Error> {
+        validate_id(&id)?;
+        if let Some(password) = &auth_password {
+            required("auth password", password)?;
+        }
+
+        Ok(Self {
+            operation,
+            id,
+            auth_password,
+        })
+    }
+}
+
+impl Serialize for OwnedContactTransfer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let auth_info = self.auth_password.as_deref().map(ContactAuthInfo::new);
+        ContactTransfer::build(self.operation, &self.id, auth_info).serialize(serializer)
+    }
+}
+
+// Request
+
+/// Type for elements under the contact &lt;transfer&gt; tag
+#[derive(Serialize, Debug)]
+pub struct ContactTransferReqData<'a> {
+    /// XML namespace for contact commands
+    #[serde(rename = "xmlns:contact")]
+    xmlns: &'a str,
+    /// The id of the contact under transfer
+    #[serde(rename = "contact:id")]
+    id: StringValue<'a>,
+    /// The authInfo for the contact under transfer
+    /// Only applicable to contact transfer and contact transfer query requests
+    #[serde(rename = "contact:authInfo")]
+    auth_info: Option<ContactAuthInfo<'a>>,
+}
+
+#[derive(Serialize, Debug)]
+/// Type for EPP XML &lt;transfer&gt; command for contacts
+pub struct ContactTransfer<'a> {
+    /// The transfer operation to perform indicated by the 'op' attr
+    /// The values are one of request, query, approve, reject or cancel
+    #[serde(rename = "op")]
+    operation: &'a str,
+    /// The data under the &lt;transfer&gt; tag in the transfer request
+    #[serde(rename = "contact:transfer")]
+    contact: ContactTransferReqData<'a>,
+}
+
+// Response
+
+/// Type that represents the &lt;trnData&gt; tag for contact transfer response
+#[derive(Deserialize, Debug)]
+pub struct ContactTransferResponseData {
+    /// The contact id
+    pub id: StringValue<'static>,
+    /// The contact transfer status
+    #[serde(rename = "trStatus")]
+    pub transfer_status: StringValue<'static>,
+    /// The epp user who requested the transfer
+    #[serde(rename = "reID")]
+    pub requester_id: StringValue<'static>,
+    /// The transfer request date
+    #[serde(rename = "reDate")]
+    pub requested_at: DateTime<Utc>,
+    /// The epp user who should acknowledge the transfer request
+    #[serde(rename = "acID")]
+    pub ack_id: StringValue<'static>,
+    /// The date by which the acknowledgment should be made
+    #[serde(rename = "acDate")]
+    pub ack_by: DateTime<Utc>,
+}
+
+/// Type that represents the &lt;resData&gt; tag for contact transfer response
+#[derive(Deserialize, Debug)]
+pub struct ContactTransferResponse {
+    /// Data under the &lt;trnData&gt; tag
+    #[serde(rename = "trnData")]
+    pub transfer_data: ContactTransferResponseData,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{ContactTransfer, OwnedContactTransfer};
+    use crate::response::ResultCode;
+    use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
+
+    #[test]
+    fn request_command() {
+        let object = ContactTransfer::new("eppdev-contact-3", "eppdev-387323");
+        assert_serialized("request/contact/transfer_request.xml", &object);
+    }
+
+    #[test]
+    fn owned_commands() {
+        let object = OwnedContactTransfer::new("eppdev-contact-3", "eppdev-387323").unwrap();
+        assert_serialized("request/contact/transfer_request.xml", &object);
+
+        let object = OwnedContactTransfer::query("eppdev-contact-3", "eppdev-387323").unwrap();
+        assert_serialized("request/contact/transfer_query.xml", &object);
+
+        let object = OwnedContactTransfer::approve("eppdev-contact-3").unwrap();
+        assert_serialized("request/contact/transfer_approve.xml", &object);
+
+        let object = OwnedContactTransfer::reject("eppdev-contact-3").unwrap();
+        assert_serialized("request/contact/transfer_reject.xml", &object);
+
+        let object = OwnedContactTransfer::cancel("eppdev-contact-3").unwrap();
+        assert_serialized("request/contact/transfer_cancel.xml", &object);
+
+        assert!(OwnedContactTransfer::new("eppdev-contact-3", "").is_err());
+    }
+
+    #[test]
+    fn approve_command() {
+        let object = ContactTransfer::approve("eppdev-contact-3");
+        assert_serialized("request/contact/transfer_approve.xml", &object);
+    }
+
+    #[test]
+    fn reject_command() {
+        let object = ContactTransfer::reject("eppdev-contact-3");
+        assert_serialized("request/contact/transfer_reject.xml", &object);
+    }
+
+    #[test]
+    fn cancel_command() {
+        let object = ContactTransfer::cancel("eppdev-contact-3");
+        assert_serialized("request/contact/transfer_cancel.xml", &object);
+    }
+
+    #[test]
+    fn query_command() {
+        let object = ContactTransfer::query("eppdev-contact-3", "eppdev-387323");
+        assert_serialized("request/contact/transfer_query.xml", &object);
+    }
+
+    #[test]
+    fn request_response() {
+        let object = response_from_file::<ContactTransfer>("response/contact/transfer_request.xml");
+
+        let result = object.res_data().unwrap();
+
+        assert_eq!(
+            object.result.code,
+            ResultCode::CommandCompletedSuccessfullyActionPending
+        );
+        assert_eq!(
+            object.result.message,
+            "Command completed successfully; action pending".into()
+        );
+        assert_eq!(result.transfer_data.id, "eppdev-contact-3".into());
+        assert_eq!(result.transfer_data.transfer_status, "pending".into());
+        assert_eq!(result.transfer_data.requester_id, "eppdev".into());
+        assert_eq!(
+            result.transfer_data.requested_at,
+            Utc.with_ymd_and_hms(2021, 7, 23, 15, 31, 21).unwrap(),
+        );
+        assert_eq!(result.transfer_data.ack_id, "ClientY".into());
+        assert_eq!(
+            result.transfer_data.ack_by,
+            Utc.with_ymd_and_hms(2021, 7, 28, 15, 31, 21).unwrap()
+        );
+        assert_eq!(*object.tr_ids.client_tr_id.as_ref().unwrap(), CLTRID.into());
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+
+    #[test]
+    fn approve_response() {
+        let object = response_from_file::<ContactTransfer>("response/contact/transfer_approve.xml");
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(object.result.message, SUCCESS_MSG.into());
+        assert_eq!(object.tr_ids.client_tr_id.unwrap(), CLTRID.into());
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+
+    #[test]
+    fn query_response() {
+        let object = response_from_file::<ContactTransfer>("response/contact/transfer_query.xml");
+
+        let result = object.res_data().unwrap();
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(object.result.message, SUCCESS_MSG.into());
+        assert_eq!(result.transfer_data.id, "eppdev-contact-3".into());
+        assert_eq!(
+            result.transfer_data.transfer_status,
+            "clientApproved".into()
+        );
+        assert_eq!(result.transfer_data.requester_id, "eppdev".into());
+        assert_eq!(result.transfer_data.ack_id, "ClientY".into());
+        assert_eq!(
+            result.transfer_data.ack_by,
+            Utc.with_ymd_and_hms(2021, 7, 28, 15, 31, 21).unwrap()
+        );
+        assert_eq!(object.tr_ids.client_tr_id.unwrap(), CLTRID.into());
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+}
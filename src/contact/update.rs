@@ -1,9 +1,10 @@
 //! Types for EPP contact create request
 
-use super::{ContactAuthInfo, Phone, PostalInfo, XMLNS};
-use crate::common::{NoExtension, ObjectStatus, StringValue};
-use crate::request::{Command, Transaction};
-use serde::Serialize;
+use super::{validate_id, ContactAuthInfo, Disclose, Phone, PostalInfo, XMLNS};
+use crate::common::{ContactStatus, NoExtension, ObjectStatus, StringValue};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
+use serde::{Serialize, Serializer};
 
 impl<'a> Transaction<NoExtension> for ContactUpdate<'a> {}
 
@@ -12,8 +13,16 @@ impl<'a> Command for ContactUpdate<'a> {
     const COMMAND: &'static str = "update";
 }
 
+impl<E: Extension> Transaction<E> for OwnedContactUpdate where ContactUpdate<'static>: Transaction<E>
+{}
+
+impl Command for OwnedContactUpdate {
+    type Response = ();
+    const COMMAND: &'static str = "update";
+}
+
 impl<'a> ContactUpdate<'a> {
-    pub fn new(id: &'a str) -> ContactUpdate {
+    pub fn new(id: &'a str) -> ContactUpdate<'a> {
         Self {
             contact: ContactUpdateRequestData {
                 xmlns: XMLNS,
@@ -33,13 +42,14 @@ impl<'a> ContactUpdate<'a> {
         voice: Phone<'a>,
         auth_password: &'a str,
     ) {
-        self.contact.change_info = Some(ContactChangeInfo {
-            email: Some(email.into()),
-            postal_info: Some(postal_info),
-            voice: Some(voice),
-            auth_info: Some(ContactAuthInfo::new(auth_password)),
-            fax: None,
-        });
+        let info = self
+            .contact
+            .change_info
+            .get_or_insert_with(ContactChangeInfo::default);
+        info.email = Some(email.into());
+        info.postal_info = Some(postal_info);
+        info.voice = Some(voice);
+        info.auth_info = Some(ContactAuthInfo::new(auth_password));
     }
 
     /// Sets the data for the &lt;fax&gt; tag under &lt;chg&gt; for the contact update request
@@ -49,19 +59,27 @@ impl<'a> ContactUpdate<'a> {
         }
     }
 
+    /// Sets the data for the &lt;disclose&gt; tag under &lt;chg&gt; for the contact update request
+    pub fn set_disclose(&mut self, disclose: Disclose) {
+        self.contact
+            .change_info
+            .get_or_insert_with(ContactChangeInfo::default)
+            .disclose = Some(disclose);
+    }
+
     /// Sets the data for the &lt;add&gt; tag for the contact update request
-    pub fn add(&mut self, status: &'a [ObjectStatus]) {
+    pub fn add(&mut self, status: &'a [ObjectStatus<ContactStatus>]) {
         self.contact.add_statuses = Some(StatusList { status });
     }
 
     /// Sets the data for the &lt;rem&gt; tag for the contact update request
-    pub fn remove(&mut self, status: &'a [ObjectStatus]) {
+    pub fn remove(&mut self, status: &'a [ObjectStatus<ContactStatus>]) {
         self.contact.remove_statuses = Some(StatusList { status });
     }
 }
 
 /// Type for elements under the &lt;chg&gt; tag for contact update request
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Default)]
 pub struct ContactChangeInfo<'a> {
     #[serde(rename = "contact:postalInfo")]
     postal_info: Option<PostalInfo<'a>>,
@@ -73,13 +91,15 @@ pub struct ContactChangeInfo<'a> {
     email: Option<StringValue<'a>>,
     #[serde(rename = "contact:authInfo")]
     auth_info: Option<ContactAuthInfo<'a>>,
+    #[serde(rename = "contact:disclose")]
+    disclose: Option<Disclose>,
 }
 
 /// Type for list of elements of the &lt;status&gt; tag for contact update request
 #[derive(Serialize, Debug)]
 pub struct StatusList<'a> {
     #[serde(rename = "contact:status")]
-    status: &'a [ObjectStatus<'a>],
+    status: &'a [ObjectStatus<ContactStatus>],
 }
 
 /// Type for elements under the contact &lt;update&gt; tag
@@ -105,11 +125,154 @@ pub struct ContactUpdate<'a> {
     contact: ContactUpdateRequestData<'a>,
 }
 
+/// An owned [`ContactUpdate`], which can be kept around or sent to another task
+///
+/// Use [`OwnedContactUpdate::builder()`] to create one. Unlike [`ContactUpdate::set_info()`],
+/// the builder can change any subset of the contact's details.
+#[derive(Clone, Debug)]
+pub struct OwnedContactUpdate {
+    id: String,
+    add_statuses: Vec<ObjectStatus<ContactStatus>>,
+    remove_statuses: Vec<ObjectStatus<ContactStatus>>,
+    email: Option<String>,
+    postal_info: Option<PostalInfo<'static>>,
+    voice: Option<Phone<'static>>,
+    fax: Option<Phone<'static>>,
+    auth_password: Option<String>,
+    disclose: Option<Disclose>,
+}
+
+impl OwnedContactUpdate {
+    /// Starts building an update command for the contact `id`
+    pub fn builder(id: impl Into<String>) -> ContactUpdateBuilder {
+        ContactUpdateBuilder {
+            update: OwnedContactUpdate {
+                id: id.into(),
+                add_statuses: Vec::new(),
+                remove_statuses: Vec::new(),
+                email: None,
+                postal_info: None,
+                voice: None,
+                fax: None,
+                auth_password: None,
+                disclose: None,
+            },
+        }
+    }
+
+    fn change_info(&self) -> Option<ContactChangeInfo<'_>> {
+        let info = ContactChangeInfo {
+            postal_info: self.postal_info.clone(),
+            voice: self.voice.clone(),
+            fax: self.fax.clone(),
+            email: self.email.as_deref().map(Into::into),
+            auth_info: self.auth_password.as_deref().map(ContactAuthInfo::new),
+            disclose: self.disclose.clone(),
+        };
+
+        let unchanged = info.postal_info.is_none()
+            && info.voice.is_none()
+            && info.fax.is_none()
+            && info.email.is_none()
+            && info.auth_info.is_none()
+            && info.disclose.is_none();
+        (!unchanged).then(|| info)
+    }
+}
+
+impl Serialize for OwnedContactUpdate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut update = ContactUpdate::new(&self.id);
+        if !self.add_statuses.is_empty() {
+            update.add(&self.add_statuses);
+        }
+        if !self.remove_statuses.is_empty() {
+            update.remove(&self.remove_statuses);
+        }
+        update.contact.change_info = self.change_info();
+        update.serialize(serializer)
+    }
+}
+
+/// Builder for an [`OwnedContactUpdate`]
+///
+/// At least one change is required.
+#[derive(Clone, Debug)]
+pub struct ContactUpdateBuilder {
+    update: OwnedContactUpdate,
+}
+
+impl ContactUpdateBuilder {
+    /// Adds a status
+    pub fn add_status(mut self, status: impl Into<ObjectStatus<ContactStatus>>) -> Self {
+        self.update.add_statuses.push(status.into());
+        self
+    }
+
+    /// Removes a status
+    pub fn remove_status(mut self, status: impl Into<ObjectStatus<ContactStatus>>) -> Self {
+        self.update.remove_statuses.push(status.into());
+        self
+    }
+
+    /// Changes the email address
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.update.email = Some(email.into());
+        self
+    }
+
+    /// Changes the postal info
+    pub fn postal_info(mut self, postal_info: PostalInfo<'_>) -> Self {
+        self.update.postal_info = Some(postal_info.into_owned());
+        self
+    }
+
+    /// Changes the voice number
+    pub fn voice(mut self, voice: Phone<'_>) -> Self {
+        self.update.voice = Some(voice.into_owned());
+        self
+    }
+
+    /// Changes the fax number
+    pub fn fax(mut self, fax: Phone<'_>) -> Self {
+        self.update.fax = Some(fax.into_owned());
+        self
+    }
+
+    /// Changes the password of the contact's auth info
+    pub fn auth_password(mut self, password: impl Into<String>) -> Self {
+        self.update.auth_password = Some(password.into());
+        self
+    }
+
+    /// Changes the disclosure preferences
+    pub fn disclose(mut self, disclose: Disclose) -> Self {
+        self.update.disclose = Some(disclose);
+        self
+    }
+
+    /// Checks the command and builds it
+    pub fn build(self) -> Result<OwnedContactUpdate, Error> {
+        let update = self.update;
+        validate_id(&update.id)?;
+        if let Some(email) = &update.email {
+            required("email", email)?;
+        }
+
+        let unchanged = update.change_info().is_none();
+        if unchanged && update.add_statuses.is_empty() && update.remove_statuses.is_empty() {
+            return Err(Error::Other("contact update must change something".into()));
+        }
+
+        Ok(update)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ContactUpdate, Phone, PostalInfo};
-    use crate::common::ObjectStatus;
-    use crate::contact::Address;
+    use super::{ContactUpdate, OwnedContactUpdate, Phone, PostalInfo};
+    use crate::common::{ContactStatus, ObjectStatus};
+    use crate::contact::{Address, Disclose, PostalInfoType};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -123,18 +286,73 @@ mod tests {
         let voice = Phone::new("+33.47237942");
 
         object.set_info("newemail@eppdev.net", postal_info, voice, "eppdev-387323");
-        let add_statuses = &[ObjectStatus {
-            status: "clientTransferProhibited".into(),
-        }];
+        let add_statuses = &[ObjectStatus::new(ContactStatus::ClientTransferProhibited)];
         object.add(add_statuses);
-        let remove_statuses = &[ObjectStatus {
-            status: "clientDeleteProhibited".into(),
-        }];
+        let remove_statuses = &[ObjectStatus::new(ContactStatus::ClientDeleteProhibited)];
         object.remove(remove_statuses);
 
         assert_serialized("request/contact/update.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let street = &["58", "Orchid Road"];
+        let address = Address::new(street, "Paris", "Paris", "392374", "FR".parse().unwrap());
+
+        let object = OwnedContactUpdate::builder("eppdev-contact-3")
+            .add_status(ContactStatus::ClientTransferProhibited)
+            .remove_status(ContactStatus::ClientDeleteProhibited)
+            .email("newemail@eppdev.net")
+            .postal_info(PostalInfo::new("loc", "John Doe", "Acme Widgets", address))
+            .voice(Phone::new("+33.47237942"))
+            .auth_password("eppdev-387323")
+            .build()
+            .unwrap();
+        assert_serialized("request/contact/update.xml", &object);
+
+        assert!(OwnedContactUpdate::builder("eppdev-contact-3")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn command_disclose() {
+        let mut object = ContactUpdate::new("eppdev-contact-3");
+
+        let street = &["58", "Orchid Road"];
+        let address = Address::new(street, "Paris", "Paris", "392374", "FR".parse().unwrap());
+        let postal_info = PostalInfo::new("loc", "John Doe", "Acme Widgets", address);
+        let voice = Phone::new("+33.47237942");
+
+        // The disclose data is kept when the rest of the info is set afterwards
+        object.set_disclose(Disclose::new(true).organization(PostalInfoType::Loc).fax());
+        object.set_info("newemail@eppdev.net", postal_info, voice, "eppdev-387323");
+        let add_statuses = &[ObjectStatus::new(ContactStatus::ClientTransferProhibited)];
+        object.add(add_statuses);
+        let remove_statuses = &[ObjectStatus::new(ContactStatus::ClientDeleteProhibited)];
+        object.remove(remove_statuses);
+
+        assert_serialized("request/contact/update_disclose.xml", &object);
+    }
+
+    #[test]
+    fn owned_command_disclose() {
+        let street = &["58", "Orchid Road"];
+        let address = Address::new(street, "Paris", "Paris", "392374", "FR".parse().unwrap());
+
+        let object = OwnedContactUpdate::builder("eppdev-contact-3")
+            .add_status(ContactStatus::ClientTransferProhibited)
+            .remove_status(ContactStatus::ClientDeleteProhibited)
+            .email("newemail@eppdev.net")
+            .postal_info(PostalInfo::new("loc", "John Doe", "Acme Widgets", address))
+            .voice(Phone::new("+33.47237942"))
+            .auth_password("eppdev-387323")
+            .disclose(Disclose::new(true).organization(PostalInfoType::Loc).fax())
+            .build()
+            .unwrap();
+        assert_serialized("request/contact/update_disclose.xml", &object);
+    }
+
     #[test]
     fn contact_update() {
         let object = response_from_file::<ContactUpdate>("response/contact/update.xml");
@@ -5,33 +5,34 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 
 use crate::common::{serialize_host_addrs_option, HostAddr, StringValue};
+use crate::request::required;
 use crate::Error;
 
 pub mod check;
-pub use check::DomainCheck;
+pub use check::{DomainCheck, OwnedDomainCheck};
 
 pub mod create;
-pub use create::DomainCreate;
+pub use create::{DomainCreate, DomainCreateBuilder, OwnedDomainCreate};
 
 pub mod delete;
-pub use delete::DomainDelete;
+pub use delete::{DomainDelete, OwnedDomainDelete};
 
 pub mod info;
-pub use info::DomainInfo;
+pub use info::{DomainInfo, OwnedDomainInfo};
 
 pub mod renew;
-pub use renew::DomainRenew;
+pub use renew::{DomainRenew, OwnedDomainRenew};
 
 pub mod transfer;
-pub use transfer::DomainTransfer;
+pub use transfer::{DomainTransfer, OwnedDomainTransfer};
 
 pub mod update;
-pub use update::DomainUpdate;
+pub use update::{DomainUpdate, DomainUpdateBuilder, OwnedDomainUpdate};
 
 pub const XMLNS: &str = "urn:ietf:params:xml:ns:domain-1.0";
 
 /// The &lt;hostAttr&gt; type for domain transactions
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HostAttr<'a> {
     /// The &lt;hostName&gt; tag
     #[serde(rename = "domain:hostName", alias = "hostName")]
@@ -92,8 +93,72 @@ pub enum HostList<'a> {
     HostAttrList(HostAttrList<'a>),
 }
 
+/// The name servers of an owned command, which may be given in only one of the two forms
+#[derive(Clone, Debug, Default)]
+struct NameServers {
+    objects: Vec<StringValue<'static>>,
+    attributes: Vec<HostAttr<'static>>,
+}
+
+impl NameServers {
+    fn add_object(&mut self, name: String) {
+        self.objects.push(name.into());
+    }
+
+    fn add_attribute(&mut self, name: String, addresses: Vec<IpAddr>) {
+        self.attributes.push(HostAttr {
+            name: name.into(),
+            addresses: (!addresses.is_empty()).then(|| addresses),
+        });
+    }
+
+    fn is_empty(&self) -> bool {
+        self.objects.is_empty() && self.attributes.is_empty()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if !self.objects.is_empty() && !self.attributes.is_empty() {
+            return Err(Error::Other(
+                "name servers must all be given either as host objects or as host attributes"
+                    .into(),
+            ));
+        }
+
+        for name in &self.objects {
+            required("name server", name)?;
+        }
+        for host in &self.attributes {
+            required("name server", &host.name)?;
+        }
+        Ok(())
+    }
+
+    fn host_list(&self) -> Option<HostList<'_>> {
+        if !self.attributes.is_empty() {
+            Some(HostList::HostAttrList(HostAttrList {
+                hosts: &self.attributes,
+            }))
+        } else if !self.objects.is_empty() {
+            Some(HostList::HostObjList(HostObjList {
+                hosts: &self.objects,
+            }))
+        } else {
+            None
+        }
+    }
+}
+
+/// Checks the contacts of an owned command
+fn validate_contacts(contacts: &[DomainContact<'_>]) -> Result<(), Error> {
+    for contact in contacts {
+        required("contact type", &contact.contact_type)?;
+        required("contact id", &contact.id)?;
+    }
+    Ok(())
+}
+
 /// The &lt;contact&gt; type on domain creation and update requests
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DomainContact<'a> {
     /// The contact id
     #[serde(rename = "$value")]
@@ -104,7 +169,8 @@ pub struct DomainContact<'a> {
 }
 
 /// The &lt;period&gt; type for registration, renewal or transfer on domain transactions
-#[derive(Clone, Copy, Debug, Serialize)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(try_from = "DeserializedPeriod")]
 pub struct Period {
     /// The interval (usually 'y' indicating years)
     unit: char,
@@ -122,6 +188,16 @@ impl Period {
         Self::new(length, 'm')
     }
 
+    /// The length of the period
+    pub fn length(&self) -> u8 {
+        self.length
+    }
+
+    /// The interval unit ('y' for years or 'm' for months)
+    pub fn unit(&self) -> char {
+        self.unit
+    }
+
     fn new(length: u8, unit: char) -> Result<Self, Error> {
         match length {
             1..=99 => Ok(Period { length, unit }),
@@ -132,6 +208,27 @@ impl Period {
     }
 }
 
+/// A &lt;period&gt; as received, checked by `Period::new()` before it is used
+#[derive(Deserialize)]
+struct DeserializedPeriod {
+    unit: char,
+    #[serde(rename = "$value")]
+    length: u8,
+}
+
+impl TryFrom<DeserializedPeriod> for Period {
+    type Error = Error;
+
+    fn try_from(period: DeserializedPeriod) -> Result<Self, Self::Error> {
+        match period.unit {
+            'y' | 'm' => Self::new(period.length, period.unit),
+            unit => Err(Error::Other(
+                format!("Period unit must be 'y' or 'm', not '{unit}'").into(),
+            )),
+        }
+    }
+}
+
 pub const ONE_YEAR: Period = Period {
     unit: 'y',
     length: 1,
@@ -173,3 +270,19 @@ impl<'a> DomainAuthInfo<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Period;
+    use crate::xml;
+
+    #[test]
+    fn period() {
+        let period = xml::deserialize::<Period>(r#"<period unit="y">2</period>"#).unwrap();
+        assert_eq!((period.length(), period.unit()), (2, 'y'));
+
+        assert!(xml::deserialize::<Period>(r#"<period unit="y">0</period>"#).is_err());
+        assert!(xml::deserialize::<Period>(r#"<period unit="y">100</period>"#).is_err());
+        assert!(xml::deserialize::<Period>(r#"<period unit="d">5</period>"#).is_err());
+    }
+}
@@ -2,8 +2,9 @@
 
 use super::XMLNS;
 use crate::common::{CheckResponse, NoExtension, StringValue};
-use crate::request::{Command, Transaction};
-use serde::Serialize;
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
+use serde::{Serialize, Serializer};
 
 impl<'a> Transaction<NoExtension> for DomainCheck<'a> {}
 
@@ -12,6 +13,13 @@ impl<'a> Command for DomainCheck<'a> {
     const COMMAND: &'static str = "check";
 }
 
+impl<E: Extension> Transaction<E> for OwnedDomainCheck where DomainCheck<'static>: Transaction<E> {}
+
+impl Command for OwnedDomainCheck {
+    type Response = CheckResponse;
+    const COMMAND: &'static str = "check";
+}
+
 // Request
 
 /// Type for &lt;name&gt; elements under the domain &lt;check&gt; tag
@@ -50,9 +58,47 @@ pub struct DomainCheck<'a> {
     pub domains: &'a [&'a str],
 }
 
+/// An owned [`DomainCheck`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedDomainCheck {
+    domains: Vec<String>,
+}
+
+impl OwnedDomainCheck {
+    /// Creates a check for `domains`, failing if there are none or one of them is empty
+    pub fn new<I>(domains: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let domains = domains.into_iter().map(Into::into).collect::<Vec<_>>();
+        if domains.is_empty() {
+            return Err(Error::Other("at least one domain must be checked".into()));
+        }
+
+        for domain in &domains {
+            required("domain name", domain)?;
+        }
+
+        Ok(Self { domains })
+    }
+}
+
+impl Serialize for OwnedDomainCheck {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeDomainCheck {
+            list: DomainList {
+                xmlns: XMLNS,
+                domains: self.domains.iter().map(|d| d.as_str().into()).collect(),
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::DomainCheck;
+    use super::{DomainCheck, OwnedDomainCheck};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -64,6 +110,15 @@ mod tests {
         assert_serialized("request/domain/check.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedDomainCheck::new(["eppdev.com", "eppdev.net"]).unwrap();
+        assert_serialized("request/domain/check.xml", &object);
+
+        assert!(OwnedDomainCheck::new(Vec::<String>::new()).is_err());
+        assert!(OwnedDomainCheck::new(["eppdev.com", ""]).is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<DomainCheck>("response/domain/check.xml");
@@ -1,11 +1,16 @@
 //! Types for EPP domain create request
 
+use std::net::IpAddr;
+
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-use super::{DomainAuthInfo, DomainContact, HostList, Period, XMLNS};
+use super::{
+    validate_contacts, DomainAuthInfo, DomainContact, HostList, NameServers, Period, XMLNS,
+};
 use crate::common::{NoExtension, StringValue};
-use crate::request::{Command, Transaction};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
 
 impl<'a> Transaction<NoExtension> for DomainCreate<'a> {}
 
@@ -14,6 +19,13 @@ impl<'a> Command for DomainCreate<'a> {
     const COMMAND: &'static str = "create";
 }
 
+impl<E: Extension> Transaction<E> for OwnedDomainCreate where DomainCreate<'static>: Transaction<E> {}
+
+impl Command for OwnedDomainCreate {
+    type Response = DomainCreateResponse;
+    const COMMAND: &'static str = "create";
+}
+
 // Request
 
 /// Type for elements under the domain &lt;create&gt; tag
@@ -76,6 +88,126 @@ impl<'a> DomainCreate<'a> {
     }
 }
 
+/// An owned [`DomainCreate`], which can be kept around or sent to another task
+///
+/// Use [`OwnedDomainCreate::builder()`] to create one.
+#[derive(Clone, Debug)]
+pub struct OwnedDomainCreate {
+    name: String,
+    period: Period,
+    ns: NameServers,
+    registrant: Option<String>,
+    auth_password: String,
+    contacts: Vec<DomainContact<'static>>,
+}
+
+impl OwnedDomainCreate {
+    /// Starts building a create command for the domain `name`
+    pub fn builder(name: impl Into<String>) -> DomainCreateBuilder {
+        DomainCreateBuilder {
+            name: name.into(),
+            period: None,
+            ns: NameServers::default(),
+            registrant: None,
+            auth_password: None,
+            contacts: Vec::new(),
+        }
+    }
+}
+
+impl Serialize for OwnedDomainCreate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DomainCreate::new(
+            &self.name,
+            self.period,
+            self.ns.host_list(),
+            self.registrant.as_deref(),
+            &self.auth_password,
+            (!self.contacts.is_empty()).then(|| self.contacts.as_slice()),
+        )
+        .serialize(serializer)
+    }
+}
+
+/// Builder for an [`OwnedDomainCreate`]
+///
+/// The period and auth password are required.
+#[derive(Clone, Debug)]
+pub struct DomainCreateBuilder {
+    name: String,
+    period: Option<Period>,
+    ns: NameServers,
+    registrant: Option<String>,
+    auth_password: Option<String>,
+    contacts: Vec<DomainContact<'static>>,
+}
+
+impl DomainCreateBuilder {
+    /// Sets the registration period
+    pub fn period(mut self, period: Period) -> Self {
+        self.period = Some(period);
+        self
+    }
+
+    /// Adds a name server as a &lt;hostObj&gt;, referring to an existing host object
+    pub fn host_obj(mut self, name: impl Into<String>) -> Self {
+        self.ns.add_object(name.into());
+        self
+    }
+
+    /// Adds a name server as a &lt;hostAttr&gt;, with its glue addresses if any
+    pub fn host_attr(mut self, name: impl Into<String>, addresses: Vec<IpAddr>) -> Self {
+        self.ns.add_attribute(name.into(), addresses);
+        self
+    }
+
+    /// Sets the id of the registrant contact
+    pub fn registrant(mut self, id: impl Into<String>) -> Self {
+        self.registrant = Some(id.into());
+        self
+    }
+
+    /// Adds a contact of the given type, such as "admin" or "tech"
+    pub fn contact(mut self, contact_type: impl Into<String>, id: impl Into<String>) -> Self {
+        self.contacts.push(DomainContact {
+            id: id.into().into(),
+            contact_type: contact_type.into().into(),
+        });
+        self
+    }
+
+    /// Sets the password of the domain's auth info
+    pub fn auth_password(mut self, password: impl Into<String>) -> Self {
+        self.auth_password = Some(password.into());
+        self
+    }
+
+    /// Checks the command and builds it
+    pub fn build(self) -> Result<OwnedDomainCreate, Error> {
+        required("domain name", &self.name)?;
+        let period = self
+            .period
+            .ok_or_else(|| Error::Other("period is required".into()))?;
+        let auth_password = self
+            .auth_password
+            .ok_or_else(|| Error::Other("auth password is required".into()))?;
+        self.ns.validate()?;
+        if let Some(registrant) = &self.registrant {
+            required("registrant", registrant)?;
+        }
+        validate_contacts(&self.contacts)?;
+
+        Ok(OwnedDomainCreate {
+            name: self.name,
+            period,
+            ns: self.ns,
+            registrant: self.registrant,
+            auth_password,
+            contacts: self.contacts,
+        })
+    }
+}
+
 // Response
 
 /// Type that represents the &lt;chkData&gt; tag for domain create response
@@ -108,7 +240,7 @@ mod tests {
 
     use chrono::{TimeZone, Utc};
 
-    use super::{DomainContact, DomainCreate, HostList, Period};
+    use super::{DomainContact, DomainCreate, HostList, OwnedDomainCreate, Period};
     use crate::domain::{HostAttr, HostAttrList, HostObjList};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
@@ -215,6 +347,56 @@ mod tests {
         assert_serialized("request/domain/create_with_host_attr.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedDomainCreate::builder("eppdev-1.com")
+            .period(Period::years(1).unwrap())
+            .host_obj("ns1.test.com")
+            .host_obj("ns2.test.com")
+            .registrant("eppdev-contact-3")
+            .contact("admin", "eppdev-contact-3")
+            .contact("tech", "eppdev-contact-3")
+            .contact("billing", "eppdev-contact-3")
+            .auth_password("epP4uthd#v")
+            .build()
+            .unwrap();
+        assert_serialized("request/domain/create_with_host_obj.xml", &object);
+
+        let object = OwnedDomainCreate::builder("eppdev-2.com")
+            .period(Period::years(1).unwrap())
+            .host_attr("ns1.eppdev-1.com", vec![])
+            .host_attr(
+                "ns2.eppdev-1.com",
+                vec![
+                    IpAddr::from([177, 232, 12, 58]),
+                    IpAddr::from([0x2404, 0x6800, 0x4001, 0x801, 0, 0, 0, 0x200e]),
+                ],
+            )
+            .registrant("eppdev-contact-3")
+            .contact("admin", "eppdev-contact-3")
+            .contact("tech", "eppdev-contact-3")
+            .contact("billing", "eppdev-contact-3")
+            .auth_password("epP4uthd#v")
+            .build()
+            .unwrap();
+        assert_serialized("request/domain/create_with_host_attr.xml", &object);
+    }
+
+    #[test]
+    fn owned_command_validation() {
+        let builder = OwnedDomainCreate::builder("eppdev-1.com").period(Period::years(1).unwrap());
+        assert!(builder.clone().build().is_err());
+
+        let builder = builder.auth_password("epP4uthd#v");
+        assert!(builder.clone().build().is_ok());
+        assert!(builder.clone().contact("admin", "").build().is_err());
+        assert!(builder
+            .host_obj("ns1.test.com")
+            .host_attr("ns2.test.com", vec![])
+            .build()
+            .is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<DomainCreate>("response/domain/create.xml");
@@ -2,8 +2,9 @@
 
 use super::XMLNS;
 use crate::common::{NoExtension, StringValue};
-use crate::request::{Command, Transaction};
-use serde::Serialize;
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
+use serde::{Serialize, Serializer};
 
 impl<'a> Transaction<NoExtension> for DomainDelete<'a> {}
 
@@ -12,6 +13,13 @@ impl<'a> Command for DomainDelete<'a> {
     const COMMAND: &'static str = "delete";
 }
 
+impl<E: Extension> Transaction<E> for OwnedDomainDelete where DomainDelete<'static>: Transaction<E> {}
+
+impl Command for OwnedDomainDelete {
+    type Response = ();
+    const COMMAND: &'static str = "delete";
+}
+
 impl<'a> DomainDelete<'a> {
     pub fn new(name: &'a str) -> Self {
         Self {
@@ -23,6 +31,26 @@ impl<'a> DomainDelete<'a> {
     }
 }
 
+/// An owned [`DomainDelete`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedDomainDelete {
+    name: String,
+}
+
+impl OwnedDomainDelete {
+    pub fn new(name: impl Into<String>) -> Result<Self, Error> {
+        let name = name.into();
+        required("domain name", &name)?;
+        Ok(Self { name })
+    }
+}
+
+impl Serialize for OwnedDomainDelete {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DomainDelete::new(&self.name).serialize(serializer)
+    }
+}
+
 /// Type for &lt;name&gt; element under the domain &lt;delete&gt; tag
 #[derive(Serialize, Debug)]
 pub struct DomainDeleteRequestData<'a> {
@@ -44,7 +72,7 @@ pub struct DomainDelete<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::DomainDelete;
+    use super::{DomainDelete, OwnedDomainDelete};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -54,6 +82,13 @@ mod tests {
         assert_serialized("request/domain/delete.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedDomainDelete::new("eppdev.com").unwrap();
+        assert_serialized("request/domain/delete.xml", &object);
+        assert!(OwnedDomainDelete::new("").is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<DomainDelete>("response/domain/delete.xml");
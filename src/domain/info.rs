@@ -1,11 +1,12 @@
 //! Types for EPP domain info request
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use super::{DomainAuthInfo, DomainContact, HostAttr, XMLNS};
-use crate::common::{NoExtension, ObjectStatus, StringValue};
-use crate::request::{Command, Transaction};
+use crate::common::{DomainStatus, NoExtension, ObjectStatus, StringValue};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
 
 impl<'a> Transaction<NoExtension> for DomainInfo<'a> {}
 
@@ -14,6 +15,13 @@ impl<'a> Command for DomainInfo<'a> {
     const COMMAND: &'static str = "info";
 }
 
+impl<E: Extension> Transaction<E> for OwnedDomainInfo where DomainInfo<'static>: Transaction<E> {}
+
+impl Command for OwnedDomainInfo {
+    type Response = DomainInfoResponse;
+    const COMMAND: &'static str = "info";
+}
+
 impl<'a> DomainInfo<'a> {
     pub fn new(name: &'a str, auth_password: Option<&'a str>) -> Self {
         Self {
@@ -28,6 +36,30 @@ impl<'a> DomainInfo<'a> {
     }
 }
 
+/// An owned [`DomainInfo`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedDomainInfo {
+    name: String,
+    auth_password: Option<String>,
+}
+
+impl OwnedDomainInfo {
+    pub fn new(name: impl Into<String>, auth_password: Option<String>) -> Result<Self, Error> {
+        let name = name.into();
+        required("domain name", &name)?;
+        Ok(Self {
+            name,
+            auth_password,
+        })
+    }
+}
+
+impl Serialize for OwnedDomainInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DomainInfo::new(&self.name, self.auth_password.as_deref()).serialize(serializer)
+    }
+}
+
 // Request
 
 /// Type for data under the &lt;name&gt; element tag for the domain &lt;info&gt; tag
@@ -84,7 +116,7 @@ pub struct DomainInfoResponseData {
     pub roid: StringValue<'static>,
     /// The list of domain statuses
     #[serde(rename = "status")]
-    pub statuses: Option<Vec<ObjectStatus<'static>>>,
+    pub statuses: Option<Vec<ObjectStatus<DomainStatus>>>,
     /// The domain registrant
     pub registrant: Option<StringValue<'static>>,
     /// The list of domain contacts
@@ -132,7 +164,8 @@ pub struct DomainInfoResponse {
 
 #[cfg(test)]
 mod tests {
-    use super::DomainInfo;
+    use super::{DomainInfo, OwnedDomainInfo};
+    use crate::common::DomainStatus;
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
     use chrono::{TimeZone, Utc};
@@ -143,6 +176,13 @@ mod tests {
         assert_serialized("request/domain/info.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedDomainInfo::new("eppdev.com", Some("2fooBAR".into())).unwrap();
+        assert_serialized("request/domain/info.xml", &object);
+        assert!(OwnedDomainInfo::new("", None).is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<DomainInfo>("response/domain/info.xml");
@@ -160,8 +200,8 @@ mod tests {
         assert_eq!(object.result.message, SUCCESS_MSG.into());
         assert_eq!(result.info_data.name, "eppdev-1.com".into());
         assert_eq!(result.info_data.roid, "125899511_DOMAIN_COM-VRSN".into());
-        assert_eq!(statuses[0].status, "ok".to_string());
-        assert_eq!(statuses[1].status, "clientTransferProhibited".to_string());
+        assert_eq!(statuses[0].status, DomainStatus::Ok);
+        assert_eq!(statuses[1].status, DomainStatus::ClientTransferProhibited);
         assert_eq!(*registrant, "eppdev-contact-2".into());
         assert_eq!(contacts[0].id, "eppdev-contact-2".to_string());
         assert_eq!(contacts[0].contact_type, "admin".to_string());
@@ -203,4 +243,25 @@ mod tests {
     fn response_alt() {
         response_from_file::<DomainInfo>("response/domain/info_alt.xml");
     }
+
+    #[test]
+    fn response_statuses() {
+        let object = response_from_file::<DomainInfo>("response/domain/info_statuses.xml");
+
+        let result = object.res_data().unwrap();
+        let statuses = result.info_data.statuses.as_ref().unwrap();
+
+        assert_eq!(statuses[0].status, DomainStatus::ClientHold);
+        assert_eq!(statuses[0].lang.as_deref(), Some("en"));
+        assert_eq!(statuses[0].reason.as_deref(), Some("Payment overdue."));
+        assert_eq!(statuses[1].status, DomainStatus::ServerUpdateProhibited);
+        assert_eq!(statuses[1].lang, None);
+        assert_eq!(statuses[1].reason.as_deref(), Some("Under investigation"));
+        assert_eq!(
+            statuses[2].status,
+            DomainStatus::Other("registryLock".into())
+        );
+        assert_eq!(statuses[2].status.as_str(), "registryLock");
+        assert_eq!(statuses[2].reason, None);
+    }
 }
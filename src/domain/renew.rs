@@ -1,11 +1,12 @@
 //! Types for EPP domain renew request
 
 use chrono::{DateTime, NaiveDate, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use super::{Period, XMLNS};
 use crate::common::{NoExtension, StringValue};
-use crate::request::{Command, Transaction};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
 
 impl<'a> Transaction<NoExtension> for DomainRenew<'a> {}
 
@@ -14,6 +15,13 @@ impl<'a> Command for DomainRenew<'a> {
     const COMMAND: &'static str = "renew";
 }
 
+impl<E: Extension> Transaction<E> for OwnedDomainRenew where DomainRenew<'static>: Transaction<E> {}
+
+impl Command for OwnedDomainRenew {
+    type Response = DomainRenewResponse;
+    const COMMAND: &'static str = "renew";
+}
+
 impl<'a> DomainRenew<'a> {
     pub fn new(name: &'a str, current_expiry_date: NaiveDate, period: Period) -> Self {
         let exp_date_str = current_expiry_date.format("%Y-%m-%d").to_string().into();
@@ -28,6 +36,36 @@ impl<'a> DomainRenew<'a> {
     }
 }
 
+/// An owned [`DomainRenew`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedDomainRenew {
+    name: String,
+    current_expiry_date: NaiveDate,
+    period: Period,
+}
+
+impl OwnedDomainRenew {
+    pub fn new(
+        name: impl Into<String>,
+        current_expiry_date: NaiveDate,
+        period: Period,
+    ) -> Result<Self, Error> {
+        let name = name.into();
+        required("domain name", &name)?;
+        Ok(Self {
+            name,
+            current_expiry_date,
+            period,
+        })
+    }
+}
+
+impl Serialize for OwnedDomainRenew {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DomainRenew::new(&self.name, self.current_expiry_date, self.period).serialize(serializer)
+    }
+}
+
 // Request
 
 /// Type for data under the domain &lt;renew&gt; tag
@@ -77,7 +115,7 @@ pub struct DomainRenewResponse {
 
 #[cfg(test)]
 mod tests {
-    use super::{DomainRenew, Period};
+    use super::{DomainRenew, OwnedDomainRenew, Period};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -90,6 +128,13 @@ mod tests {
         assert_serialized("request/domain/renew.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let exp_date = NaiveDate::from_ymd_opt(2022, 7, 23).unwrap();
+        let object = OwnedDomainRenew::new("eppdev.com", exp_date, Period::years(1).unwrap());
+        assert_serialized("request/domain/renew.xml", &object.unwrap());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<DomainRenew>("response/domain/renew.xml");
@@ -1,11 +1,12 @@
 //! Types for EPP domain transfer request
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use super::{DomainAuthInfo, Period, XMLNS};
 use crate::common::{NoExtension, StringValue};
-use crate::request::{Command, Transaction};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
 
 impl<'a> Transaction<NoExtension> for DomainTransfer<'a> {}
 
@@ -14,6 +15,16 @@ impl<'a> Command for DomainTransfer<'a> {
     const COMMAND: &'static str = "transfer";
 }
 
+impl<E: Extension> Transaction<E> for OwnedDomainTransfer where
+    DomainTransfer<'static>: Transaction<E>
+{
+}
+
+impl Command for OwnedDomainTransfer {
+    type Response = DomainTransferResponse;
+    const COMMAND: &'static str = "transfer";
+}
+
 impl<'a> DomainTransfer<'a> {
     pub fn new(name: &'a str, period: Option<Period>, auth_password: &'a str) -> Self {
         Self::build(
@@ -63,6 +74,68 @@ impl<'a> DomainTransfer<'a> {
     }
 }
 
+/// An owned [`DomainTransfer`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedDomainTransfer {
+    operation: &'static str,
+    name: String,
+    period: Option<Period>,
+    auth_password: Option<String>,
+}
+
+impl OwnedDomainTransfer {
+    pub fn new(
+        name: impl Into<String>,
+        period: Option<Period>,
+        auth_password: impl Into<String>,
+    ) -> Result<Self, Error> {
+        Self::build("request", name.into(), period, Some(auth_password.into()))
+    }
+
+    pub fn query(name: impl Into<String>, auth_password: impl Into<String>) -> Result<Self, Error> {
+        Self::build("query", name.into(), None, Some(auth_password.into()))
+    }
+
+    pub fn approve(name: impl Into<String>) -> Result<Self, Error> {
+        Self::build("approve", name.into(), None, None)
+    }
+
+    pub fn reject(name: impl Into<String>) -> Result<Self, Error> {
+        Self::build("reject", name.into(), None, None)
+    }
+
+    pub fn cancel(name: impl Into<String>) -> Result<Self, Error> {
+        Self::build("cancel", name.into(), None, None)
+    }
+
+    fn build(
+        operation: &'static str,
+        name: String,
+        period: Option<Period>,
+        auth_password: Option<String>,
+    ) -> Result<Self, Error> {
+        required("domain name", &name)?;
+        if let Some(password) = &auth_password {
+            required("auth password", password)?;
+        }
+
+        Ok(Self {
+            operation,
+            name,
+            period,
+            auth_password,
+        })
+    }
+}
+
+impl Serialize for OwnedDomainTransfer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let auth_info = self.auth_password.as_deref().map(DomainAuthInfo::new);
+        DomainTransfer::build(self.operation, &self.name, self.period, auth_info)
+            .serialize(serializer)
+    }
+}
+
 // Request
 
 /// Type for elements under the domain &lt;transfer&gt; tag
@@ -135,7 +208,7 @@ pub struct DomainTransferResponse {
 mod tests {
     use chrono::{TimeZone, Utc};
 
-    use super::{DomainTransfer, Period};
+    use super::{DomainTransfer, OwnedDomainTransfer, Period};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -146,6 +219,27 @@ mod tests {
         assert_serialized("request/domain/transfer_request.xml", &object);
     }
 
+    #[test]
+    fn owned_commands() {
+        let period = Some(Period::years(1).unwrap());
+        let object = OwnedDomainTransfer::new("testing.com", period, "epP4uthd#v").unwrap();
+        assert_serialized("request/domain/transfer_request.xml", &object);
+
+        let object = OwnedDomainTransfer::query("testing.com", "epP4uthd#v").unwrap();
+        assert_serialized("request/domain/transfer_query.xml", &object);
+
+        let object = OwnedDomainTransfer::approve("testing.com").unwrap();
+        assert_serialized("request/domain/transfer_approve.xml", &object);
+
+        let object = OwnedDomainTransfer::reject("testing.com").unwrap();
+        assert_serialized("request/domain/transfer_reject.xml", &object);
+
+        let object = OwnedDomainTransfer::cancel("testing.com").unwrap();
+        assert_serialized("request/domain/transfer_cancel.xml", &object);
+
+        assert!(OwnedDomainTransfer::query("testing.com", "").is_err());
+    }
+
     #[test]
     fn approve_command() {
         let object = DomainTransfer::approve("testing.com");
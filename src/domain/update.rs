@@ -1,12 +1,15 @@
 //! Types for EPP domain check request
 //!
-use super::{DomainAuthInfo, DomainContact, HostList, XMLNS};
+use std::net::IpAddr;
+
+use super::{validate_contacts, DomainAuthInfo, DomainContact, HostList, NameServers, XMLNS};
 use crate::{
-    common::{NoExtension, ObjectStatus, StringValue},
-    request::{Command, Transaction},
+    common::{DomainStatus, NoExtension, ObjectStatus, StringValue},
+    request::{required, Command, Extension, Transaction},
+    Error,
 };
 
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 
 impl<'a> Transaction<NoExtension> for DomainUpdate<'a> {}
 
@@ -15,6 +18,13 @@ impl<'a> Command for DomainUpdate<'a> {
     const COMMAND: &'static str = "update";
 }
 
+impl<E: Extension> Transaction<E> for OwnedDomainUpdate where DomainUpdate<'static>: Transaction<E> {}
+
+impl Command for OwnedDomainUpdate {
+    type Response = ();
+    const COMMAND: &'static str = "update";
+}
+
 impl<'a> DomainUpdate<'a> {
     pub fn new(name: &'a str) -> Self {
         Self {
@@ -67,7 +77,7 @@ pub struct DomainAddRemove<'a> {
     pub contacts: Option<&'a [DomainContact<'a>]>,
     /// The list of statuses to add to or remove from the domain
     #[serde(rename = "domain:status")]
-    pub statuses: Option<&'a [ObjectStatus<'a>]>,
+    pub statuses: Option<&'a [ObjectStatus<DomainStatus>]>,
 }
 
 /// Type for elements under the &lt;update&gt; tag for domain update
@@ -99,10 +109,186 @@ pub struct DomainUpdate<'a> {
     pub domain: DomainUpdateRequestData<'a>,
 }
 
+/// The name servers, contacts and statuses to add to or remove from a domain in an
+/// [`OwnedDomainUpdate`]
+#[derive(Clone, Debug, Default)]
+struct OwnedAddRemove {
+    ns: NameServers,
+    contacts: Vec<DomainContact<'static>>,
+    statuses: Vec<ObjectStatus<DomainStatus>>,
+}
+
+impl OwnedAddRemove {
+    fn is_empty(&self) -> bool {
+        self.ns.is_empty() && self.contacts.is_empty() && self.statuses.is_empty()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        self.ns.validate()?;
+        validate_contacts(&self.contacts)
+    }
+
+    fn as_add_remove(&self) -> Option<DomainAddRemove<'_>> {
+        match self.is_empty() {
+            true => None,
+            false => Some(DomainAddRemove {
+                ns: self.ns.host_list(),
+                contacts: (!self.contacts.is_empty()).then(|| self.contacts.as_slice()),
+                statuses: (!self.statuses.is_empty()).then(|| self.statuses.as_slice()),
+            }),
+        }
+    }
+
+    fn contact(&mut self, contact_type: String, id: String) {
+        self.contacts.push(DomainContact {
+            id: id.into(),
+            contact_type: contact_type.into(),
+        });
+    }
+}
+
+/// An owned [`DomainUpdate`], which can be kept around or sent to another task
+///
+/// Use [`OwnedDomainUpdate::builder()`] to create one.
+#[derive(Clone, Debug)]
+pub struct OwnedDomainUpdate {
+    name: String,
+    add: OwnedAddRemove,
+    remove: OwnedAddRemove,
+    registrant: Option<String>,
+    auth_password: Option<String>,
+}
+
+impl OwnedDomainUpdate {
+    /// Starts building an update command for the domain `name`
+    pub fn builder(name: impl Into<String>) -> DomainUpdateBuilder {
+        DomainUpdateBuilder {
+            update: OwnedDomainUpdate {
+                name: name.into(),
+                add: OwnedAddRemove::default(),
+                remove: OwnedAddRemove::default(),
+                registrant: None,
+                auth_password: None,
+            },
+        }
+    }
+}
+
+impl Serialize for OwnedDomainUpdate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut update = DomainUpdate::new(&self.name);
+        update.domain.add = self.add.as_add_remove();
+        update.domain.remove = self.remove.as_add_remove();
+        if self.registrant.is_some() || self.auth_password.is_some() {
+            update.info(DomainChangeInfo {
+                registrant: self.registrant.as_deref().map(Into::into),
+                auth_info: self.auth_password.as_deref().map(DomainAuthInfo::new),
+            });
+        }
+
+        update.serialize(serializer)
+    }
+}
+
+/// Builder for an [`OwnedDomainUpdate`]
+///
+/// At least one change is required.
+#[derive(Clone, Debug)]
+pub struct DomainUpdateBuilder {
+    update: OwnedDomainUpdate,
+}
+
+impl DomainUpdateBuilder {
+    /// Adds a name server as a &lt;hostObj&gt;
+    pub fn add_host_obj(mut self, name: impl Into<String>) -> Self {
+        self.update.add.ns.add_object(name.into());
+        self
+    }
+
+    /// Adds a name server as a &lt;hostAttr&gt;, with its glue addresses if any
+    pub fn add_host_attr(mut self, name: impl Into<String>, addresses: Vec<IpAddr>) -> Self {
+        self.update.add.ns.add_attribute(name.into(), addresses);
+        self
+    }
+
+    /// Adds a contact of the given type, such as "admin" or "tech"
+    pub fn add_contact(mut self, contact_type: impl Into<String>, id: impl Into<String>) -> Self {
+        self.update.add.contact(contact_type.into(), id.into());
+        self
+    }
+
+    /// Adds a status
+    pub fn add_status(mut self, status: impl Into<ObjectStatus<DomainStatus>>) -> Self {
+        self.update.add.statuses.push(status.into());
+        self
+    }
+
+    /// Removes a name server given as a &lt;hostObj&gt;
+    pub fn remove_host_obj(mut self, name: impl Into<String>) -> Self {
+        self.update.remove.ns.add_object(name.into());
+        self
+    }
+
+    /// Removes a name server given as a &lt;hostAttr&gt;
+    pub fn remove_host_attr(mut self, name: impl Into<String>, addresses: Vec<IpAddr>) -> Self {
+        self.update.remove.ns.add_attribute(name.into(), addresses);
+        self
+    }
+
+    /// Removes a contact of the given type
+    pub fn remove_contact(
+        mut self,
+        contact_type: impl Into<String>,
+        id: impl Into<String>,
+    ) -> Self {
+        self.update.remove.contact(contact_type.into(), id.into());
+        self
+    }
+
+    /// Removes a status
+    pub fn remove_status(mut self, status: impl Into<ObjectStatus<DomainStatus>>) -> Self {
+        self.update.remove.statuses.push(status.into());
+        self
+    }
+
+    /// Changes the registrant contact
+    pub fn registrant(mut self, id: impl Into<String>) -> Self {
+        self.update.registrant = Some(id.into());
+        self
+    }
+
+    /// Changes the password of the domain's auth info
+    pub fn auth_password(mut self, password: impl Into<String>) -> Self {
+        self.update.auth_password = Some(password.into());
+        self
+    }
+
+    /// Checks the command and builds it
+    pub fn build(self) -> Result<OwnedDomainUpdate, Error> {
+        let update = self.update;
+        required("domain name", &update.name)?;
+        update.add.validate()?;
+        update.remove.validate()?;
+        if let Some(registrant) = &update.registrant {
+            required("registrant", registrant)?;
+        }
+
+        let unchanged = update.registrant.is_none() && update.auth_password.is_none();
+        if unchanged && update.add.is_empty() && update.remove.is_empty() {
+            return Err(Error::Other("domain update must change something".into()));
+        }
+
+        Ok(update)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{DomainAddRemove, DomainAuthInfo, DomainChangeInfo, DomainContact, DomainUpdate};
-    use crate::common::ObjectStatus;
+    use super::{
+        DomainAddRemove, DomainAuthInfo, DomainChangeInfo, DomainContact, DomainUpdate,
+        OwnedDomainUpdate,
+    };
+    use crate::common::{DomainStatus, ObjectStatus};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -110,9 +296,7 @@ mod tests {
     fn command() {
         let mut object = DomainUpdate::new("eppdev.com");
 
-        let statuses = &[ObjectStatus {
-            status: "clientDeleteProhibited".into(),
-        }];
+        let statuses = &[ObjectStatus::new(DomainStatus::ClientDeleteProhibited)];
 
         let add = DomainAddRemove {
             ns: None,
@@ -142,6 +326,42 @@ mod tests {
         assert_serialized("request/domain/update.xml", &object);
     }
 
+    #[test]
+    fn command_status_reason() {
+        let mut object = DomainUpdate::new("eppdev.com");
+
+        let statuses =
+            &[ObjectStatus::new(DomainStatus::ClientHold).reason("Payment overdue.", Some("en"))];
+
+        object.add(DomainAddRemove {
+            ns: None,
+            contacts: None,
+            statuses: Some(statuses),
+        });
+        assert_serialized("request/domain/update_status_reason.xml", &object);
+    }
+
+    #[test]
+    fn owned_command() {
+        let object = OwnedDomainUpdate::builder("eppdev.com")
+            .add_status(DomainStatus::ClientDeleteProhibited)
+            .remove_contact("billing", "eppdev-contact-2")
+            .auth_password("epP5uthd#v")
+            .build()
+            .unwrap();
+        assert_serialized("request/domain/update.xml", &object);
+
+        let status =
+            ObjectStatus::new(DomainStatus::ClientHold).reason("Payment overdue.", Some("en"));
+        let object = OwnedDomainUpdate::builder("eppdev.com")
+            .add_status(status)
+            .build()
+            .unwrap();
+        assert_serialized("request/domain/update_status_reason.xml", &object);
+
+        assert!(OwnedDomainUpdate::builder("eppdev.com").build().is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<DomainUpdate>("response/domain/update.xml");
@@ -0,0 +1,199 @@
+//! Types for the EPP allocation token extension
+//!
+//! As described in [RFC 8495](https://tools.ietf.org/html/rfc8495).
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::NoExtension;
+use crate::domain::{
+    check::DomainCheck, create::DomainCreate, info::DomainInfo, renew::DomainRenew,
+    transfer::DomainTransfer, update::DomainUpdate,
+};
+use crate::request::{Extension, Transaction};
+
+pub const XMLNS: &str = "urn:ietf:params:xml:ns:allocationToken-1.0";
+
+impl Transaction<AllocationToken<'_>> for DomainCheck<'_> {}
+impl Transaction<AllocationToken<'_>> for DomainCreate<'_> {}
+impl Transaction<AllocationToken<'_>> for DomainRenew<'_> {}
+impl Transaction<AllocationToken<'_>> for DomainTransfer<'_> {}
+impl Transaction<AllocationToken<'_>> for DomainUpdate<'_> {}
+
+impl Extension for AllocationToken<'_> {
+    type Response = NoExtension;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+impl Transaction<Info<'_>> for DomainInfo<'_> {}
+
+impl Extension for Info<'_> {
+    type Response = InfoResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+// Request
+
+/// Type for EPP XML &lt;allocationToken:allocationToken&gt;
+#[derive(Serialize, Debug)]
+pub struct AllocationTokenData<'a> {
+    /// XML namespace for the allocation token extension
+    #[serde(rename = "xmlns:allocationToken")]
+    xmlns: &'a str,
+    /// The allocation token
+    #[serde(rename = "$value")]
+    token: Cow<'a, str>,
+}
+
+/// Type for the allocation token extension in domain check, create, renew, transfer and
+/// update commands
+#[derive(Serialize, Debug)]
+pub struct AllocationToken<'a> {
+    #[serde(rename = "allocationToken:allocationToken")]
+    pub data: AllocationTokenData<'a>,
+}
+
+impl<'a> AllocationToken<'a> {
+    /// Create a new allocation token extension
+    pub fn new(token: &'a str) -> Self {
+        Self {
+            data: AllocationTokenData {
+                xmlns: XMLNS,
+                token: token.into(),
+            },
+        }
+    }
+}
+
+/// Type for EPP XML &lt;allocationToken:info&gt;
+#[derive(Serialize, Debug)]
+pub struct InfoData<'a> {
+    /// XML namespace for the allocation token extension
+    #[serde(rename = "xmlns:allocationToken")]
+    xmlns: &'a str,
+}
+
+/// Type for the allocation token extension in domain info commands, requesting the
+/// allocation token bound to the domain
+#[derive(Serialize, Debug)]
+pub struct Info<'a> {
+    #[serde(rename = "allocationToken:info")]
+    pub data: InfoData<'a>,
+}
+
+impl Default for Info<'static> {
+    fn default() -> Self {
+        Self {
+            data: InfoData { xmlns: XMLNS },
+        }
+    }
+}
+
+// Response
+
+/// Type that represents the &lt;extension&gt; tag for allocation token info responses
+#[derive(Deserialize, Debug)]
+pub struct InfoResponse {
+    /// The allocation token bound to the domain
+    #[serde(rename = "allocationToken")]
+    pub token: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{AllocationToken, Info};
+    use crate::domain::check::DomainCheck;
+    use crate::domain::create::DomainCreate;
+    use crate::domain::info::DomainInfo;
+    use crate::domain::renew::DomainRenew;
+    use crate::domain::transfer::DomainTransfer;
+    use crate::domain::Period;
+    use crate::response::ResultCode;
+    use crate::tests::{assert_serialized, response_from_file_with_ext, SUCCESS_MSG, SVTRID};
+
+    #[test]
+    fn check() {
+        let ext = AllocationToken::new("abc123");
+        let object = DomainCheck {
+            domains: &["eppdev.com", "eppdev.net"],
+        };
+
+        assert_serialized(
+            "request/extensions/allocation_token_check.xml",
+            (&object, &ext),
+        );
+    }
+
+    #[test]
+    fn create() {
+        let ext = AllocationToken::new("abc123");
+        let object = DomainCreate::new(
+            "eppdev.com",
+            Period::years(1).unwrap(),
+            None,
+            None,
+            "epP4uthd#v",
+            None,
+        );
+
+        assert_serialized(
+            "request/extensions/allocation_token_create.xml",
+            (&object, &ext),
+        );
+    }
+
+    #[test]
+    fn renew() {
+        let ext = AllocationToken::new("abc123");
+        let exp_date = NaiveDate::from_ymd_opt(2022, 7, 23).unwrap();
+        let object = DomainRenew::new("eppdev.com", exp_date, Period::years(1).unwrap());
+
+        assert_serialized(
+            "request/extensions/allocation_token_renew.xml",
+            (&object, &ext),
+        );
+    }
+
+    #[test]
+    fn transfer() {
+        let ext = AllocationToken::new("abc123");
+        let object = DomainTransfer::new("testing.com", Period::years(1).ok(), "epP4uthd#v");
+
+        assert_serialized(
+            "request/extensions/allocation_token_transfer.xml",
+            (&object, &ext),
+        );
+    }
+
+    #[test]
+    fn info() {
+        let object = DomainInfo::new("eppdev.com", Some("2fooBAR"));
+
+        assert_serialized(
+            "request/extensions/allocation_token_info.xml",
+            (&object, &Info::default()),
+        );
+    }
+
+    #[test]
+    fn info_response() {
+        let object = response_from_file_with_ext::<DomainInfo, Info>(
+            "response/extensions/allocation_token_info.xml",
+        );
+        let ext = object.extension.unwrap();
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(object.result.message, SUCCESS_MSG.into());
+        assert_eq!(ext.token, "abc123");
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+}
@@ -0,0 +1,211 @@
+//! Types for the EPP change poll extension
+//!
+//! As described in [RFC 8590](https://tools.ietf.org/html/rfc8590). The changed object is
+//! returned in the poll message data, see [`MessageData`](crate::message::poll::MessageData).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::message::poll::MessagePoll;
+use crate::request::{Extension, Transaction};
+
+pub const XMLNS: &str = "urn:ietf:params:xml:ns:changePoll-1.0";
+
+impl Transaction<ChangePoll> for MessagePoll<'_> {}
+
+impl Extension for ChangePoll {
+    type Response = ChangePollResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+// Request
+
+/// Marker extension to parse &lt;changePoll:changeData&gt; from poll responses
+///
+/// Servers add &lt;changePoll:changeData&gt; to a poll response whenever the message is about
+/// a change they made to an object, without the client asking for it. Using `ChangePoll`
+/// only selects that response type; the &lt;poll&gt; request is sent without an
+/// &lt;extension&gt; element.
+#[derive(Debug, Default)]
+pub struct ChangePoll;
+
+impl Serialize for ChangePoll {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+// Response
+
+/// Whether the object data in the poll message reflects the object before or after the change
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    Before,
+    After,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::After
+    }
+}
+
+/// The kind of operation that changed the object
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationType {
+    Create,
+    Delete,
+    Renew,
+    Transfer,
+    Update,
+    Restore,
+    AutoRenew,
+    AutoDelete,
+    AutoPurge,
+    Custom,
+}
+
+/// Type that represents the &lt;operation&gt; tag for change poll data
+#[derive(Debug, Deserialize)]
+pub struct Operation {
+    /// A server specific sub-operation, or the name of a custom operation
+    pub op: Option<String>,
+    /// The operation
+    #[serde(rename = "$value")]
+    pub kind: OperationType,
+}
+
+/// The kind of case that caused the change
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseType {
+    Udrp,
+    Urs,
+    Custom,
+}
+
+/// Type that represents the &lt;caseId&gt; tag for change poll data
+#[derive(Debug, Deserialize)]
+pub struct CaseId {
+    /// The type of the case
+    #[serde(rename = "type")]
+    pub case_type: CaseType,
+    /// The name of a custom case type
+    pub name: Option<String>,
+    /// The case identifier
+    #[serde(rename = "$value")]
+    pub id: String,
+}
+
+/// Type that represents the &lt;changeData&gt; tag for change poll messages
+#[derive(Debug, Deserialize)]
+pub struct ChangeData {
+    /// Whether the object data reflects the object before or after the change
+    #[serde(default)]
+    pub state: State,
+    /// The operation that changed the object
+    pub operation: Operation,
+    /// When the change happened
+    pub date: DateTime<Utc>,
+    /// The server transaction identifier of the change
+    #[serde(rename = "svTRID")]
+    pub server_tr_id: String,
+    /// The user or process that made the change
+    pub who: String,
+    /// The case that caused the change
+    #[serde(rename = "caseId")]
+    pub case_id: Option<CaseId>,
+    /// The reason for the change
+    pub reason: Option<String>,
+}
+
+/// Type that represents the &lt;extension&gt; tag for change poll messages
+#[derive(Debug, Deserialize)]
+pub struct ChangePollResponse {
+    /// Data under the &lt;changeData&gt; tag
+    #[serde(rename = "changeData")]
+    pub change_data: ChangeData,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{CaseType, ChangePoll, OperationType, State};
+    use crate::common::DomainStatus;
+    use crate::message::poll::{MessageData, MessagePoll};
+    use crate::response::ResultCode;
+    use crate::tests::{assert_serialized, response_from_file_with_ext, SVTRID};
+
+    #[test]
+    fn command() {
+        assert_serialized(
+            "request/message/poll.xml",
+            (&MessagePoll::default(), &ChangePoll),
+        );
+    }
+
+    #[test]
+    fn domain_update_response() {
+        let object = response_from_file_with_ext::<MessagePoll, ChangePoll>(
+            "response/message/poll_domain_info_change.xml",
+        );
+        let change = object.extension.unwrap().change_data;
+
+        assert_eq!(
+            object.result.code,
+            ResultCode::CommandCompletedSuccessfullyAckToDequeue
+        );
+        assert_eq!(change.state, State::After);
+        assert_eq!(change.operation.kind, OperationType::Update);
+        assert!(change.operation.op.is_none());
+        assert_eq!(
+            change.date,
+            Utc.with_ymd_and_hms(2022, 10, 22, 14, 25, 57).unwrap()
+        );
+        assert_eq!(change.server_tr_id, "12345-XYZ");
+        assert_eq!(change.who, "URS Admin");
+        let case_id = change.case_id.unwrap();
+        assert_eq!(case_id.case_type, CaseType::Urs);
+        assert_eq!(case_id.id, "urs123");
+        assert_eq!(change.reason.as_deref(), Some("URS Lock"));
+
+        match object.res_data.unwrap().message_data {
+            MessageData::DomainInfo(domain) => {
+                assert_eq!(domain.name, "eppdev.com".into());
+                let statuses = domain.statuses.unwrap();
+                assert!(statuses
+                    .iter()
+                    .any(|s| s.status == DomainStatus::ServerUpdateProhibited));
+            }
+            _ => panic!("Wrong type"),
+        }
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+
+    #[test]
+    fn host_delete_response() {
+        let object = response_from_file_with_ext::<MessagePoll, ChangePoll>(
+            "response/message/poll_host_info.xml",
+        );
+        let change = object.extension.unwrap().change_data;
+
+        assert_eq!(change.state, State::Before);
+        assert_eq!(change.operation.kind, OperationType::Delete);
+        assert_eq!(change.operation.op.as_deref(), Some("purge"));
+        assert_eq!(change.server_tr_id, "1234");
+        assert_eq!(change.who, "regy_batch");
+        assert!(change.case_id.is_none());
+        assert_eq!(change.reason.as_deref(), Some("Unused objects policy"));
+
+        match object.res_data.unwrap().message_data {
+            MessageData::HostInfo(host) => assert_eq!(host.name, "ns.test.com".into()),
+            _ => panic!("Wrong type"),
+        }
+    }
+}
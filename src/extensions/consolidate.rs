@@ -17,12 +17,20 @@ impl Transaction<Update> for DomainUpdate<'_> {}
 
 impl Extension for Update {
     type Response = NoExtension;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
 }
 
 impl Transaction<UpdateWithNameStore<'_>> for DomainUpdate<'_> {}
 
 impl Extension for UpdateWithNameStore<'_> {
     type Response = NameStore<'static>;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS, &self.namestore.xmlns]
+    }
 }
 
 #[derive(PartialEq, Eq, Debug)]
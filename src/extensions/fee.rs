@@ -0,0 +1,860 @@
+//! Types for the EPP registry fee extension
+//!
+//! As described in [RFC 8748](https://tools.ietf.org/html/rfc8748). Several drafts of the
+//! extension are still deployed by registries; the namespace used in each request is
+//! selected through [`Version`]. fee-0.23 uses the same structure as fee-1.0, while the
+//! fee-0.5 and fee-0.11 check commands differ and are covered by [`LegacyCheck`].
+
+use std::borrow::Cow;
+
+use serde::ser::{Error as _, SerializeStruct};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::common::StringValue;
+use crate::domain::{
+    check::DomainCheck, create::DomainCreate, renew::DomainRenew, transfer::DomainTransfer,
+    update::DomainUpdate, Period,
+};
+use crate::request::{Extension, Transaction};
+
+use super::rgp::request::{RgpRequestResponse, RgpRestoreRequest};
+use super::rgp::XMLNS as RGP_XMLNS;
+
+pub const XMLNS: &str = "urn:ietf:params:xml:ns:epp:fee-1.0";
+pub const XMLNS_0_23: &str = "urn:ietf:params:xml:ns:fee-0.23";
+pub const XMLNS_0_11: &str = "urn:ietf:params:xml:ns:fee-0.11";
+pub const XMLNS_0_5: &str = "urn:ietf:params:xml:ns:fee-0.5";
+
+impl Transaction<Check<'_>> for DomainCheck<'_> {}
+
+impl Extension for Check<'_> {
+    type Response = CheckResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![self.data.xmlns]
+    }
+}
+
+impl Transaction<LegacyCheck<'_>> for DomainCheck<'_> {}
+
+impl Extension for LegacyCheck<'_> {
+    type Response = LegacyCheckResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        match self.data.kind {
+            LegacyCheckKind::V0_5 { .. } => vec![XMLNS_0_5],
+            LegacyCheckKind::V0_11 => vec![XMLNS_0_11],
+        }
+    }
+}
+
+impl Transaction<Create<'_>> for DomainCreate<'_> {}
+
+impl Extension for Create<'_> {
+    type Response = TransformResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![self.data.xmlns]
+    }
+}
+
+impl Transaction<Renew<'_>> for DomainRenew<'_> {}
+
+impl Extension for Renew<'_> {
+    type Response = TransformResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![self.data.xmlns]
+    }
+}
+
+impl Transaction<Transfer<'_>> for DomainTransfer<'_> {}
+
+impl Extension for Transfer<'_> {
+    type Response = TransformResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![self.data.xmlns]
+    }
+}
+
+impl Transaction<Update<'_>> for DomainUpdate<'_> {}
+
+impl Extension for Update<'_> {
+    type Response = TransformResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![self.data.xmlns]
+    }
+}
+
+impl Transaction<UpdateWithRgpRestore<'_>> for DomainUpdate<'_> {}
+
+impl Extension for UpdateWithRgpRestore<'_> {
+    type Response = UpdateWithRgpRestoreResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![RGP_XMLNS, self.fee.xmlns]
+    }
+}
+
+/// The version of the fee extension, identified by its namespace
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Version {
+    V0_5,
+    V0_11,
+    V0_23,
+    V1_0,
+}
+
+impl Version {
+    /// The XML namespace for this version
+    pub fn xmlns(&self) -> &'static str {
+        match self {
+            Self::V0_5 => XMLNS_0_5,
+            Self::V0_11 => XMLNS_0_11,
+            Self::V0_23 => XMLNS_0_23,
+            Self::V1_0 => XMLNS,
+        }
+    }
+
+    /// Looks up the version for a namespace, e.g. one advertised in the server greeting
+    pub fn from_xmlns(xmlns: &str) -> Option<Self> {
+        match xmlns {
+            XMLNS_0_5 => Some(Self::V0_5),
+            XMLNS_0_11 => Some(Self::V0_11),
+            XMLNS_0_23 => Some(Self::V0_23),
+            XMLNS => Some(Self::V1_0),
+            _ => None,
+        }
+    }
+}
+
+/// The &lt;fee&gt; type, used both for fee agreements and fees returned by the server
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Fee<'a> {
+    /// A human-readable description of the fee
+    pub description: Option<Cow<'a, str>>,
+    /// Whether the fee is refundable if the object is deleted
+    pub refundable: Option<bool>,
+    /// The grace period in which the fee is refundable, as an XML duration
+    #[serde(rename = "grace-period")]
+    pub grace_period: Option<Cow<'a, str>>,
+    /// When the fee is applied, either "immediate" or "delayed"
+    pub applied: Option<Cow<'a, str>>,
+    /// The fee amount
+    #[serde(rename = "$value")]
+    pub amount: Cow<'a, str>,
+}
+
+impl<'a> Fee<'a> {
+    /// Creates a new Fee with the given amount
+    pub fn new(amount: &'a str) -> Self {
+        Self {
+            description: None,
+            refundable: None,
+            grace_period: None,
+            applied: None,
+            amount: amount.into(),
+        }
+    }
+}
+
+// Request
+
+/// A command for which fees are checked, represented by the &lt;command&gt; tag
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct FeeCommand<'a> {
+    /// The command name, e.g. "create", "renew", "transfer" or "restore"
+    pub name: &'a str,
+    /// The launch phase the command applies to
+    pub phase: Option<&'a str>,
+    /// The launch subphase the command applies to
+    pub subphase: Option<&'a str>,
+    /// The registration period the fees are checked for
+    #[serde(rename = "fee:period")]
+    pub period: Option<Period>,
+}
+
+impl<'a> FeeCommand<'a> {
+    /// Creates a new FeeCommand for the given command name
+    pub fn new(name: &'a str, period: Option<Period>) -> Self {
+        Self {
+            name,
+            phase: None,
+            subphase: None,
+            period,
+        }
+    }
+}
+
+/// Type for EPP XML &lt;fee:check&gt; for fee-1.0 and fee-0.23
+#[derive(Serialize, Debug)]
+pub struct CheckData<'a> {
+    /// XML namespace for the fee extension
+    #[serde(rename = "xmlns:fee")]
+    xmlns: &'a str,
+    /// The currency fees should be returned in
+    #[serde(rename = "fee:currency")]
+    currency: Option<StringValue<'a>>,
+    /// The commands to check fees for
+    #[serde(rename = "fee:command")]
+    commands: &'a [FeeCommand<'a>],
+}
+
+/// Type for the fee extension in domain check commands
+#[derive(Serialize, Debug)]
+pub struct Check<'a> {
+    #[serde(rename = "fee:check")]
+    pub data: CheckData<'a>,
+}
+
+impl<'a> Check<'a> {
+    /// Create a new fee check for the given commands
+    ///
+    /// Only [`Version::V1_0`] and [`Version::V0_23`] are supported, older versions should
+    /// use [`LegacyCheck`].
+    pub fn new(version: Version, commands: &'a [FeeCommand<'a>]) -> Self {
+        Self {
+            data: CheckData {
+                xmlns: version.xmlns(),
+                currency: None,
+                commands,
+            },
+        }
+    }
+
+    /// Set the currency fees should be returned in
+    pub fn currency(&mut self, currency: &'a str) {
+        self.data.currency = Some(currency.into());
+    }
+}
+
+#[derive(Debug)]
+enum LegacyCheckKind<'a> {
+    V0_5 { domains: &'a [&'a str] },
+    V0_11,
+}
+
+/// Type for EPP XML &lt;fee:check&gt; for fee-0.5 and fee-0.11
+#[derive(Debug)]
+pub struct LegacyCheckData<'a> {
+    kind: LegacyCheckKind<'a>,
+    currency: Option<&'a str>,
+    command: FeeCommand<'a>,
+}
+
+#[derive(Serialize)]
+struct LegacyCommand<'a> {
+    phase: Option<&'a str>,
+    subphase: Option<&'a str>,
+    #[serde(rename = "$value")]
+    name: &'a str,
+}
+
+impl<'a> From<&FeeCommand<'a>> for LegacyCommand<'a> {
+    fn from(command: &FeeCommand<'a>) -> Self {
+        Self {
+            phase: command.phase,
+            subphase: command.subphase,
+            name: command.name,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct LegacyDomain<'a> {
+    #[serde(rename = "fee:name")]
+    name: StringValue<'a>,
+    #[serde(rename = "fee:currency")]
+    currency: Option<StringValue<'a>>,
+    #[serde(rename = "fee:command")]
+    command: LegacyCommand<'a>,
+    #[serde(rename = "fee:period")]
+    period: Option<Period>,
+}
+
+impl Serialize for LegacyCheckData<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let currency = self.currency.map(StringValue::from);
+        let mut state = serializer.serialize_struct("fee:check", 4)?;
+        match self.kind {
+            LegacyCheckKind::V0_5 { domains } => {
+                if domains.is_empty() {
+                    return Err(S::Error::custom(
+                        "fee-0.5 check requires at least one domain",
+                    ));
+                }
+
+                let domains = domains
+                    .iter()
+                    .map(|&name| LegacyDomain {
+                        name: name.into(),
+                        currency: currency.clone(),
+                        command: LegacyCommand::from(&self.command),
+                        period: self.command.period,
+                    })
+                    .collect::<Vec<_>>();
+
+                state.serialize_field("xmlns:fee", XMLNS_0_5)?;
+                state.serialize_field("fee:domain", &domains)?;
+            }
+            LegacyCheckKind::V0_11 => {
+                state.serialize_field("xmlns:fee", XMLNS_0_11)?;
+                state.serialize_field("fee:command", &LegacyCommand::from(&self.command))?;
+                state.serialize_field("fee:currency", &currency)?;
+                state.serialize_field("fee:period", &self.command.period)?;
+            }
+        }
+        state.end()
+    }
+}
+
+/// Type for the fee extension in domain check commands for fee-0.5 and fee-0.11
+///
+/// These versions only check a single command per request.
+#[derive(Serialize, Debug)]
+pub struct LegacyCheck<'a> {
+    #[serde(rename = "fee:check")]
+    pub data: LegacyCheckData<'a>,
+}
+
+impl<'a> LegacyCheck<'a> {
+    /// Create a new fee-0.5 check; this version repeats the domain names in the extension
+    pub fn v0_5(domains: &'a [&'a str], command: FeeCommand<'a>) -> Self {
+        Self {
+            data: LegacyCheckData {
+                kind: LegacyCheckKind::V0_5 { domains },
+                currency: None,
+                command,
+            },
+        }
+    }
+
+    /// Create a new fee-0.11 check
+    pub fn v0_11(command: FeeCommand<'a>) -> Self {
+        Self {
+            data: LegacyCheckData {
+                kind: LegacyCheckKind::V0_11,
+                currency: None,
+                command,
+            },
+        }
+    }
+
+    /// Set the currency fees should be returned in
+    pub fn currency(&mut self, currency: &'a str) {
+        self.data.currency = Some(currency);
+    }
+}
+
+/// Type for the fee agreement in transform commands, e.g. &lt;fee:create&gt;
+#[derive(Serialize, Debug)]
+pub struct TransformRequestData<'a> {
+    /// XML namespace for the fee extension
+    #[serde(rename = "xmlns:fee")]
+    xmlns: &'a str,
+    /// The currency of the fees
+    #[serde(rename = "fee:currency")]
+    currency: Option<StringValue<'a>>,
+    /// The fees the client agrees to
+    #[serde(rename = "fee:fee")]
+    fees: &'a [Fee<'a>],
+}
+
+impl<'a> TransformRequestData<'a> {
+    /// Create a new fee agreement for the given fees
+    pub fn new(version: Version, fees: &'a [Fee<'a>]) -> Self {
+        Self {
+            xmlns: version.xmlns(),
+            currency: None,
+            fees,
+        }
+    }
+
+    /// Set the currency of the fees
+    pub fn currency(&mut self, currency: &'a str) {
+        self.currency = Some(currency.into());
+    }
+}
+
+/// Type for the fee extension in domain create commands
+#[derive(Serialize, Debug)]
+pub struct Create<'a> {
+    #[serde(rename = "fee:create")]
+    pub data: TransformRequestData<'a>,
+}
+
+/// Type for the fee extension in domain renew commands
+#[derive(Serialize, Debug)]
+pub struct Renew<'a> {
+    #[serde(rename = "fee:renew")]
+    pub data: TransformRequestData<'a>,
+}
+
+/// Type for the fee extension in domain transfer commands
+#[derive(Serialize, Debug)]
+pub struct Transfer<'a> {
+    #[serde(rename = "fee:transfer")]
+    pub data: TransformRequestData<'a>,
+}
+
+/// Type for the fee extension in domain update commands
+#[derive(Serialize, Debug)]
+pub struct Update<'a> {
+    #[serde(rename = "fee:update")]
+    pub data: TransformRequestData<'a>,
+}
+
+/// Type for a domain restore request carrying a fee agreement for the restore
+#[derive(Serialize, Debug)]
+pub struct UpdateWithRgpRestore<'a> {
+    #[serde(rename = "rgp:update")]
+    pub restore: RgpRestoreRequest<'a>,
+    #[serde(rename = "fee:update")]
+    pub fee: TransformRequestData<'a>,
+}
+
+impl<'a> UpdateWithRgpRestore<'a> {
+    /// Create a new RGP restore request with the given fee agreement
+    pub fn new(fee: TransformRequestData<'a>) -> Self {
+        Self {
+            restore: RgpRestoreRequest::default(),
+            fee,
+        }
+    }
+}
+
+// Response
+
+/// Type that represents the &lt;credit&gt; tag in fee responses
+#[derive(Deserialize, Debug)]
+pub struct Credit {
+    /// A human-readable description of the credit
+    pub description: Option<String>,
+    /// The credit amount, a negative value
+    #[serde(rename = "$value")]
+    pub amount: String,
+}
+
+/// Type that represents the &lt;command&gt; tag in fee check responses
+#[derive(Deserialize, Debug)]
+pub struct CommandFees {
+    /// The command name
+    pub name: String,
+    /// The launch phase the fees apply to
+    pub phase: Option<String>,
+    /// The launch subphase the fees apply to
+    pub subphase: Option<String>,
+    /// The period the fees apply to
+    pub period: Option<Period>,
+    /// The fees for the command
+    #[serde(rename = "fee", default)]
+    pub fees: Vec<Fee<'static>>,
+    /// The credits for the command
+    #[serde(rename = "credit", default)]
+    pub credits: Vec<Credit>,
+    /// The reason the fees could not be determined
+    pub reason: Option<String>,
+}
+
+fn available_default() -> bool {
+    true
+}
+
+/// Type that represents the &lt;cd&gt; tag in fee check responses
+#[derive(Deserialize, Debug)]
+pub struct CheckedObject {
+    /// The object identifier
+    #[serde(rename = "objID")]
+    pub id: String,
+    /// Whether fee information is available for the object
+    #[serde(rename = "avail", default = "available_default")]
+    pub available: bool,
+    /// The fee class of the object, e.g. "premium"
+    pub class: Option<String>,
+    /// The currency of the fees, only used by legacy versions
+    pub currency: Option<String>,
+    /// The fees per checked command
+    #[serde(rename = "command", default)]
+    pub commands: Vec<CommandFees>,
+    /// The reason the fees could not be determined
+    pub reason: Option<String>,
+}
+
+/// Type that represents the &lt;chkData&gt; tag in fee check responses
+#[derive(Deserialize, Debug)]
+pub struct CheckResponseData {
+    /// The currency of the fees
+    pub currency: Option<String>,
+    /// Data under the &lt;cd&gt; tags
+    #[serde(rename = "cd")]
+    pub list: Vec<CheckedObject>,
+}
+
+/// Type that represents the &lt;extension&gt; tag for fee check responses
+#[derive(Deserialize, Debug)]
+pub struct CheckResponse {
+    /// Data under the &lt;chkData&gt; tag
+    #[serde(rename = "chkData")]
+    pub check_data: CheckResponseData,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeserializedLegacyCommand {
+    phase: Option<String>,
+    subphase: Option<String>,
+    #[serde(rename = "$value")]
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeserializedLegacyCheckItem {
+    #[serde(rename = "objID", alias = "name")]
+    id: String,
+    avail: Option<bool>,
+    command: DeserializedLegacyCommand,
+    currency: Option<String>,
+    period: Option<Period>,
+    #[serde(rename = "fee", default)]
+    fees: Vec<Fee<'static>>,
+    #[serde(rename = "credit", default)]
+    credits: Vec<Credit>,
+    class: Option<String>,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeserializedLegacyCheckData {
+    #[serde(rename = "cd")]
+    list: Vec<DeserializedLegacyCheckItem>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DeserializedLegacyCheckResponse {
+    #[serde(rename = "chkData")]
+    check_data: DeserializedLegacyCheckData,
+}
+
+/// Type that represents the &lt;extension&gt; tag for fee-0.5 and fee-0.11 check responses
+///
+/// The legacy layout is converted to the same [`CheckResponseData`] as fee-1.0 responses.
+#[derive(Deserialize, Debug)]
+#[serde(from = "DeserializedLegacyCheckResponse")]
+pub struct LegacyCheckResponse {
+    pub check_data: CheckResponseData,
+}
+
+impl From<DeserializedLegacyCheckResponse> for LegacyCheckResponse {
+    fn from(rsp: DeserializedLegacyCheckResponse) -> Self {
+        let list = rsp
+            .check_data
+            .list
+            .into_iter()
+            .map(|item| CheckedObject {
+                id: item.id,
+                available: item.avail.unwrap_or(true),
+                class: item.class,
+                currency: item.currency,
+                commands: vec![CommandFees {
+                    name: item.command.name,
+                    phase: item.command.phase,
+                    subphase: item.command.subphase,
+                    period: item.period,
+                    fees: item.fees,
+                    credits: item.credits,
+                    reason: None,
+                }],
+                reason: item.reason,
+            })
+            .collect();
+
+        Self {
+            check_data: CheckResponseData {
+                currency: None,
+                list,
+            },
+        }
+    }
+}
+
+/// Type that represents the fee data returned for transform commands, e.g. &lt;creData&gt;
+#[derive(Deserialize, Debug)]
+pub struct TransformResponseData {
+    /// The currency of the fees
+    pub currency: Option<String>,
+    /// The period the fees apply to
+    pub period: Option<Period>,
+    /// The fees charged for the command
+    #[serde(rename = "fee", default)]
+    pub fees: Vec<Fee<'static>>,
+    /// The credits issued for the command
+    #[serde(rename = "credit", default)]
+    pub credits: Vec<Credit>,
+    /// The account balance after the command
+    pub balance: Option<String>,
+    /// The credit limit of the account
+    #[serde(rename = "creditLimit")]
+    pub credit_limit: Option<String>,
+}
+
+/// Type that represents the &lt;extension&gt; tag for fee transform responses
+#[derive(Deserialize, Debug)]
+pub struct TransformResponse {
+    #[serde(
+        rename = "creData",
+        alias = "renData",
+        alias = "trnData",
+        alias = "updData",
+        alias = "delData"
+    )]
+    pub data: TransformResponseData,
+}
+
+/// Type that represents the &lt;extension&gt; tag for domain restore responses with fees
+#[derive(Deserialize, Debug)]
+pub struct UpdateWithRgpRestoreResponse {
+    /// Data under the &lt;rgp:upData&gt; tag
+    #[serde(rename = "upData")]
+    pub restore: Option<RgpRequestResponse>,
+    /// Data under the &lt;fee:updData&gt; tag
+    #[serde(rename = "updData")]
+    pub fee: Option<TransformResponseData>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Check, Create, Fee, FeeCommand, LegacyCheck, Renew, TransformRequestData,
+        UpdateWithRgpRestore, Version, XMLNS_0_23,
+    };
+    use crate::domain::check::DomainCheck;
+    use crate::domain::create::DomainCreate;
+    use crate::domain::renew::DomainRenew;
+    use crate::domain::update::{DomainChangeInfo, DomainUpdate};
+    use crate::domain::Period;
+    use crate::extensions::rgp::RgpStatus;
+    use crate::response::ResultCode;
+    use crate::tests::{assert_serialized, response_from_file_with_ext, SUCCESS_MSG, SVTRID};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn version_from_xmlns() {
+        assert_eq!(Version::from_xmlns(XMLNS_0_23), Some(Version::V0_23));
+        assert_eq!(
+            Version::from_xmlns(Version::V0_5.xmlns()),
+            Some(Version::V0_5)
+        );
+        assert_eq!(Version::from_xmlns("urn:ietf:params:xml:ns:fee-0.4"), None);
+    }
+
+    #[test]
+    fn check() {
+        let commands = &[
+            FeeCommand::new("create", Some(Period::years(2).unwrap())),
+            FeeCommand::new("renew", None),
+            FeeCommand::new("transfer", None),
+            FeeCommand::new("restore", None),
+        ];
+        let mut ext = Check::new(Version::V1_0, commands);
+        ext.currency("USD");
+
+        let object = DomainCheck {
+            domains: &["eppdev.com", "eppdev.net"],
+        };
+
+        assert_serialized("request/extensions/fee_check.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn check_0_11() {
+        let mut command = FeeCommand::new("create", Some(Period::years(1).unwrap()));
+        command.phase = Some("sunrise");
+        let mut ext = LegacyCheck::v0_11(command);
+        ext.currency("USD");
+
+        let object = DomainCheck {
+            domains: &["eppdev.com", "eppdev.net"],
+        };
+
+        assert_serialized("request/extensions/fee_check_0_11.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn check_0_5() {
+        let domains = &["eppdev.com", "eppdev.net"];
+        let command = FeeCommand::new("renew", Some(Period::years(1).unwrap()));
+        let ext = LegacyCheck::v0_5(domains, command);
+
+        let object = DomainCheck { domains };
+
+        assert_serialized("request/extensions/fee_check_0_5.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn create() {
+        let fees = &[Fee::new("5.00")];
+        let mut data = TransformRequestData::new(Version::V1_0, fees);
+        data.currency("USD");
+        let ext = Create { data };
+
+        let object = DomainCreate::new(
+            "eppdev.com",
+            Period::years(1).unwrap(),
+            None,
+            None,
+            "epP4uthd#v",
+            None,
+        );
+
+        assert_serialized("request/extensions/fee_create.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn renew() {
+        let mut fee = Fee::new("10.00");
+        fee.description = Some("Renewal Fee".into());
+        let fees = &[fee];
+        let ext = Renew {
+            data: TransformRequestData::new(Version::V0_23, fees),
+        };
+
+        let exp_date = NaiveDate::from_ymd_opt(2022, 7, 23).unwrap();
+        let object = DomainRenew::new("eppdev.com", exp_date, Period::years(1).unwrap());
+
+        assert_serialized("request/extensions/fee_renew.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn restore() {
+        let fees = &[Fee::new("40.00")];
+        let ext = UpdateWithRgpRestore::new(TransformRequestData::new(Version::V1_0, fees));
+
+        let mut object = DomainUpdate::new("eppdev.com");
+        object.info(DomainChangeInfo {
+            registrant: None,
+            auth_info: None,
+        });
+
+        assert_serialized("request/extensions/fee_restore.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn check_response() {
+        let object =
+            response_from_file_with_ext::<DomainCheck, Check>("response/extensions/fee_check.xml");
+        let ext = object.extension.unwrap().check_data;
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(object.result.message, SUCCESS_MSG.into());
+        assert_eq!(ext.currency.as_deref(), Some("USD"));
+        assert_eq!(ext.list.len(), 2);
+
+        let premium = &ext.list[0];
+        assert_eq!(premium.id, "eppdev.com");
+        assert!(premium.available);
+        assert_eq!(premium.class.as_deref(), Some("premium"));
+        assert_eq!(premium.commands.len(), 2);
+
+        let create = &premium.commands[0];
+        assert_eq!(create.name, "create");
+        assert_eq!(create.period.unwrap().length(), 2);
+        assert_eq!(create.period.unwrap().unit(), 'y');
+        assert_eq!(create.fees[0].amount, "10.00");
+        assert_eq!(
+            create.fees[0].description.as_deref(),
+            Some("Registration Fee")
+        );
+        assert_eq!(create.fees[0].refundable, Some(true));
+        assert_eq!(create.fees[0].grace_period.as_deref(), Some("P5D"));
+        assert_eq!(premium.commands[1].name, "renew");
+        assert_eq!(premium.commands[1].fees[0].amount, "5.00");
+
+        let unavailable = &ext.list[1];
+        assert_eq!(unavailable.id, "eppdev.net");
+        assert!(!unavailable.available);
+        assert!(unavailable.commands[0].fees.is_empty());
+        assert_eq!(
+            unavailable.commands[0].reason.as_deref(),
+            Some("Only 1 year registration periods are valid.")
+        );
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+
+    #[test]
+    fn check_response_0_11() {
+        let object = response_from_file_with_ext::<DomainCheck, LegacyCheck>(
+            "response/extensions/fee_check_0_11.xml",
+        );
+        let ext = object.extension.unwrap().check_data;
+
+        assert_eq!(ext.list.len(), 1);
+        let checked = &ext.list[0];
+        assert_eq!(checked.id, "eppdev.com");
+        assert!(checked.available);
+        assert_eq!(checked.currency.as_deref(), Some("USD"));
+        assert_eq!(checked.class.as_deref(), Some("premium-tier1"));
+
+        let command = &checked.commands[0];
+        assert_eq!(command.name, "create");
+        assert_eq!(command.phase.as_deref(), Some("sunrise"));
+        assert_eq!(command.period.unwrap().length(), 1);
+        assert_eq!(command.fees.len(), 2);
+        assert_eq!(command.fees[0].amount, "5.00");
+        assert_eq!(command.fees[0].refundable, Some(false));
+        assert_eq!(command.fees[1].amount, "10.00");
+    }
+
+    #[test]
+    fn check_response_0_5() {
+        let object = response_from_file_with_ext::<DomainCheck, LegacyCheck>(
+            "response/extensions/fee_check_0_5.xml",
+        );
+        let ext = object.extension.unwrap().check_data;
+
+        assert_eq!(ext.list.len(), 2);
+        assert_eq!(ext.list[0].id, "eppdev.com");
+        assert_eq!(ext.list[0].commands[0].name, "renew");
+        assert_eq!(ext.list[0].commands[0].fees[0].amount, "5.00");
+        assert_eq!(ext.list[1].id, "eppdev.net");
+        assert_eq!(ext.list[1].commands[0].fees[0].amount, "5.00");
+    }
+
+    #[test]
+    fn create_response() {
+        let object = response_from_file_with_ext::<DomainCreate, Create>(
+            "response/extensions/fee_create.xml",
+        );
+        let ext = object.extension.unwrap().data;
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(ext.currency.as_deref(), Some("USD"));
+        assert_eq!(ext.fees[0].amount, "5.00");
+        assert_eq!(ext.fees[0].refundable, Some(true));
+        assert_eq!(ext.fees[0].grace_period.as_deref(), Some("P5D"));
+        assert_eq!(ext.balance.as_deref(), Some("-5.00"));
+        assert_eq!(ext.credit_limit.as_deref(), Some("1000.00"));
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+
+    #[test]
+    fn restore_response() {
+        let object = response_from_file_with_ext::<DomainUpdate, UpdateWithRgpRestore>(
+            "response/extensions/fee_restore.xml",
+        );
+        let ext = object.extension.unwrap();
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(
+            ext.restore.unwrap().rgp_status[0].status,
+            RgpStatus::PendingRestore
+        );
+
+        let fee = ext.fee.unwrap();
+        assert_eq!(fee.fees[0].amount, "40.00");
+        assert_eq!(fee.credits[0].amount, "-5.00");
+        assert_eq!(fee.credits[0].description.as_deref(), Some("AGP Credit"));
+        assert_eq!(fee.balance.as_deref(), Some("1005.00"));
+        assert!(fee.credit_limit.is_none());
+    }
+}
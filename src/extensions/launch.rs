@@ -0,0 +1,831 @@
+//! Types for the EPP launch phase mapping
+//!
+//! As described in [RFC 8334](https://tools.ietf.org/html/rfc8334). Marks are described by
+//! the mark and signed mark formats from [RFC 7848](https://tools.ietf.org/html/rfc7848).
+
+use std::borrow::Cow;
+
+use chrono::{DateTime, Utc};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Serialize};
+
+use crate::common::{serialize_element, serialize_element_option, NoExtension, StringValue};
+use crate::domain::{
+    check::DomainCheck, create::DomainCreate, delete::DomainDelete, info::DomainInfo,
+    update::DomainUpdate,
+};
+use crate::request::{Extension, Transaction};
+
+pub const XMLNS: &str = "urn:ietf:params:xml:ns:launch-1.0";
+pub const MARK_XMLNS: &str = "urn:ietf:params:xml:ns:mark-1.0";
+pub const SIGNED_MARK_XMLNS: &str = "urn:ietf:params:xml:ns:signedMark-1.0";
+
+impl Transaction<Check<'_>> for DomainCheck<'_> {}
+
+impl Extension for Check<'_> {
+    type Response = CheckResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+impl Transaction<Create<'_>> for DomainCreate<'_> {}
+
+impl Extension for Create<'_> {
+    type Response = CreateResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+impl Transaction<Info<'_>> for DomainInfo<'_> {}
+
+impl Extension for Info<'_> {
+    type Response = InfoResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+impl Transaction<Update<'_>> for DomainUpdate<'_> {}
+
+impl Extension for Update<'_> {
+    type Response = NoExtension;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+impl Transaction<Delete<'_>> for DomainDelete<'_> {}
+
+impl Extension for Delete<'_> {
+    type Response = NoExtension;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+/// The &lt;phase&gt; type identifying the launch phase of a command
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Phase<'a> {
+    /// The name of a custom phase or a sub-phase
+    pub name: Option<Cow<'a, str>>,
+    /// The launch phase, e.g. "sunrise" or "claims"
+    #[serde(rename = "$value")]
+    pub phase: Cow<'a, str>,
+}
+
+impl<'a> Phase<'a> {
+    pub const SUNRISE: Phase<'static> = Phase {
+        name: None,
+        phase: Cow::Borrowed("sunrise"),
+    };
+
+    pub const LANDRUSH: Phase<'static> = Phase {
+        name: None,
+        phase: Cow::Borrowed("landrush"),
+    };
+
+    pub const CLAIMS: Phase<'static> = Phase {
+        name: None,
+        phase: Cow::Borrowed("claims"),
+    };
+
+    pub const OPEN: Phase<'static> = Phase {
+        name: None,
+        phase: Cow::Borrowed("open"),
+    };
+
+    /// Creates a custom phase with the given name
+    pub fn custom(name: &'a str) -> Self {
+        Self {
+            name: Some(name.into()),
+            phase: "custom".into(),
+        }
+    }
+}
+
+// Request
+
+/// Type for EPP XML &lt;launch:check&gt;
+#[derive(Serialize, Debug)]
+pub struct CheckData<'a> {
+    /// XML namespace for the launch extension
+    #[serde(rename = "xmlns:launch")]
+    xmlns: &'a str,
+    /// The type of check, either "claims" or "avail"
+    #[serde(rename = "type")]
+    check_type: &'a str,
+    /// The launch phase to check
+    #[serde(rename = "launch:phase")]
+    phase: Phase<'a>,
+}
+
+/// Type for the launch extension in domain check commands
+#[derive(Serialize, Debug)]
+pub struct Check<'a> {
+    #[serde(rename = "launch:check")]
+    pub data: CheckData<'a>,
+}
+
+impl<'a> Check<'a> {
+    /// Create a claims check, to find out whether trademark claims exist for the domains
+    pub fn claims(phase: Phase<'a>) -> Self {
+        Self::new("claims", phase)
+    }
+
+    /// Create an availability check for the domains in the given phase
+    ///
+    /// The server answers availability checks in the regular domain check response data.
+    pub fn avail(phase: Phase<'a>) -> Self {
+        Self::new("avail", phase)
+    }
+
+    fn new(check_type: &'a str, phase: Phase<'a>) -> Self {
+        Self {
+            data: CheckData {
+                xmlns: XMLNS,
+                check_type,
+                phase,
+            },
+        }
+    }
+}
+
+/// The &lt;code&gt; type for a mark validation code
+#[derive(Serialize, Debug)]
+pub struct Code<'a> {
+    /// The identifier of the validator that issued the code
+    #[serde(rename = "validatorID")]
+    pub validator_id: Option<&'a str>,
+    /// The validation code
+    #[serde(rename = "$value")]
+    pub code: &'a str,
+}
+
+/// The &lt;addr&gt; type for mark holders
+#[derive(Serialize, Debug)]
+pub struct MarkAddress<'a> {
+    /// The street lines of the address
+    #[serde(rename = "mark:street")]
+    pub street: &'a [StringValue<'a>],
+    /// The city of the address
+    #[serde(rename = "mark:city")]
+    pub city: StringValue<'a>,
+    /// The state or province of the address
+    #[serde(rename = "mark:sp")]
+    pub province: Option<StringValue<'a>>,
+    /// The postal code of the address
+    #[serde(rename = "mark:pc")]
+    pub postal_code: Option<StringValue<'a>>,
+    /// The two-letter country code of the address
+    #[serde(rename = "mark:cc")]
+    pub country_code: StringValue<'a>,
+}
+
+/// The &lt;holder&gt; type for the holder of a mark
+#[derive(Serialize, Debug)]
+pub struct MarkHolder<'a> {
+    /// The entitlement of the holder, one of "owner", "assignee" or "licensee"
+    pub entitlement: &'a str,
+    /// The name of the holder
+    #[serde(rename = "mark:name")]
+    pub name: Option<StringValue<'a>>,
+    /// The organization of the holder
+    #[serde(rename = "mark:org")]
+    pub org: Option<StringValue<'a>>,
+    /// The address of the holder
+    #[serde(rename = "mark:addr")]
+    pub addr: MarkAddress<'a>,
+}
+
+/// The &lt;trademark&gt; type for a registered trademark
+#[derive(Serialize, Debug)]
+pub struct Trademark<'a> {
+    /// The mark identifier
+    #[serde(rename = "mark:id")]
+    pub id: StringValue<'a>,
+    /// The name of the mark
+    #[serde(rename = "mark:markName")]
+    pub mark_name: StringValue<'a>,
+    /// The holder of the mark
+    #[serde(rename = "mark:holder")]
+    pub holder: MarkHolder<'a>,
+    /// The two-letter country code of the jurisdiction the mark is registered in
+    #[serde(rename = "mark:jurisdiction")]
+    pub jurisdiction: StringValue<'a>,
+    /// The Nice classification classes of the mark
+    #[serde(rename = "mark:class", serialize_with = "serialize_classes")]
+    pub classes: &'a [u16],
+    /// The domain labels corresponding to the mark
+    #[serde(rename = "mark:label")]
+    pub labels: &'a [StringValue<'a>],
+    /// The goods and services the mark applies to
+    #[serde(rename = "mark:goodsAndServices")]
+    pub goods_and_services: StringValue<'a>,
+    /// The trademark registration number
+    #[serde(rename = "mark:regNum")]
+    pub registration_number: StringValue<'a>,
+    /// The date the trademark was registered
+    #[serde(rename = "mark:regDate", serialize_with = "serialize_element")]
+    pub registration_date: DateTime<Utc>,
+    /// The date the trademark expires
+    #[serde(rename = "mark:exDate", serialize_with = "serialize_element_option")]
+    pub expiry_date: Option<DateTime<Utc>>,
+}
+
+fn serialize_classes<S>(classes: &[u16], ser: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    let mut seq = ser.serialize_seq(Some(classes.len()))?;
+    for class in classes {
+        seq.serialize_element(&StringValue::from(class.to_string()))?;
+    }
+    seq.end()
+}
+
+/// The &lt;mark&gt; type from the mark-1.0 namespace
+#[derive(Serialize, Debug)]
+pub struct Mark<'a> {
+    /// XML namespace for marks
+    #[serde(rename = "xmlns:mark")]
+    xmlns: &'a str,
+    /// The trademark
+    #[serde(rename = "mark:trademark")]
+    trademark: Trademark<'a>,
+}
+
+impl<'a> Mark<'a> {
+    /// Creates a new Mark for the given trademark
+    pub fn new(trademark: Trademark<'a>) -> Self {
+        Self {
+            xmlns: MARK_XMLNS,
+            trademark,
+        }
+    }
+}
+
+/// The &lt;codeMark&gt; type, a mark and/or the code used to validate it
+#[derive(Serialize, Debug)]
+pub struct CodeMark<'a> {
+    /// The validation code
+    #[serde(rename = "launch:code")]
+    pub code: Option<Code<'a>>,
+    /// The mark
+    #[serde(rename = "mark:mark")]
+    pub mark: Option<Mark<'a>>,
+}
+
+/// The &lt;encodedSignedMark&gt; type holding a base64-encoded signed mark data (SMD) file
+#[derive(Serialize, Debug)]
+pub struct EncodedSignedMark<'a> {
+    /// XML namespace for signed marks
+    #[serde(rename = "xmlns:smd")]
+    xmlns: &'a str,
+    /// The base64-encoded signed mark
+    #[serde(rename = "$value")]
+    data: &'a str,
+}
+
+/// The &lt;noticeID&gt; type for a claims notice
+#[derive(Serialize, Debug)]
+pub struct NoticeId<'a> {
+    /// The identifier of the validator that issued the notice
+    #[serde(rename = "validatorID")]
+    pub validator_id: Option<&'a str>,
+    /// The notice identifier
+    #[serde(rename = "$value")]
+    pub id: &'a str,
+}
+
+/// The &lt;notice&gt; type for the claims notice accepted by the registrant
+#[derive(Serialize, Debug)]
+pub struct Notice<'a> {
+    /// The identifier of the claims notice
+    #[serde(rename = "launch:noticeID")]
+    pub notice_id: NoticeId<'a>,
+    /// The expiry of the claims notice
+    #[serde(rename = "launch:notAfter", serialize_with = "serialize_element")]
+    pub not_after: DateTime<Utc>,
+    /// The date the claims notice was accepted
+    #[serde(rename = "launch:acceptedDate", serialize_with = "serialize_element")]
+    pub accepted_date: DateTime<Utc>,
+}
+
+/// Type for EPP XML &lt;launch:create&gt;
+#[derive(Serialize, Debug)]
+pub struct CreateData<'a> {
+    /// XML namespace for the launch extension
+    #[serde(rename = "xmlns:launch")]
+    xmlns: &'a str,
+    /// The type of object to create, either "application" or "registration"
+    #[serde(rename = "type")]
+    create_type: Option<&'a str>,
+    /// The launch phase of the create
+    #[serde(rename = "launch:phase")]
+    phase: Phase<'a>,
+    /// The marks with optional validation codes
+    #[serde(rename = "launch:codeMark")]
+    code_marks: Option<&'a [CodeMark<'a>]>,
+    /// The encoded signed mark
+    #[serde(rename = "smd:encodedSignedMark")]
+    encoded_signed_mark: Option<EncodedSignedMark<'a>>,
+    /// The accepted claims notice
+    #[serde(rename = "launch:notice")]
+    notice: Option<Notice<'a>>,
+}
+
+/// Type for the launch extension in domain create commands
+#[derive(Serialize, Debug)]
+pub struct Create<'a> {
+    #[serde(rename = "launch:create")]
+    pub data: CreateData<'a>,
+}
+
+impl<'a> Create<'a> {
+    /// Create a new launch create extension for the given phase
+    pub fn new(phase: Phase<'a>) -> Self {
+        Self {
+            data: CreateData {
+                xmlns: XMLNS,
+                create_type: None,
+                phase,
+                code_marks: None,
+                encoded_signed_mark: None,
+                notice: None,
+            },
+        }
+    }
+
+    /// Explicitly request the creation of a launch application
+    pub fn application(&mut self) {
+        self.data.create_type = Some("application");
+    }
+
+    /// Explicitly request the creation of a registration
+    pub fn registration(&mut self) {
+        self.data.create_type = Some("registration");
+    }
+
+    /// Set the base64-encoded signed mark (SMD) validating the create
+    ///
+    /// This replaces any code marks set before.
+    pub fn encoded_signed_mark(&mut self, data: &'a str) {
+        self.data.code_marks = None;
+        self.data.encoded_signed_mark = Some(EncodedSignedMark {
+            xmlns: SIGNED_MARK_XMLNS,
+            data,
+        });
+    }
+
+    /// Set the code marks validating the create
+    ///
+    /// This replaces any encoded signed mark set before.
+    pub fn code_marks(&mut self, code_marks: &'a [CodeMark<'a>]) {
+        self.data.encoded_signed_mark = None;
+        self.data.code_marks = Some(code_marks);
+    }
+
+    /// Set the claims notice accepted by the registrant
+    pub fn notice(&mut self, notice: Notice<'a>) {
+        self.data.notice = Some(notice);
+    }
+}
+
+/// Type for EPP XML &lt;launch:info&gt;
+#[derive(Serialize, Debug)]
+pub struct InfoData<'a> {
+    /// XML namespace for the launch extension
+    #[serde(rename = "xmlns:launch")]
+    xmlns: &'a str,
+    /// Whether the mark should be included in the response
+    #[serde(rename = "includeMark")]
+    include_mark: Option<bool>,
+    /// The launch phase of the application
+    #[serde(rename = "launch:phase")]
+    phase: Phase<'a>,
+    /// The application identifier
+    #[serde(rename = "launch:applicationID")]
+    application_id: Option<StringValue<'a>>,
+}
+
+/// Type for the launch extension in domain info commands
+#[derive(Serialize, Debug)]
+pub struct Info<'a> {
+    #[serde(rename = "launch:info")]
+    pub data: InfoData<'a>,
+}
+
+impl<'a> Info<'a> {
+    /// Create a new launch info extension for the given phase and application
+    pub fn new(phase: Phase<'a>, application_id: Option<&'a str>) -> Self {
+        Self {
+            data: InfoData {
+                xmlns: XMLNS,
+                include_mark: None,
+                phase,
+                application_id: application_id.map(|id| id.into()),
+            },
+        }
+    }
+
+    /// Request the mark to be included in the response
+    pub fn include_mark(&mut self, include_mark: bool) {
+        self.data.include_mark = Some(include_mark);
+    }
+}
+
+/// Type for EPP XML &lt;launch:update&gt; and &lt;launch:delete&gt;
+#[derive(Serialize, Debug)]
+pub struct ApplicationData<'a> {
+    /// XML namespace for the launch extension
+    #[serde(rename = "xmlns:launch")]
+    xmlns: &'a str,
+    /// The launch phase of the application
+    #[serde(rename = "launch:phase")]
+    phase: Phase<'a>,
+    /// The application identifier
+    #[serde(rename = "launch:applicationID")]
+    application_id: StringValue<'a>,
+}
+
+impl<'a> ApplicationData<'a> {
+    fn new(phase: Phase<'a>, application_id: &'a str) -> Self {
+        Self {
+            xmlns: XMLNS,
+            phase,
+            application_id: application_id.into(),
+        }
+    }
+}
+
+/// Type for the launch extension in domain update commands
+#[derive(Serialize, Debug)]
+pub struct Update<'a> {
+    #[serde(rename = "launch:update")]
+    pub data: ApplicationData<'a>,
+}
+
+impl<'a> Update<'a> {
+    /// Create a new launch update extension for the given application
+    pub fn new(phase: Phase<'a>, application_id: &'a str) -> Self {
+        Self {
+            data: ApplicationData::new(phase, application_id),
+        }
+    }
+}
+
+/// Type for the launch extension in domain delete commands
+#[derive(Serialize, Debug)]
+pub struct Delete<'a> {
+    #[serde(rename = "launch:delete")]
+    pub data: ApplicationData<'a>,
+}
+
+impl<'a> Delete<'a> {
+    /// Create a new launch delete extension for the given application
+    pub fn new(phase: Phase<'a>, application_id: &'a str) -> Self {
+        Self {
+            data: ApplicationData::new(phase, application_id),
+        }
+    }
+}
+
+// Response
+
+/// Type that represents the &lt;claimKey&gt; tag for claims check responses
+#[derive(Deserialize, Debug)]
+pub struct ClaimKey {
+    /// The identifier of the validator holding the claims
+    #[serde(rename = "validatorID")]
+    pub validator_id: Option<String>,
+    /// The key to fetch the claims notice with
+    #[serde(rename = "$value")]
+    pub key: String,
+}
+
+/// Type that represents the &lt;name&gt; tag for claims check responses
+#[derive(Deserialize, Debug)]
+struct ClaimsName {
+    #[serde(rename = "$value")]
+    name: StringValue<'static>,
+    exists: bool,
+}
+
+/// Type that represents the &lt;cd&gt; tag for claims check responses
+#[derive(Deserialize, Debug)]
+struct CheckResponseDataItem {
+    name: ClaimsName,
+    #[serde(rename = "claimKey", default)]
+    claim_keys: Vec<ClaimKey>,
+}
+
+/// Type that represents the &lt;chkData&gt; tag for claims check responses
+#[derive(Deserialize, Debug)]
+struct CheckResponseData {
+    phase: Phase<'static>,
+    #[serde(rename = "cd")]
+    list: Vec<CheckResponseDataItem>,
+}
+
+/// Type that represents the &lt;extension&gt; tag for claims check responses
+#[derive(Deserialize, Debug)]
+struct DeserializedCheckResponse {
+    #[serde(rename = "chkData")]
+    check_data: CheckResponseData,
+}
+
+/// The claims check result for a single domain
+#[derive(Debug)]
+pub struct Claims {
+    pub name: String,
+    pub exists: bool,
+    pub claim_keys: Vec<ClaimKey>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(from = "DeserializedCheckResponse")]
+pub struct CheckResponse {
+    pub phase: Phase<'static>,
+    pub list: Vec<Claims>,
+}
+
+impl From<DeserializedCheckResponse> for CheckResponse {
+    fn from(rsp: DeserializedCheckResponse) -> Self {
+        Self {
+            phase: rsp.check_data.phase,
+            list: rsp
+                .check_data
+                .list
+                .into_iter()
+                .map(|item| Claims {
+                    name: item.name.name.to_string(),
+                    exists: item.name.exists,
+                    claim_keys: item.claim_keys,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Type that represents the &lt;creData&gt; tag for launch create responses
+#[derive(Deserialize, Debug)]
+pub struct CreateResponseData {
+    /// The launch phase of the create
+    pub phase: Phase<'static>,
+    /// The identifier of the created application
+    #[serde(rename = "applicationID")]
+    pub application_id: Option<String>,
+}
+
+/// Type that represents the &lt;extension&gt; tag for launch create responses
+#[derive(Deserialize, Debug)]
+pub struct CreateResponse {
+    /// Data under the &lt;creData&gt; tag
+    #[serde(rename = "creData")]
+    pub create_data: CreateResponseData,
+}
+
+/// Type that represents the &lt;status&gt; tag of a launch application
+#[derive(Deserialize, Debug)]
+pub struct ApplicationStatus {
+    /// The status, e.g. "pendingValidation" or "allocated"
+    #[serde(rename = "s")]
+    pub status: String,
+    /// The name of a custom status
+    pub name: Option<String>,
+    /// A human-readable reason for the status
+    #[serde(rename = "$value")]
+    pub reason: Option<String>,
+}
+
+/// Type that represents the &lt;infData&gt; tag for launch info responses
+#[derive(Deserialize, Debug)]
+pub struct InfoResponseData {
+    /// The launch phase of the application
+    pub phase: Phase<'static>,
+    /// The application identifier
+    #[serde(rename = "applicationID")]
+    pub application_id: Option<String>,
+    /// The status of the application
+    pub status: Option<ApplicationStatus>,
+}
+
+/// Type that represents the &lt;extension&gt; tag for launch info responses
+#[derive(Deserialize, Debug)]
+pub struct InfoResponse {
+    /// Data under the &lt;infData&gt; tag
+    #[serde(rename = "infData")]
+    pub info_data: InfoResponseData,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{
+        Check, Code, CodeMark, Create, Delete, Info, Mark, MarkAddress, MarkHolder, Notice,
+        NoticeId, Phase, Trademark, Update,
+    };
+    use crate::domain::check::DomainCheck;
+    use crate::domain::create::DomainCreate;
+    use crate::domain::delete::DomainDelete;
+    use crate::domain::info::DomainInfo;
+    use crate::domain::update::DomainUpdate;
+    use crate::domain::Period;
+    use crate::response::ResultCode;
+    use crate::tests::{assert_serialized, response_from_file_with_ext, SUCCESS_MSG, SVTRID};
+
+    fn domain_create() -> DomainCreate<'static> {
+        DomainCreate::new(
+            "eppdev.com",
+            Period::years(1).unwrap(),
+            None,
+            None,
+            "epP4uthd#v",
+            None,
+        )
+    }
+
+    #[test]
+    fn claims_check() {
+        let ext = Check::claims(Phase::CLAIMS);
+        let object = DomainCheck {
+            domains: &["eppdev.com", "eppdev.net"],
+        };
+
+        assert_serialized(
+            "request/extensions/launch_claims_check.xml",
+            (&object, &ext),
+        );
+    }
+
+    #[test]
+    fn avail_check() {
+        let ext = Check::avail(Phase::custom("idn-release"));
+        let object = DomainCheck {
+            domains: &["eppdev.com", "eppdev.net"],
+        };
+
+        assert_serialized("request/extensions/launch_avail_check.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn create_encoded_signed_mark() {
+        let mut ext = Create::new(Phase::SUNRISE);
+        ext.encoded_signed_mark("PD94bWwgdmVyc2lvbj0iMS4wIiBlbmNvZGluZz0iVVRGLTgiPz4K");
+
+        assert_serialized(
+            "request/extensions/launch_create_smd.xml",
+            (&domain_create(), &ext),
+        );
+    }
+
+    #[test]
+    fn create_code_mark() {
+        let street = &["123 Example Dr.".into(), "Suite 100".into()];
+        let labels = &["eppdev".into()];
+        let trademark = Trademark {
+            id: "1234-2".into(),
+            mark_name: "EPP Dev".into(),
+            holder: MarkHolder {
+                entitlement: "owner",
+                name: None,
+                org: Some("EPP Dev Inc.".into()),
+                addr: MarkAddress {
+                    street,
+                    city: "Reston".into(),
+                    province: Some("VA".into()),
+                    postal_code: Some("20190".into()),
+                    country_code: "US".into(),
+                },
+            },
+            jurisdiction: "US".into(),
+            classes: &[35, 36],
+            labels,
+            goods_and_services: "Domain registration services".into(),
+            registration_number: "234235".into(),
+            registration_date: Utc.with_ymd_and_hms(2009, 8, 16, 9, 0, 0).unwrap(),
+            expiry_date: Some(Utc.with_ymd_and_hms(2025, 8, 16, 9, 0, 0).unwrap()),
+        };
+        let code_marks = &[CodeMark {
+            code: Some(Code {
+                validator_id: Some("sample"),
+                code: "49FD46E6C4B45C55D4AC",
+            }),
+            mark: Some(Mark::new(trademark)),
+        }];
+
+        let mut ext = Create::new(Phase::SUNRISE);
+        ext.application();
+        ext.code_marks(code_marks);
+
+        assert_serialized(
+            "request/extensions/launch_create_code_mark.xml",
+            (&domain_create(), &ext),
+        );
+    }
+
+    #[test]
+    fn create_claims_notice() {
+        let mut ext = Create::new(Phase::CLAIMS);
+        ext.notice(Notice {
+            notice_id: NoticeId {
+                validator_id: Some("tmch"),
+                id: "370d0b7c9223372036854775807",
+            },
+            not_after: Utc.with_ymd_and_hms(2022, 6, 19, 10, 0, 0).unwrap(),
+            accepted_date: Utc.with_ymd_and_hms(2022, 6, 19, 9, 0, 0).unwrap(),
+        });
+
+        assert_serialized(
+            "request/extensions/launch_create_notice.xml",
+            (&domain_create(), &ext),
+        );
+    }
+
+    #[test]
+    fn info() {
+        let mut ext = Info::new(Phase::SUNRISE, Some("abc123"));
+        ext.include_mark(true);
+        let object = DomainInfo::new("eppdev.com", Some("2fooBAR"));
+
+        assert_serialized("request/extensions/launch_info.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn update() {
+        let ext = Update::new(Phase::SUNRISE, "abc123");
+        let object = DomainUpdate::new("eppdev.com");
+
+        assert_serialized("request/extensions/launch_update.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn delete() {
+        let ext = Delete::new(Phase::SUNRISE, "abc123");
+        let object = DomainDelete::new("eppdev.com");
+
+        assert_serialized("request/extensions/launch_delete.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn claims_check_response() {
+        let object = response_from_file_with_ext::<DomainCheck, Check>(
+            "response/extensions/launch_claims_check.xml",
+        );
+        let ext = object.extension.unwrap();
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(object.result.message, SUCCESS_MSG.into());
+        assert_eq!(ext.phase, Phase::CLAIMS);
+        assert_eq!(ext.list[0].name, "eppdev.com");
+        assert!(!ext.list[0].exists);
+        assert!(ext.list[0].claim_keys.is_empty());
+        assert_eq!(ext.list[1].name, "eppdev.net");
+        assert!(ext.list[1].exists);
+        assert_eq!(
+            ext.list[1].claim_keys[0].validator_id.as_deref(),
+            Some("tmch")
+        );
+        assert_eq!(
+            ext.list[1].claim_keys[0].key,
+            "2013041500/2/6/9/rJ1NrDO92vDsAzf7EQzgjX4R0000000001"
+        );
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+
+    #[test]
+    fn create_response() {
+        let object = response_from_file_with_ext::<DomainCreate, Create>(
+            "response/extensions/launch_create.xml",
+        );
+        let ext = object.extension.unwrap().create_data;
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(ext.phase, Phase::SUNRISE);
+        assert_eq!(ext.application_id.as_deref(), Some("2393-9323-E08C-03B1"));
+    }
+
+    #[test]
+    fn info_response() {
+        let object =
+            response_from_file_with_ext::<DomainInfo, Info>("response/extensions/launch_info.xml");
+        let ext = object.extension.unwrap().info_data;
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(ext.phase, Phase::custom("idn-release"));
+        assert_eq!(ext.application_id.as_deref(), Some("abc123"));
+
+        let status = ext.status.unwrap();
+        assert_eq!(status.status, "pendingValidation");
+        assert!(status.name.is_none());
+        assert!(status.reason.is_none());
+    }
+}
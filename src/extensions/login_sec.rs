@@ -0,0 +1,301 @@
+//! Types for the EPP login security extension
+//!
+//! As described in [RFC 8807](https://tools.ietf.org/html/rfc8807). Passwords moved into the
+//! extension must be replaced by [`PASSWORD_PLACEHOLDER`] in the [`Login`] command, and
+//! [`XMLNS`] must be included in its extension URIs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::common::StringValue;
+use crate::login::Login;
+use crate::request::{Extension, Transaction};
+
+pub const XMLNS: &str = "urn:ietf:params:xml:ns:epp:loginSec-1.0";
+
+/// The value of the &lt;pw&gt; and &lt;newPW&gt; tags in the login command when the
+/// password is carried in the extension
+pub const PASSWORD_PLACEHOLDER: &str = "[LOGIN-SECURITY]";
+
+impl Transaction<LoginSec<'_>> for Login<'_> {}
+
+impl Extension for LoginSec<'_> {
+    type Response = LoginSecResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+// Request
+
+/// The &lt;userAgent&gt; type describing the client software
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct UserAgent<'a> {
+    /// The name and version of the client application
+    #[serde(rename = "loginSec:app")]
+    pub app: Option<StringValue<'a>>,
+    /// The name and version of the technology the client is built with
+    #[serde(rename = "loginSec:tech")]
+    pub tech: Option<StringValue<'a>>,
+    /// The name and version of the operating system
+    #[serde(rename = "loginSec:os")]
+    pub os: Option<StringValue<'a>>,
+}
+
+/// Type for EPP XML &lt;loginSec:loginSec&gt;
+#[derive(Serialize, Debug, Clone)]
+pub struct LoginSecData<'a> {
+    /// XML namespace for the login security extension
+    #[serde(rename = "xmlns:loginSec")]
+    xmlns: &'a str,
+    /// The client user agent
+    #[serde(rename = "loginSec:userAgent")]
+    user_agent: Option<UserAgent<'a>>,
+    /// The password
+    #[serde(rename = "loginSec:pw")]
+    password: Option<StringValue<'a>>,
+    /// The new password
+    #[serde(rename = "loginSec:newPW")]
+    new_password: Option<StringValue<'a>>,
+}
+
+/// Type for the login security extension in login commands
+#[derive(Serialize, Debug, Clone)]
+pub struct LoginSec<'a> {
+    #[serde(rename = "loginSec:loginSec")]
+    pub data: LoginSecData<'a>,
+}
+
+impl<'a> LoginSec<'a> {
+    /// Create a new login security extension
+    ///
+    /// When `password` or `new_password` is set, the corresponding field in the [`Login`]
+    /// command should be [`PASSWORD_PLACEHOLDER`].
+    pub fn new(
+        user_agent: Option<UserAgent<'a>>,
+        password: Option<&'a str>,
+        new_password: Option<&'a str>,
+    ) -> Self {
+        Self {
+            data: LoginSecData {
+                xmlns: XMLNS,
+                user_agent,
+                password: password.map(Into::into),
+                new_password: new_password.map(Into::into),
+            },
+        }
+    }
+
+    /// Converts into a `LoginSec` that owns its data
+    pub fn into_owned(self) -> LoginSec<'static> {
+        let data = self.data;
+        LoginSec {
+            data: LoginSecData {
+                xmlns: XMLNS,
+                user_agent: data.user_agent.map(UserAgent::into_owned),
+                password: data.password.map(StringValue::into_owned),
+                new_password: data.new_password.map(StringValue::into_owned),
+            },
+        }
+    }
+
+    /// Converts into a `LoginSec` for logging in again later, see `Login::into_relogin()`
+    pub(crate) fn into_relogin(self) -> LoginSec<'static> {
+        let mut login_sec = self.into_owned();
+        if let Some(new_password) = login_sec.data.new_password.take() {
+            login_sec.data.password = Some(new_password);
+        }
+        login_sec
+    }
+}
+
+impl UserAgent<'_> {
+    /// Converts into a `UserAgent` that owns its data
+    pub fn into_owned(self) -> UserAgent<'static> {
+        UserAgent {
+            app: self.app.map(StringValue::into_owned),
+            tech: self.tech.map(StringValue::into_owned),
+            os: self.os.map(StringValue::into_owned),
+        }
+    }
+}
+
+// Response
+
+/// The type of a login security event, as described in section 3.1 of RFC 8807
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventType {
+    Password,
+    Certificate,
+    Cipher,
+    TlsProtocol,
+    NewPassword,
+    Stat,
+    Custom,
+    /// A type not described in RFC 8807, kept as it was received
+    Other(String),
+}
+
+impl EventType {
+    /// The value of the 'type' attr
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Password => "password",
+            Self::Certificate => "certificate",
+            Self::Cipher => "cipher",
+            Self::TlsProtocol => "tlsProtocol",
+            Self::NewPassword => "newPW",
+            Self::Stat => "stat",
+            Self::Custom => "custom",
+            Self::Other(event_type) => event_type,
+        }
+    }
+}
+
+impl From<&str> for EventType {
+    fn from(s: &str) -> Self {
+        match s {
+            "password" => Self::Password,
+            "certificate" => Self::Certificate,
+            "cipher" => Self::Cipher,
+            "tlsProtocol" => Self::TlsProtocol,
+            "newPW" => Self::NewPassword,
+            "stat" => Self::Stat,
+            "custom" => Self::Custom,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EventType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let event_type = String::deserialize(deserializer)?;
+        Ok(Self::from(event_type.as_str()))
+    }
+}
+
+/// The level of a login security event
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventLevel {
+    Warning,
+    Error,
+}
+
+/// Type that represents the &lt;event&gt; tag for login security responses
+#[derive(Debug, Deserialize)]
+pub struct Event {
+    /// The type of the event
+    #[serde(rename = "type")]
+    pub event_type: EventType,
+    /// The name of a custom or statistical event
+    pub name: Option<String>,
+    /// The level of the event
+    pub level: EventLevel,
+    /// When the password or certificate expires
+    #[serde(rename = "exDate")]
+    pub expiring_at: Option<DateTime<Utc>>,
+    /// The value that triggered the event, e.g. the negotiated cipher
+    pub value: Option<String>,
+    /// The period a statistical value applies to, as an XML duration
+    pub duration: Option<String>,
+    /// The language of the description
+    pub lang: Option<String>,
+    /// A human-readable description of the event
+    #[serde(rename = "$value")]
+    pub description: Option<String>,
+}
+
+/// Type that represents the &lt;loginSecData&gt; tag for login security responses
+#[derive(Debug, Deserialize)]
+pub struct LoginSecResponseData {
+    /// The login security events
+    #[serde(rename = "event")]
+    pub events: Vec<Event>,
+}
+
+/// Type that represents the &lt;extension&gt; tag for login security responses
+#[derive(Debug, Deserialize)]
+pub struct LoginSecResponse {
+    /// Data under the &lt;loginSecData&gt; tag
+    #[serde(rename = "loginSecData")]
+    pub data: LoginSecResponseData,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{EventLevel, EventType, LoginSec, UserAgent, PASSWORD_PLACEHOLDER, XMLNS};
+    use crate::login::Login;
+    use crate::response::ResultCode;
+    use crate::tests::{assert_serialized, response_from_file_with_ext, SUCCESS_MSG, SVTRID};
+
+    #[test]
+    fn command() {
+        let object = Login::new(
+            "username",
+            PASSWORD_PLACEHOLDER,
+            Some(PASSWORD_PLACEHOLDER),
+            Some(&[XMLNS]),
+        );
+        let user_agent = UserAgent {
+            app: Some("EPP Client 1.0".into()),
+            tech: Some("Rust 1.59".into()),
+            os: None,
+        };
+        let ext = LoginSec::new(
+            Some(user_agent),
+            Some("this is a long password"),
+            Some("an even longer new password"),
+        );
+
+        assert_serialized("request/extensions/login_sec.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn response() {
+        let object =
+            response_from_file_with_ext::<Login, LoginSec>("response/extensions/login_sec.xml");
+        let events = object.extension.unwrap().data.events;
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(object.result.message, SUCCESS_MSG.into());
+        assert_eq!(events.len(), 5);
+
+        assert_eq!(events[0].event_type, EventType::Password);
+        assert_eq!(events[0].level, EventLevel::Warning);
+        assert_eq!(
+            events[0].expiring_at,
+            Utc.with_ymd_and_hms(2022, 3, 24, 22, 0, 0).single()
+        );
+        assert_eq!(events[0].lang.as_deref(), Some("en"));
+        assert_eq!(
+            events[0].description.as_deref(),
+            Some("Password expiring in a week")
+        );
+
+        assert_eq!(events[1].event_type, EventType::Certificate);
+        assert_eq!(
+            events[1].expiring_at,
+            Utc.with_ymd_and_hms(2022, 4, 1, 22, 0, 0).single()
+        );
+        assert!(events[1].description.is_none());
+
+        assert_eq!(events[2].event_type, EventType::NewPassword);
+        assert_eq!(events[2].level, EventLevel::Error);
+
+        assert_eq!(events[3].event_type, EventType::Stat);
+        assert_eq!(events[3].name.as_deref(), Some("failedLogins"));
+        assert_eq!(events[3].value.as_deref(), Some("100"));
+        assert_eq!(events[3].duration.as_deref(), Some("P1D"));
+
+        assert_eq!(
+            events[4].event_type,
+            EventType::Other("accountLock".to_owned())
+        );
+        assert_eq!(events[4].event_type.as_str(), "accountLock");
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+}
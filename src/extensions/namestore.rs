@@ -51,7 +51,7 @@ impl Transaction<NameStore<'_>> for HostUpdate<'_> {}
 
 impl<'a> NameStore<'a> {
     /// Create a new RGP restore report request
-    pub fn new(subproduct: &str) -> NameStore {
+    pub fn new(subproduct: &str) -> NameStore<'_> {
         NameStore {
             data: NameStoreData {
                 xmlns: XMLNS.into(),
@@ -73,6 +73,10 @@ impl<'a> NameStoreData<'a> {
 
 impl<'a> Extension for NameStore<'a> {
     type Response = NameStore<'static>;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![&self.data.xmlns]
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::common::status_enum;
+
 pub mod report;
 pub mod request;
 
@@ -15,3 +17,16 @@ pub struct Update<T> {
     )]
     pub data: T,
 }
+
+status_enum! {
+    /// Grace period statuses, as described in section 3.2 of RFC 3915
+    pub enum RgpStatus ("RFC 3915") {
+        AddPeriod => "addPeriod",
+        AutoRenewPeriod => "autoRenewPeriod",
+        RenewPeriod => "renewPeriod",
+        TransferPeriod => "transferPeriod",
+        RedemptionPeriod => "redemptionPeriod",
+        PendingRestore => "pendingRestore",
+        PendingDelete => "pendingDelete",
+    }
+}
@@ -47,6 +47,10 @@ impl<'a> RgpRestoreReport<'a> {
 
 impl<'a> Extension for Update<RgpRestoreReport<'a>> {
     type Response = NoExtension;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
 }
 
 /// Type corresponding to the &lt;report&gt; section in the EPP rgp restore extension
@@ -1,13 +1,14 @@
 //! Types for EPP RGP restore request
 
 use crate::{
+    common::ObjectStatus,
     domain::{info::DomainInfo, update::DomainUpdate},
     request::{Extension, Transaction},
 };
 
 use serde::{Deserialize, Serialize};
 
-use super::{Update, XMLNS};
+use super::{RgpStatus, Update, XMLNS};
 
 impl<'a> Transaction<Update<RgpRestoreRequest<'a>>> for DomainUpdate<'a> {}
 
@@ -15,6 +16,10 @@ impl<'a> Transaction<Update<RgpRestoreRequest<'a>>> for DomainInfo<'a> {}
 
 impl<'a> Extension for Update<RgpRestoreRequest<'a>> {
     type Response = Update<RgpRequestResponse>;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
 }
 
 // Request
@@ -48,26 +53,18 @@ impl Default for RgpRestoreRequest<'static> {
 
 // Response
 
-/// Type that represents the &lt;rgpStatus&gt; tag for domain rgp restore request response
-#[derive(Deserialize, Debug)]
-pub struct RgpStatus {
-    /// The domain RGP status
-    #[serde(rename = "s")]
-    pub status: String,
-}
-
 #[derive(Deserialize, Debug)]
 #[serde(rename = "upData")]
 /// Type that represents the &lt;resData&gt; tag for domain transfer response
 pub struct RgpRequestResponse {
     /// Data under the &lt;rgpStatus&gt; tag
     #[serde(rename = "rgpStatus")]
-    pub rgp_status: Vec<RgpStatus>,
+    pub rgp_status: Vec<ObjectStatus<RgpStatus>>,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{RgpRestoreRequest, Update};
+    use super::{RgpRestoreRequest, RgpStatus, Update};
     use crate::domain::info::DomainInfo;
     use crate::domain::update::{DomainChangeInfo, DomainUpdate};
     use crate::response::ResultCode;
@@ -103,7 +100,7 @@ mod tests {
 
         assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
         assert_eq!(object.result.message, SUCCESS_MSG.into());
-        assert_eq!(ext.data.rgp_status[0].status, "pendingRestore".to_string());
+        assert_eq!(ext.data.rgp_status[0].status, RgpStatus::PendingRestore);
         assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
     }
 
@@ -114,7 +111,7 @@ mod tests {
         );
         let ext = object.extension.unwrap();
 
-        assert_eq!(ext.data.rgp_status[0].status, "addPeriod");
-        assert_eq!(ext.data.rgp_status[1].status, "renewPeriod");
+        assert_eq!(ext.data.rgp_status[0].status, RgpStatus::AddPeriod);
+        assert_eq!(ext.data.rgp_status[1].status, RgpStatus::RenewPeriod);
     }
 }
@@ -0,0 +1,474 @@
+//! Types for the EPP DNS security extension (secDNS-1.1)
+//!
+//! As described in [RFC 5910](https://tools.ietf.org/html/rfc5910).
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::common::{serialize_element, serialize_element_option, NoExtension, StringValue};
+use crate::domain::{create::DomainCreate, info::DomainInfo, update::DomainUpdate};
+use crate::request::{Extension, Transaction};
+
+pub const XMLNS: &str = "urn:ietf:params:xml:ns:secDNS-1.1";
+
+impl Transaction<Create<'_>> for DomainCreate<'_> {}
+
+impl Extension for Create<'_> {
+    type Response = NoExtension;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+impl Transaction<Update<'_>> for DomainUpdate<'_> {}
+
+impl Extension for Update<'_> {
+    type Response = NoExtension;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+impl Transaction<Info> for DomainInfo<'_> {}
+
+impl Extension for Info {
+    type Response = InfoResponse;
+
+    fn namespaces(&self) -> Vec<&str> {
+        vec![XMLNS]
+    }
+}
+
+// Request
+
+/// Either a list of DS records or a list of DNSKEY records
+///
+/// Registries accept one of the two interfaces, but never both in the same command.
+#[derive(Clone, Copy, Debug)]
+pub enum DsOrKeyType<'a> {
+    DsData(&'a [DsDataType<'a>]),
+    KeyData(&'a [KeyDataType<'a>]),
+}
+
+impl<'a> DsOrKeyType<'a> {
+    fn split(self) -> (Option<&'a [DsDataType<'a>]>, Option<&'a [KeyDataType<'a>]>) {
+        match self {
+            Self::DsData(ds_data) => (Some(ds_data), None),
+            Self::KeyData(key_data) => (None, Some(key_data)),
+        }
+    }
+}
+
+/// The &lt;dsData&gt; type for DS record delegation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DsDataType<'a> {
+    /// The key tag of the DNSKEY referenced by the DS record
+    #[serde(
+        rename = "secDNS:keyTag",
+        alias = "keyTag",
+        serialize_with = "serialize_element"
+    )]
+    pub key_tag: u16,
+    /// The DNSKEY algorithm number
+    #[serde(
+        rename = "secDNS:alg",
+        alias = "alg",
+        serialize_with = "serialize_element"
+    )]
+    pub algorithm: u8,
+    /// The digest algorithm number
+    #[serde(
+        rename = "secDNS:digestType",
+        alias = "digestType",
+        serialize_with = "serialize_element"
+    )]
+    pub digest_type: u8,
+    /// The hex-encoded digest
+    #[serde(rename = "secDNS:digest", alias = "digest")]
+    pub digest: StringValue<'a>,
+    /// The optional DNSKEY the DS record was generated from
+    #[serde(rename = "secDNS:keyData", alias = "keyData")]
+    pub key_data: Option<KeyDataType<'a>>,
+}
+
+impl<'a> DsDataType<'a> {
+    /// Creates a new DsDataType instance
+    pub fn new(key_tag: u16, algorithm: u8, digest_type: u8, digest: &'a str) -> Self {
+        Self {
+            key_tag,
+            algorithm,
+            digest_type,
+            digest: digest.into(),
+            key_data: None,
+        }
+    }
+
+    /// Sets the &lt;keyData&gt; for the DS record
+    pub fn set_key_data(&mut self, key_data: KeyDataType<'a>) {
+        self.key_data = Some(key_data);
+    }
+}
+
+/// The &lt;keyData&gt; type for DNSKEY record delegation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KeyDataType<'a> {
+    /// The DNSKEY flags
+    #[serde(
+        rename = "secDNS:flags",
+        alias = "flags",
+        serialize_with = "serialize_element"
+    )]
+    pub flags: u16,
+    /// The DNSKEY protocol, always 3
+    #[serde(
+        rename = "secDNS:protocol",
+        alias = "protocol",
+        serialize_with = "serialize_element"
+    )]
+    pub protocol: u8,
+    /// The DNSKEY algorithm number
+    #[serde(
+        rename = "secDNS:alg",
+        alias = "alg",
+        serialize_with = "serialize_element"
+    )]
+    pub algorithm: u8,
+    /// The base64-encoded public key
+    #[serde(rename = "secDNS:pubKey", alias = "pubKey")]
+    pub public_key: StringValue<'a>,
+}
+
+impl<'a> KeyDataType<'a> {
+    /// Creates a new KeyDataType instance for the DNSSEC protocol
+    pub fn new(flags: u16, algorithm: u8, public_key: &'a str) -> Self {
+        Self {
+            flags,
+            protocol: 3,
+            algorithm,
+            public_key: public_key.into(),
+        }
+    }
+}
+
+/// Type for elements under the &lt;secDNS:create&gt; tag
+#[derive(Serialize, Debug)]
+pub struct CreateData<'a> {
+    /// XML namespace for the secDNS extension
+    #[serde(rename = "xmlns:secDNS")]
+    xmlns: &'a str,
+    /// The maximum signature lifetime in seconds
+    #[serde(
+        rename = "secDNS:maxSigLife",
+        serialize_with = "serialize_element_option"
+    )]
+    max_sig_life: Option<u32>,
+    /// The list of DS records
+    #[serde(rename = "secDNS:dsData")]
+    ds_data: Option<&'a [DsDataType<'a>]>,
+    /// The list of DNSKEY records
+    #[serde(rename = "secDNS:keyData")]
+    key_data: Option<&'a [KeyDataType<'a>]>,
+}
+
+/// Type for EPP XML &lt;secDNS:create&gt; extension for domain create
+#[derive(Serialize, Debug)]
+pub struct Create<'a> {
+    #[serde(rename = "secDNS:create")]
+    pub data: CreateData<'a>,
+}
+
+impl<'a> Create<'a> {
+    /// Creates a new secDNS create extension with the given DS or DNSKEY records
+    pub fn new(data: DsOrKeyType<'a>) -> Self {
+        let (ds_data, key_data) = data.split();
+        Self {
+            data: CreateData {
+                xmlns: XMLNS,
+                max_sig_life: None,
+                ds_data,
+                key_data,
+            },
+        }
+    }
+
+    /// Sets the maximum signature lifetime (in seconds) requested from the registry
+    pub fn max_sig_life(&mut self, seconds: u32) {
+        self.data.max_sig_life = Some(seconds);
+    }
+}
+
+/// Type for elements under the &lt;secDNS:rem&gt; tag for the secDNS update
+#[derive(Serialize, Debug)]
+pub struct UpdateRemove<'a> {
+    /// Remove all DS and DNSKEY records
+    #[serde(rename = "secDNS:all", serialize_with = "serialize_element_option")]
+    all: Option<bool>,
+    /// The list of DS records to remove
+    #[serde(rename = "secDNS:dsData")]
+    ds_data: Option<&'a [DsDataType<'a>]>,
+    /// The list of DNSKEY records to remove
+    #[serde(rename = "secDNS:keyData")]
+    key_data: Option<&'a [KeyDataType<'a>]>,
+}
+
+/// Type for elements under the &lt;secDNS:add&gt; tag for the secDNS update
+#[derive(Serialize, Debug)]
+pub struct UpdateAdd<'a> {
+    /// The list of DS records to add
+    #[serde(rename = "secDNS:dsData")]
+    ds_data: Option<&'a [DsDataType<'a>]>,
+    /// The list of DNSKEY records to add
+    #[serde(rename = "secDNS:keyData")]
+    key_data: Option<&'a [KeyDataType<'a>]>,
+}
+
+/// Type for elements under the &lt;secDNS:chg&gt; tag for the secDNS update
+#[derive(Serialize, Debug)]
+pub struct UpdateChange {
+    /// The new maximum signature lifetime in seconds
+    #[serde(
+        rename = "secDNS:maxSigLife",
+        serialize_with = "serialize_element_option"
+    )]
+    max_sig_life: Option<u32>,
+}
+
+/// Type for elements under the &lt;secDNS:update&gt; tag
+#[derive(Serialize, Debug)]
+pub struct UpdateData<'a> {
+    /// XML namespace for the secDNS extension
+    #[serde(rename = "xmlns:secDNS")]
+    xmlns: &'a str,
+    /// The value of the urgent attr, asking the registry to process the change ASAP
+    urgent: Option<bool>,
+    /// The data under the &lt;rem&gt; tag
+    #[serde(rename = "secDNS:rem")]
+    remove: Option<UpdateRemove<'a>>,
+    /// The data under the &lt;add&gt; tag
+    #[serde(rename = "secDNS:add")]
+    add: Option<UpdateAdd<'a>>,
+    /// The data under the &lt;chg&gt; tag
+    #[serde(rename = "secDNS:chg")]
+    change: Option<UpdateChange>,
+}
+
+/// Type for EPP XML &lt;secDNS:update&gt; extension for domain update
+#[derive(Serialize, Debug)]
+pub struct Update<'a> {
+    #[serde(rename = "secDNS:update")]
+    pub data: UpdateData<'a>,
+}
+
+impl<'a> Update<'a> {
+    /// Creates a new, empty secDNS update extension
+    pub fn new() -> Self {
+        Self {
+            data: UpdateData {
+                xmlns: XMLNS,
+                urgent: None,
+                remove: None,
+                add: None,
+                change: None,
+            },
+        }
+    }
+
+    /// Sets the urgent attr on the &lt;update&gt; tag
+    pub fn urgent(&mut self, urgent: bool) {
+        self.data.urgent = Some(urgent);
+    }
+
+    /// Sets the DS or DNSKEY records for the &lt;add&gt; tag
+    pub fn add(&mut self, add: DsOrKeyType<'a>) {
+        let (ds_data, key_data) = add.split();
+        self.data.add = Some(UpdateAdd { ds_data, key_data });
+    }
+
+    /// Sets the DS or DNSKEY records for the &lt;rem&gt; tag
+    pub fn remove(&mut self, remove: DsOrKeyType<'a>) {
+        let (ds_data, key_data) = remove.split();
+        self.data.remove = Some(UpdateRemove {
+            all: None,
+            ds_data,
+            key_data,
+        });
+    }
+
+    /// Sets the &lt;rem&gt; tag to remove all existing DS and DNSKEY records
+    pub fn remove_all(&mut self) {
+        self.data.remove = Some(UpdateRemove {
+            all: Some(true),
+            ds_data: None,
+            key_data: None,
+        });
+    }
+
+    /// Sets the maximum signature lifetime (in seconds) under the &lt;chg&gt; tag
+    pub fn max_sig_life(&mut self, seconds: u32) {
+        self.data.change = Some(UpdateChange {
+            max_sig_life: Some(seconds),
+        });
+    }
+}
+
+impl Default for Update<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Marker extension to parse &lt;secDNS:infData&gt; from domain info responses
+///
+/// RFC 5910 defines no request data for &lt;info&gt;: a server that supports the extension
+/// returns the delegation signer or key data unasked. `Info` therefore writes nothing, and the
+/// request is identical to a plain domain info.
+#[derive(Debug, Default)]
+pub struct Info;
+
+impl Serialize for Info {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+// Response
+
+/// Type that represents the &lt;infData&gt; tag for the secDNS domain info extension
+#[derive(Deserialize, Debug)]
+pub struct InfoData {
+    /// The maximum signature lifetime in seconds
+    #[serde(rename = "maxSigLife")]
+    pub max_sig_life: Option<u32>,
+    /// The list of DS records
+    #[serde(rename = "dsData")]
+    pub ds_data: Option<Vec<DsDataType<'static>>>,
+    /// The list of DNSKEY records
+    #[serde(rename = "keyData")]
+    pub key_data: Option<Vec<KeyDataType<'static>>>,
+}
+
+/// Type that represents the &lt;extension&gt; tag for the secDNS domain info extension
+#[derive(Deserialize, Debug)]
+pub struct InfoResponse {
+    /// Data under the &lt;infData&gt; tag
+    #[serde(rename = "infData")]
+    pub info_data: InfoData,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Create, DsDataType, DsOrKeyType, Info, KeyDataType, Update};
+    use crate::domain::create::DomainCreate;
+    use crate::domain::info::DomainInfo;
+    use crate::domain::update::DomainUpdate;
+    use crate::domain::Period;
+    use crate::response::ResultCode;
+    use crate::tests::{assert_serialized, response_from_file_with_ext, SUCCESS_MSG, SVTRID};
+
+    #[test]
+    fn create_ds_data() {
+        let mut ds = DsDataType::new(12345, 3, 1, "49FD46E6C4B45C55D4AC");
+        ds.set_key_data(KeyDataType::new(257, 3, "AQPJ////4Q=="));
+        let ds_data = &[ds];
+
+        let mut ext = Create::new(DsOrKeyType::DsData(ds_data));
+        ext.max_sig_life(604800);
+
+        let object = DomainCreate::new(
+            "eppdev.com",
+            Period::years(1).unwrap(),
+            None,
+            None,
+            "epP4uthd#v",
+            None,
+        );
+
+        assert_serialized("request/extensions/secdns_create.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn create_key_data() {
+        let key_data = &[KeyDataType::new(257, 5, "AQPJ////4Q==")];
+        let ext = Create::new(DsOrKeyType::KeyData(key_data));
+
+        let object = DomainCreate::new(
+            "eppdev.com",
+            Period::years(1).unwrap(),
+            None,
+            None,
+            "epP4uthd#v",
+            None,
+        );
+
+        assert_serialized(
+            "request/extensions/secdns_create_key_data.xml",
+            (&object, &ext),
+        );
+    }
+
+    #[test]
+    fn update() {
+        let ds_data = &[DsDataType::new(12346, 3, 1, "38EC35D5B3A34B44C39B")];
+
+        let mut ext = Update::new();
+        ext.urgent(true);
+        ext.remove_all();
+        ext.add(DsOrKeyType::DsData(ds_data));
+        ext.max_sig_life(605900);
+
+        let object = DomainUpdate::new("eppdev.com");
+
+        assert_serialized("request/extensions/secdns_update.xml", (&object, &ext));
+    }
+
+    #[test]
+    fn update_remove_ds_data() {
+        let ds_data = &[DsDataType::new(12345, 3, 1, "49FD46E6C4B45C55D4AC")];
+
+        let mut ext = Update::new();
+        ext.remove(DsOrKeyType::DsData(ds_data));
+
+        let object = DomainUpdate::new("eppdev.com");
+
+        assert_serialized(
+            "request/extensions/secdns_update_rem_ds_data.xml",
+            (&object, &ext),
+        );
+    }
+
+    #[test]
+    fn info_command() {
+        let object = DomainInfo::new("eppdev.com", Some("2fooBAR"));
+        assert_serialized("request/domain/info.xml", (&object, &Info));
+    }
+
+    #[test]
+    fn info_response() {
+        let object = response_from_file_with_ext::<DomainInfo, Info>(
+            "response/extensions/domain_info_secdns.xml",
+        );
+        let ext = object.extension.unwrap();
+
+        assert_eq!(object.result.code, ResultCode::CommandCompletedSuccessfully);
+        assert_eq!(object.result.message, SUCCESS_MSG.into());
+        assert_eq!(ext.info_data.max_sig_life, Some(604800));
+        assert!(ext.info_data.key_data.is_none());
+
+        let ds_data = ext.info_data.ds_data.unwrap();
+        assert_eq!(ds_data.len(), 2);
+        assert_eq!(ds_data[0].key_tag, 12345);
+        assert_eq!(ds_data[0].algorithm, 3);
+        assert_eq!(ds_data[0].digest_type, 1);
+        assert_eq!(ds_data[0].digest, "49FD46E6C4B45C55D4AC".into());
+        assert!(ds_data[0].key_data.is_none());
+
+        let key_data = ds_data[1].key_data.as_ref().unwrap();
+        assert_eq!(ds_data[1].key_tag, 12346);
+        assert_eq!(key_data.flags, 257);
+        assert_eq!(key_data.protocol, 3);
+        assert_eq!(key_data.algorithm, 8);
+        assert_eq!(key_data.public_key, "AQPJ////4Q==".into());
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+}
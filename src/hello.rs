@@ -46,7 +46,7 @@ struct FlattenedServiceMenu {
     pub svc_ext: Option<ServiceExtension<'static>>,
 }
 
-impl<'a, 'de: 'a> Deserialize<'de> for ServiceMenu {
+impl<'de> Deserialize<'de> for ServiceMenu {
     /// Deserializes the <svcMenu> data to the `ServiceMenu` type
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
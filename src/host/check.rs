@@ -4,8 +4,9 @@ use std::fmt::Debug;
 
 use super::XMLNS;
 use crate::common::{CheckResponse, NoExtension, StringValue};
-use crate::request::{Command, Transaction};
-use serde::Serialize;
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
+use serde::{Serialize, Serializer};
 
 impl<'a> Transaction<NoExtension> for HostCheck<'a> {}
 
@@ -14,6 +15,13 @@ impl<'a> Command for HostCheck<'a> {
     const COMMAND: &'static str = "check";
 }
 
+impl<E: Extension> Transaction<E> for OwnedHostCheck where HostCheck<'static>: Transaction<E> {}
+
+impl Command for OwnedHostCheck {
+    type Response = CheckResponse;
+    const COMMAND: &'static str = "check";
+}
+
 // Request
 
 /// Type for data under the host &lt;check&gt; tag
@@ -54,9 +62,47 @@ pub struct HostCheck<'a> {
     pub hosts: &'a [&'a str],
 }
 
+/// An owned [`HostCheck`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedHostCheck {
+    hosts: Vec<String>,
+}
+
+impl OwnedHostCheck {
+    /// Creates a check for `hosts`, failing if there are none or one of them is empty
+    pub fn new<I>(hosts: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let hosts = hosts.into_iter().map(Into::into).collect::<Vec<_>>();
+        if hosts.is_empty() {
+            return Err(Error::Other("at least one host must be checked".into()));
+        }
+
+        for host in &hosts {
+            required("host name", host)?;
+        }
+
+        Ok(Self { hosts })
+    }
+}
+
+impl Serialize for OwnedHostCheck {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeHostCheck {
+            list: HostList {
+                xmlns: XMLNS,
+                hosts: self.hosts.iter().map(|host| host.as_str().into()).collect(),
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::HostCheck;
+    use super::{HostCheck, OwnedHostCheck};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -68,6 +114,15 @@ mod tests {
         assert_serialized("request/host/check.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedHostCheck::new(["ns1.eppdev-1.com", "host1.eppdev-1.com"]).unwrap();
+        assert_serialized("request/host/check.xml", &object);
+
+        assert!(OwnedHostCheck::new(Vec::<String>::new()).is_err());
+        assert!(OwnedHostCheck::new([""]).is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<HostCheck>("response/host/check.xml");
@@ -3,11 +3,12 @@
 use std::net::IpAddr;
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use super::XMLNS;
 use crate::common::{serialize_host_addrs_option, NoExtension, StringValue};
-use crate::request::{Command, Transaction};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
 
 impl<'a> Transaction<NoExtension> for HostCreate<'a> {}
 
@@ -16,6 +17,13 @@ impl<'a> Command for HostCreate<'a> {
     const COMMAND: &'static str = "create";
 }
 
+impl<E: Extension> Transaction<E> for OwnedHostCreate where HostCreate<'static>: Transaction<E> {}
+
+impl Command for OwnedHostCreate {
+    type Response = HostCreateResponse;
+    const COMMAND: &'static str = "create";
+}
+
 impl<'a> HostCreate<'a> {
     pub fn new(host: &'a str, addresses: Option<&'a [IpAddr]>) -> Self {
         Self {
@@ -28,6 +36,28 @@ impl<'a> HostCreate<'a> {
     }
 }
 
+/// An owned [`HostCreate`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedHostCreate {
+    name: String,
+    addresses: Vec<IpAddr>,
+}
+
+impl OwnedHostCreate {
+    pub fn new(name: impl Into<String>, addresses: Vec<IpAddr>) -> Result<Self, Error> {
+        let name = name.into();
+        required("host name", &name)?;
+        Ok(Self { name, addresses })
+    }
+}
+
+impl Serialize for OwnedHostCreate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let addresses = (!self.addresses.is_empty()).then(|| self.addresses.as_slice());
+        HostCreate::new(&self.name, addresses).serialize(serializer)
+    }
+}
+
 // Request
 
 /// Type for data under the host &lt;create&gt; tag
@@ -76,7 +106,7 @@ pub struct HostCreateResponse {
 mod tests {
     use chrono::{TimeZone, Utc};
 
-    use super::{HostCreate, IpAddr};
+    use super::{HostCreate, IpAddr, OwnedHostCreate};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -91,6 +121,18 @@ mod tests {
         assert_serialized("request/host/create.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let addresses = vec![
+            IpAddr::from([29, 245, 122, 14]),
+            IpAddr::from([0x2404, 0x6800, 0x4001, 0x801, 0, 0, 0, 0x200e]),
+        ];
+
+        let object = OwnedHostCreate::new("host1.eppdev-1.com", addresses).unwrap();
+        assert_serialized("request/host/create.xml", &object);
+        assert!(OwnedHostCreate::new("", vec![]).is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<HostCreate>("response/host/create.xml");
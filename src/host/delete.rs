@@ -2,8 +2,9 @@
 
 use super::XMLNS;
 use crate::common::{NoExtension, StringValue};
-use crate::request::{Command, Transaction};
-use serde::Serialize;
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
+use serde::{Serialize, Serializer};
 
 impl<'a> Transaction<NoExtension> for HostDelete<'a> {}
 
@@ -12,6 +13,13 @@ impl<'a> Command for HostDelete<'a> {
     const COMMAND: &'static str = "delete";
 }
 
+impl<E: Extension> Transaction<E> for OwnedHostDelete where HostDelete<'static>: Transaction<E> {}
+
+impl Command for OwnedHostDelete {
+    type Response = ();
+    const COMMAND: &'static str = "delete";
+}
+
 impl<'a> HostDelete<'a> {
     pub fn new(name: &'a str) -> Self {
         Self {
@@ -23,6 +31,26 @@ impl<'a> HostDelete<'a> {
     }
 }
 
+/// An owned [`HostDelete`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedHostDelete {
+    name: String,
+}
+
+impl OwnedHostDelete {
+    pub fn new(name: impl Into<String>) -> Result<Self, Error> {
+        let name = name.into();
+        required("host name", &name)?;
+        Ok(Self { name })
+    }
+}
+
+impl Serialize for OwnedHostDelete {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HostDelete::new(&self.name).serialize(serializer)
+    }
+}
+
 /// Type for data under the host &lt;delete&gt; tag
 #[derive(Serialize, Debug)]
 pub struct HostDeleteRequestData<'a> {
@@ -44,7 +72,7 @@ pub struct HostDelete<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::HostDelete;
+    use super::{HostDelete, OwnedHostDelete};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -54,6 +82,13 @@ mod tests {
         assert_serialized("request/host/delete.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedHostDelete::new("ns1.eppdev-1.com").unwrap();
+        assert_serialized("request/host/delete.xml", &object);
+        assert!(OwnedHostDelete::new("").is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<HostDelete>("response/host/delete.xml");
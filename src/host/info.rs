@@ -4,11 +4,12 @@ use std::net::IpAddr;
 use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 use super::XMLNS;
-use crate::common::{HostAddr, NoExtension, ObjectStatus, StringValue};
-use crate::request::{Command, Transaction};
+use crate::common::{HostAddr, HostStatus, NoExtension, ObjectStatus, StringValue};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
 
 impl<'a> Transaction<NoExtension> for HostInfo<'a> {}
 
@@ -17,6 +18,13 @@ impl<'a> Command for HostInfo<'a> {
     const COMMAND: &'static str = "info";
 }
 
+impl<E: Extension> Transaction<E> for OwnedHostInfo where HostInfo<'static>: Transaction<E> {}
+
+impl Command for OwnedHostInfo {
+    type Response = HostInfoResponse;
+    const COMMAND: &'static str = "info";
+}
+
 impl<'a> HostInfo<'a> {
     pub fn new(name: &'a str) -> Self {
         Self {
@@ -28,6 +36,26 @@ impl<'a> HostInfo<'a> {
     }
 }
 
+/// An owned [`HostInfo`], which can be kept around or sent to another task
+#[derive(Clone, Debug)]
+pub struct OwnedHostInfo {
+    name: String,
+}
+
+impl OwnedHostInfo {
+    pub fn new(name: impl Into<String>) -> Result<Self, Error> {
+        let name = name.into();
+        required("host name", &name)?;
+        Ok(Self { name })
+    }
+}
+
+impl Serialize for OwnedHostInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HostInfo::new(&self.name).serialize(serializer)
+    }
+}
+
 // Request
 
 /// Type for data under the host &lt;info&gt; tag
@@ -60,7 +88,7 @@ pub struct HostInfoResponseData {
     pub roid: StringValue<'static>,
     /// The list of host statuses
     #[serde(rename = "status")]
-    pub statuses: Vec<ObjectStatus<'static>>,
+    pub statuses: Vec<ObjectStatus<HostStatus>>,
     /// The list of host IP addresses
     #[serde(rename = "addr", deserialize_with = "deserialize_host_addrs")]
     pub addresses: Vec<IpAddr>,
@@ -108,7 +136,8 @@ pub struct HostInfoResponse {
 mod tests {
     use chrono::{TimeZone, Utc};
 
-    use super::{HostInfo, IpAddr};
+    use super::{HostInfo, IpAddr, OwnedHostInfo};
+    use crate::common::HostStatus;
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -118,6 +147,13 @@ mod tests {
         assert_serialized("request/host/info.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let object = OwnedHostInfo::new("ns1.eppdev-1.com").unwrap();
+        assert_serialized("request/host/info.xml", &object);
+        assert!(OwnedHostInfo::new("").is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<HostInfo>("response/host/info.xml");
@@ -127,7 +163,7 @@ mod tests {
         assert_eq!(object.result.message, SUCCESS_MSG.into());
         assert_eq!(result.info_data.name, "host2.eppdev-1.com".into());
         assert_eq!(result.info_data.roid, "UNDEF-ROID".into());
-        assert_eq!(result.info_data.statuses[0].status, "ok".to_string());
+        assert_eq!(result.info_data.statuses[0].status, HostStatus::Ok);
         assert_eq!(
             result.info_data.addresses[0],
             IpAddr::from([29, 245, 122, 14])
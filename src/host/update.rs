@@ -3,9 +3,12 @@
 use std::net::IpAddr;
 
 use super::XMLNS;
-use crate::common::{serialize_host_addrs_option, NoExtension, ObjectStatus, StringValue};
-use crate::request::{Command, Transaction};
-use serde::Serialize;
+use crate::common::{
+    serialize_host_addrs_option, HostStatus, NoExtension, ObjectStatus, StringValue,
+};
+use crate::request::{required, Command, Extension, Transaction};
+use crate::Error;
+use serde::{Serialize, Serializer};
 
 impl<'a> Transaction<NoExtension> for HostUpdate<'a> {}
 
@@ -14,6 +17,13 @@ impl<'a> Command for HostUpdate<'a> {
     const COMMAND: &'static str = "update";
 }
 
+impl<E: Extension> Transaction<E> for OwnedHostUpdate where HostUpdate<'static>: Transaction<E> {}
+
+impl Command for OwnedHostUpdate {
+    type Response = ();
+    const COMMAND: &'static str = "update";
+}
+
 impl<'a> HostUpdate<'a> {
     pub fn new(name: &'a str) -> Self {
         Self {
@@ -59,7 +69,7 @@ pub struct HostAddRemove<'a> {
     pub addresses: Option<&'a [IpAddr]>,
     /// The statuses to be added to or removed from the host
     #[serde(rename = "host:status")]
-    pub statuses: Option<&'a [ObjectStatus<'a>]>,
+    pub statuses: Option<&'a [ObjectStatus<HostStatus>]>,
 }
 
 /// Type for data under the host &lt;update&gt; tag
@@ -90,11 +100,128 @@ pub struct HostUpdate<'a> {
     host: HostUpdateRequestData<'a>,
 }
 
+/// The addresses and statuses to add to or remove from a host in an [`OwnedHostUpdate`]
+#[derive(Clone, Debug, Default)]
+struct OwnedAddRemove {
+    addresses: Vec<IpAddr>,
+    statuses: Vec<ObjectStatus<HostStatus>>,
+}
+
+impl OwnedAddRemove {
+    fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.statuses.is_empty()
+    }
+
+    fn as_add_remove(&self) -> Option<HostAddRemove<'_>> {
+        match self.is_empty() {
+            true => None,
+            false => Some(HostAddRemove {
+                addresses: (!self.addresses.is_empty()).then(|| self.addresses.as_slice()),
+                statuses: (!self.statuses.is_empty()).then(|| self.statuses.as_slice()),
+            }),
+        }
+    }
+}
+
+/// An owned [`HostUpdate`], which can be kept around or sent to another task
+///
+/// Use [`OwnedHostUpdate::builder()`] to create one.
+#[derive(Clone, Debug)]
+pub struct OwnedHostUpdate {
+    name: String,
+    add: OwnedAddRemove,
+    remove: OwnedAddRemove,
+    new_name: Option<String>,
+}
+
+impl OwnedHostUpdate {
+    /// Starts building an update command for the host `name`
+    pub fn builder(name: impl Into<String>) -> HostUpdateBuilder {
+        HostUpdateBuilder {
+            update: OwnedHostUpdate {
+                name: name.into(),
+                add: OwnedAddRemove::default(),
+                remove: OwnedAddRemove::default(),
+                new_name: None,
+            },
+        }
+    }
+}
+
+impl Serialize for OwnedHostUpdate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut update = HostUpdate::new(&self.name);
+        update.host.add = self.add.as_add_remove();
+        update.host.remove = self.remove.as_add_remove();
+        update.host.change_info = self
+            .new_name
+            .as_deref()
+            .map(|name| HostChangeInfo { name: name.into() });
+        update.serialize(serializer)
+    }
+}
+
+/// Builder for an [`OwnedHostUpdate`]
+///
+/// At least one change is required.
+#[derive(Clone, Debug)]
+pub struct HostUpdateBuilder {
+    update: OwnedHostUpdate,
+}
+
+impl HostUpdateBuilder {
+    /// Adds an IP address
+    pub fn add_address(mut self, address: IpAddr) -> Self {
+        self.update.add.addresses.push(address);
+        self
+    }
+
+    /// Adds a status
+    pub fn add_status(mut self, status: impl Into<ObjectStatus<HostStatus>>) -> Self {
+        self.update.add.statuses.push(status.into());
+        self
+    }
+
+    /// Removes an IP address
+    pub fn remove_address(mut self, address: IpAddr) -> Self {
+        self.update.remove.addresses.push(address);
+        self
+    }
+
+    /// Removes a status
+    pub fn remove_status(mut self, status: impl Into<ObjectStatus<HostStatus>>) -> Self {
+        self.update.remove.statuses.push(status.into());
+        self
+    }
+
+    /// Renames the host
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.update.new_name = Some(name.into());
+        self
+    }
+
+    /// Checks the command and builds it
+    pub fn build(self) -> Result<OwnedHostUpdate, Error> {
+        let update = self.update;
+        required("host name", &update.name)?;
+        if let Some(name) = &update.new_name {
+            required("new host name", name)?;
+        }
+
+        let unchanged = update.new_name.is_none();
+        if unchanged && update.add.is_empty() && update.remove.is_empty() {
+            return Err(Error::Other("host update must change something".into()));
+        }
+
+        Ok(update)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::IpAddr;
-    use super::{HostAddRemove, HostChangeInfo, HostUpdate};
-    use crate::common::ObjectStatus;
+    use super::{HostAddRemove, HostChangeInfo, HostUpdate, OwnedHostUpdate};
+    use crate::common::{HostStatus, ObjectStatus};
     use crate::response::ResultCode;
     use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
 
@@ -109,9 +236,7 @@ mod tests {
             statuses: None,
         };
 
-        let statuses = &[ObjectStatus {
-            status: "clientDeleteProhibited".into(),
-        }];
+        let statuses = &[ObjectStatus::new(HostStatus::ClientDeleteProhibited)];
 
         let remove = HostAddRemove {
             addresses: None,
@@ -129,6 +254,22 @@ mod tests {
         assert_serialized("request/host/update.xml", &object);
     }
 
+    #[test]
+    fn owned_command() {
+        let address = IpAddr::from([0x2404, 0x6800, 0x4001, 0x801, 0, 0, 0, 0x200e]);
+        let object = OwnedHostUpdate::builder("host1.eppdev-1.com")
+            .add_address(address)
+            .remove_status(HostStatus::ClientDeleteProhibited)
+            .name("host2.eppdev-1.com")
+            .build()
+            .unwrap();
+        assert_serialized("request/host/update.xml", &object);
+
+        assert!(OwnedHostUpdate::builder("host1.eppdev-1.com")
+            .build()
+            .is_err());
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<HostUpdate>("response/host/update.xml");
@@ -28,17 +28,18 @@
 //!
 //! ## Contacts
 //!
-//! Specified in [RFC 5732](https://tools.ietf.org/html/rfc5732).
+//! Specified in [RFC 5733](https://tools.ietf.org/html/rfc5733).
 //!
 //! - [`contact::ContactCheck`]
 //! - [`contact::ContactCreate`]
 //! - [`contact::ContactInfo`]
 //! - [`contact::ContactUpdate`]
+//! - [`contact::ContactTransfer`]
 //! - [`contact::ContactDelete`]
 //!
 //! ## Hosts
 //!
-//! Specified in [RFC 5733](https://tools.ietf.org/html/rfc5733).
+//! Specified in [RFC 5732](https://tools.ietf.org/html/rfc5732).
 //!
 //! - [`host::HostCheck`]
 //! - [`host::HostCreate`]
@@ -52,6 +53,22 @@
 //! - [`extensions::rgp::request::RgpRestoreRequest`]
 //! - [`extensions::namestore::NameStore`]
 //! - [`extensions::consolidate::Update`]
+//! - [`extensions::secdns::Create`]
+//! - [`extensions::secdns::Update`]
+//! - [`extensions::fee::Check`]
+//! - [`extensions::fee::Create`]
+//! - [`extensions::fee::Renew`]
+//! - [`extensions::fee::Transfer`]
+//! - [`extensions::fee::UpdateWithRgpRestore`]
+//! - [`extensions::launch::Check`]
+//! - [`extensions::launch::Create`]
+//! - [`extensions::launch::Info`]
+//! - [`extensions::launch::Update`]
+//! - [`extensions::launch::Delete`]
+//! - [`extensions::allocation_token::AllocationToken`]
+//! - [`extensions::allocation_token::Info`]
+//! - [`extensions::change_poll::ChangePoll`]
+//! - [`extensions::login_sec::LoginSec`]
 //!
 //! ## Operation
 //!
@@ -101,32 +118,45 @@ mod error;
 pub mod hello;
 pub mod login;
 pub mod logout;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod pool;
+pub mod proxy;
+pub mod record;
+pub mod redact;
 pub mod request;
 pub mod response;
+pub mod session;
 pub mod xml;
 
 pub mod extensions {
+    pub mod allocation_token;
+    pub mod change_poll;
     pub mod consolidate;
+    pub mod fee;
+    pub mod launch;
+    pub mod login_sec;
     pub mod low_balance;
     pub mod namestore;
     pub mod rgp;
+    pub mod secdns;
 }
 
 pub mod host {
     pub mod check;
-    pub use check::HostCheck;
+    pub use check::{HostCheck, OwnedHostCheck};
 
     pub mod create;
-    pub use create::HostCreate;
+    pub use create::{HostCreate, OwnedHostCreate};
 
     pub mod delete;
-    pub use delete::HostDelete;
+    pub use delete::{HostDelete, OwnedHostDelete};
 
     pub mod info;
-    pub use info::HostInfo;
+    pub use info::{HostInfo, OwnedHostInfo};
 
     pub mod update;
-    pub use update::HostUpdate;
+    pub use update::{HostUpdate, HostUpdateBuilder, OwnedHostUpdate};
 
     pub const XMLNS: &str = "urn:ietf:params:xml:ns:host-1.0";
 }
@@ -135,6 +165,9 @@ pub mod message {
     pub mod ack;
     pub use ack::MessageAck;
 
+    pub mod consumer;
+    pub use consumer::MessageConsumer;
+
     pub mod poll;
     pub use poll::MessagePoll;
 }
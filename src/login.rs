@@ -1,16 +1,19 @@
 use std::fmt::Debug;
 
 use serde::Serialize;
+use tracing::warn;
 
 use crate::{
     common::{NoExtension, Options, ServiceExtension, Services, StringValue},
-    contact, domain, host,
+    contact, domain,
+    hello::Greeting,
+    host,
     request::{Command, Transaction, EPP_LANG, EPP_VERSION},
 };
 
 impl<'a> Transaction<NoExtension> for Login<'a> {}
 
-#[derive(Serialize, Debug, Eq, PartialEq)]
+#[derive(Serialize, Debug, Clone, Eq, PartialEq)]
 /// Type corresponding to the &lt;login&gt; tag in an EPP XML login request
 pub struct Login<'a> {
     /// The username to use for the login
@@ -62,6 +65,44 @@ impl<'a> Login<'a> {
         }
     }
 
+    /// Create a `Login` for the services offered in the server's `greeting`
+    ///
+    /// Announces the host, contact and domain objects that the server offers, and those of
+    /// the `ext_uris` that it offers. Requested extensions that are not offered are left out
+    /// with a warning; commands using them will then fail with an error before being sent.
+    pub fn from_greeting(
+        greeting: &Greeting,
+        username: &'a str,
+        password: &'a str,
+        new_password: Option<&'a str>,
+        ext_uris: &[&'a str],
+    ) -> Self {
+        let services = &greeting.svc_menu.services;
+        let offered_ext_uris = services
+            .svc_ext
+            .as_ref()
+            .and_then(|ext| ext.ext_uris.as_deref())
+            .unwrap_or_default();
+
+        let mut negotiated = Vec::with_capacity(ext_uris.len());
+        for &uri in ext_uris {
+            match offered_ext_uris.iter().any(|offered| **offered == *uri) {
+                true => negotiated.push(uri),
+                false => warn!(
+                    "extension {} is not offered by {}",
+                    uri, greeting.service_id
+                ),
+            }
+        }
+
+        let mut login = Self::new(username, password, new_password, Some(&negotiated));
+        login
+            .services
+            .obj_uris
+            .retain(|uri| services.obj_uris.contains(uri));
+        login
+    }
+
     /// Sets the <options> tag data
     pub fn options(&mut self, options: Options<'a>) {
         self.options = options;
@@ -71,18 +112,56 @@ impl<'a> Login<'a> {
     pub fn services(&mut self, services: Services<'a>) {
         self.services = services;
     }
+
+    /// Converts into a `Login` that owns its data
+    pub fn into_owned(self) -> Login<'static> {
+        Login {
+            username: self.username.into_owned(),
+            password: self.password.into_owned(),
+            new_password: self.new_password.map(StringValue::into_owned),
+            options: self.options.into_owned(),
+            services: self.services.into_owned(),
+        }
+    }
+
+    /// Converts into a `Login` for logging in again later
+    ///
+    /// If this login changes the password, the new password is used to log in again.
+    pub(crate) fn into_relogin(self) -> Login<'static> {
+        let mut login = self.into_owned();
+        if let Some(new_password) = login.new_password.take() {
+            login.password = new_password;
+        }
+        login
+    }
 }
 
 impl<'a> Command for Login<'a> {
     type Response = ();
     const COMMAND: &'static str = "login";
+
+    fn negotiated_extensions(&self) -> Option<Vec<&str>> {
+        let ext_uris = match &self.services.svc_ext {
+            Some(ServiceExtension {
+                ext_uris: Some(uris),
+            }) => uris.iter().map(|uri| &**uri).collect(),
+            _ => Vec::new(),
+        };
+        Some(ext_uris)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Login;
+    use crate::extensions::{fee, rgp, secdns};
+    use crate::hello::GreetingDocument;
+    use crate::request::Command;
     use crate::response::ResultCode;
-    use crate::tests::{assert_serialized, response_from_file, CLTRID, SUCCESS_MSG, SVTRID};
+    use crate::tests::{
+        assert_serialized, get_xml, response_from_file, CLTRID, SUCCESS_MSG, SVTRID,
+    };
+    use crate::xml;
 
     #[test]
     fn command() {
@@ -97,6 +176,19 @@ mod tests {
         assert_serialized("request/login_no_extension.xml", &object);
     }
 
+    #[test]
+    fn command_from_greeting() {
+        let xml = get_xml("response/greeting.xml").unwrap();
+        let greeting = xml::deserialize::<GreetingDocument>(&xml).unwrap().data;
+        let ext_uris = [secdns::XMLNS, fee::XMLNS, rgp::XMLNS];
+        let object = Login::from_greeting(&greeting, "username", "password", None, &ext_uris);
+        assert_serialized("request/login_greeting.xml", &object);
+        assert_eq!(
+            object.negotiated_extensions(),
+            Some(vec![secdns::XMLNS, rgp::XMLNS])
+        );
+    }
+
     #[test]
     fn response() {
         let object = response_from_file::<Login>("response/login.xml");
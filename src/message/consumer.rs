@@ -0,0 +1,241 @@
+//! Draining the message queue with automatic acknowledgement
+//!
+//! Registries keep service messages, such as transfer notifications, in a queue until they
+//! are acknowledged. A poll returns the oldest message, and keeps returning it until it is
+//! acked, so a [`MessageConsumer`] only acks a message once it has been handled: a message
+//! whose handling failed, or was interrupted, is delivered again by the next poll.
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::debug;
+
+use super::poll::{MessageData, UnknownData, UnknownExtension};
+use super::{MessageAck, MessagePoll};
+use crate::client::EppClient;
+use crate::connection::Connector;
+use crate::error::Error;
+use crate::response::ResultCode;
+
+/// Settings for a [`MessageConsumer`]
+#[derive(Clone, Copy, Debug)]
+pub struct ConsumerConfig {
+    /// How long to wait before polling again after finding the queue empty; doubled for every
+    /// following empty poll
+    pub idle_delay: Duration,
+    /// The longest wait between polls of an empty queue
+    pub max_idle_delay: Duration,
+    /// Stop after this many messages, or as soon as the queue is empty, instead of waiting
+    /// for new messages
+    pub batch_size: Option<usize>,
+}
+
+impl ConsumerConfig {
+    /// Settings for draining at most `size` messages without waiting for new ones
+    pub fn batch(size: usize) -> Self {
+        Self {
+            batch_size: Some(size),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ConsumerConfig {
+    fn default() -> Self {
+        Self {
+            idle_delay: Duration::from_secs(1),
+            max_idle_delay: Duration::from_secs(60),
+            batch_size: None,
+        }
+    }
+}
+
+/// A message taken from the queue
+#[derive(Debug)]
+pub struct QueuedMessage {
+    /// The message ID, used to acknowledge it
+    pub id: String,
+    /// The number of messages in the queue, including this one
+    pub count: u32,
+    /// The date the message was queued
+    pub date: Option<DateTime<Utc>>,
+    /// The human-readable text of the message
+    pub text: Option<String>,
+    /// The object data of the message, if any
+    pub data: Option<MessageData>,
+    /// The blocks under the &lt;extension&gt; tag of the message, such as change poll data,
+    /// kept as raw XML
+    pub extensions: Vec<UnknownData>,
+}
+
+/// An error that stopped [`MessageConsumer::run()`]
+#[derive(Debug)]
+pub enum RunError<E> {
+    /// Polling or acknowledging a message failed
+    Epp(Error),
+    /// The handler failed; the message it was given stays in the queue
+    Handler(E),
+}
+
+impl<E: fmt::Display> fmt::Display for RunError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Epp(e) => write!(f, "{e}"),
+            Self::Handler(e) => write!(f, "message handler error: {e}"),
+        }
+    }
+}
+
+impl<E: StdError + 'static> StdError for RunError<E> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Epp(e) => Some(e),
+            Self::Handler(e) => Some(e),
+        }
+    }
+}
+
+impl<E> From<Error> for RunError<E> {
+    fn from(e: Error) -> Self {
+        Self::Epp(e)
+    }
+}
+
+/// Polls the message queue of a logged-in client, acknowledging messages once handled
+///
+/// Use [`MessageConsumer::next()`] like a stream, acking each message with
+/// [`MessageConsumer::ack()`], or pass a handler to [`MessageConsumer::run()`].
+///
+/// The consumer does not implement `Stream`: a poll that is in progress is a future borrowing
+/// the client, which a `Stream` would have to keep inside itself between calls to
+/// `poll_next()`, and acking would then need a second borrow of the client while the stream
+/// holds the first. An `async fn next()` lets each poll and ack borrow the client in turn.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use epp_client::client::RustlsConnector;
+/// use epp_client::login::Login;
+/// use epp_client::message::consumer::{ConsumerConfig, MessageConsumer};
+/// use epp_client::EppClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let connector = RustlsConnector::new(("example.com".to_owned(), 700), None).await.unwrap();
+/// let timeout = Duration::from_secs(5);
+/// let mut client = EppClient::new(connector, "registry_name".into(), timeout).await.unwrap();
+/// let login = Login::new("username", "password", None, None);
+/// client.transact(&login, "transaction-id").await.unwrap();
+///
+/// let mut consumer = MessageConsumer::new(&mut client, ConsumerConfig::batch(100));
+/// while let Some(message) = consumer.next().await.unwrap() {
+///     println!("{}: {:?}", message.id, message.text);
+///     consumer.ack(&message).await.unwrap();
+/// }
+/// # }
+/// ```
+pub struct MessageConsumer<'a, C: Connector> {
+    client: &'a mut EppClient<C>,
+    config: ConsumerConfig,
+    delivered: usize,
+    counter: u64,
+}
+
+impl<'a, C: Connector> MessageConsumer<'a, C> {
+    pub fn new(client: &'a mut EppClient<C>, config: ConsumerConfig) -> Self {
+        Self {
+            client,
+            config,
+            delivered: 0,
+            counter: 0,
+        }
+    }
+
+    /// Returns the next message, or `None` once a batch is done
+    ///
+    /// Without a batch size, this waits until a message is queued, polling with a growing
+    /// delay while the queue is empty. A message that is not acked is returned again.
+    pub async fn next(&mut self) -> Result<Option<QueuedMessage>, Error> {
+        let mut delay = self.config.idle_delay;
+        loop {
+            if let Some(size) = self.config.batch_size {
+                if self.delivered >= size {
+                    return Ok(None);
+                }
+            }
+
+            let id = self.transaction_id("poll");
+            let poll = MessagePoll::default();
+            let rsp = self
+                .client
+                .transact((&poll, &UnknownExtension), &id)
+                .await?;
+            let queue = match rsp.message_queue {
+                Some(queue)
+                    if rsp.result.code != ResultCode::CommandCompletedSuccessfullyNoMessages =>
+                {
+                    queue
+                }
+                _ if self.config.batch_size.is_some() => return Ok(None),
+                _ => {
+                    debug!("{}: message queue is empty", self.client.registry());
+                    tokio::time::sleep(delay).await;
+                    delay = delay.saturating_mul(2).min(self.config.max_idle_delay);
+                    continue;
+                }
+            };
+
+            self.delivered += 1;
+            return Ok(Some(QueuedMessage {
+                id: queue.id,
+                count: queue.count,
+                date: queue.date,
+                text: queue.message.map(|text| text.to_string()),
+                data: rsp.res_data.map(|data| data.message_data),
+                extensions: rsp.extension.map(|ext| ext.blocks).unwrap_or_default(),
+            }));
+        }
+    }
+
+    /// Acknowledges `message`, removing it from the queue
+    pub async fn ack(&mut self, message: &QueuedMessage) -> Result<(), Error> {
+        let id = self.transaction_id("ack");
+        self.client
+            .transact(&MessageAck::new(&message.id), &id)
+            .await?;
+        Ok(())
+    }
+
+    /// Passes messages to `handler`, acking each one after the handler succeeds
+    ///
+    /// Returns the number of messages handled once a batch is done. The first error from
+    /// the handler stops the consumer and is returned as [`RunError::Handler`]; the message it
+    /// failed on stays in the queue.
+    pub async fn run<F, Fut, E>(&mut self, mut handler: F) -> Result<usize, RunError<E>>
+    where
+        F: FnMut(QueuedMessage) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+    {
+        let mut handled = 0;
+        while let Some(message) = self.next().await? {
+            let id = message.id.clone();
+            handler(message).await.map_err(RunError::Handler)?;
+
+            let tr_id = self.transaction_id("ack");
+            self.client.transact(&MessageAck::new(&id), &tr_id).await?;
+            handled += 1;
+        }
+
+        Ok(handled)
+    }
+
+    fn transaction_id(&mut self, command: &str) -> String {
+        self.counter += 1;
+        format!("{}:{}:{}", self.client.registry(), command, self.counter)
+    }
+}
@@ -1,17 +1,34 @@
-use crate::common::NoExtension;
+use std::fmt::{self, Write};
+
+use chrono::{DateTime, Utc};
+use quick_xml::escape::escape;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{self, DeserializeOwned, EnumAccess, MapAccess, VariantAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::common::{NoExtension, StringValue};
+use crate::contact::info::ContactInfoData;
+use crate::contact::transfer::ContactTransferResponseData;
+use crate::domain::info::DomainInfoResponseData;
 use crate::domain::transfer::DomainTransferResponseData;
 use crate::extensions::low_balance::LowBalance;
 use crate::host::info::HostInfoResponseData;
-use crate::request::{Command, Transaction};
-use serde::{Deserialize, Serialize};
+use crate::request::{Command, Extension, Transaction};
+use crate::response::ResponseTRID;
 
 impl<'a> Transaction<NoExtension> for MessagePoll<'a> {}
 
+impl<'a> Transaction<UnknownExtension> for MessagePoll<'a> {}
+
 impl<'a> Command for MessagePoll<'a> {
     type Response = MessagePollResponse;
     const COMMAND: &'static str = "poll";
 }
 
+impl Extension for UnknownExtension {
+    type Response = UnknownExtensionData;
+}
+
 // Request
 
 #[derive(Serialize, Debug)]
@@ -28,37 +45,589 @@ impl Default for MessagePoll<'static> {
     }
 }
 
+/// Marker extension to keep the &lt;extension&gt; blocks of poll responses as raw XML
+///
+/// Registries attach their own extensions to queued messages, often ones the client never
+/// negotiated. With `UnknownExtension`, each block is returned as [`UnknownData`] instead of
+/// being dropped; the &lt;poll&gt; request itself is sent without an &lt;extension&gt; element.
+#[derive(Debug, Default)]
+pub struct UnknownExtension;
+
+impl Serialize for UnknownExtension {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
 // Response
 
 /// Type that represents the &lt;trnData&gt; tag for message poll response
 #[non_exhaustive]
-#[derive(Deserialize, Debug)]
+#[derive(Debug)]
 pub enum MessageData {
     /// Data under the &lt;domain:trnData&gt; tag
-    #[serde(rename = "trnData")]
     DomainTransfer(DomainTransferResponseData),
+    /// Data under the &lt;contact:trnData&gt; tag
+    ContactTransfer(ContactTransferResponseData),
+    /// Data under the &lt;domain:infData&gt; tag
+    DomainInfo(Box<DomainInfoResponseData>),
+    /// Data under the &lt;contact:infData&gt; tag
+    ContactInfo(Box<ContactInfoData<'static>>),
     /// Data under the &lt;host:infData&gt; tag
-    #[serde(rename = "infData")]
     HostInfo(HostInfoResponseData),
+    /// Data under the &lt;domain:panData&gt; tag
+    DomainPendingAction(PendingActionData),
+    /// Data under the &lt;contact:panData&gt; tag
+    ContactPendingAction(PendingActionData),
+    /// Data under the &lt;host:panData&gt; tag
+    HostPendingAction(PendingActionData),
     /// Data under the &lt;lowbalance&gt; tag
-    #[serde(rename = "pollData")]
     LowBalance(LowBalance),
+    /// Data of a type this library does not know, so that the message can still be acked;
+    /// its markup is rebuilt rather than copied, see [`UnknownData`]
+    Unknown(UnknownData),
+}
+
+impl<'de> Deserialize<'de> for MessageData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_enum(
+            "MessageData",
+            &["trnData", "infData", "panData", "pollData"],
+            MessageDataVisitor,
+        )
+    }
+}
+
+struct MessageDataVisitor;
+
+impl<'de> Visitor<'de> for MessageDataVisitor {
+    type Value = MessageData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a poll message data tag")
+    }
+
+    /// Tells the data apart by the local name of its tag
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let (name, variant) = data.variant::<String>()?;
+        Ok(match name.as_str() {
+            "trnData" => match variant.newtype_variant()? {
+                TransferData::Domain(data) => MessageData::DomainTransfer(data),
+                TransferData::Contact(data) => MessageData::ContactTransfer(data),
+                TransferData::Unknown(data) => MessageData::Unknown(data),
+            },
+            "infData" => match variant.newtype_variant()? {
+                InfoData::Domain(data) => MessageData::DomainInfo(data),
+                InfoData::Contact(data) => MessageData::ContactInfo(data),
+                InfoData::Host(data) => MessageData::HostInfo(data),
+                InfoData::Unknown(data) => MessageData::Unknown(data),
+            },
+            "panData" => match variant.newtype_variant()? {
+                PanData::Domain(data) => MessageData::DomainPendingAction(data),
+                PanData::Contact(data) => MessageData::ContactPendingAction(data),
+                PanData::Host(data) => MessageData::HostPendingAction(data),
+                PanData::Unknown(data) => MessageData::Unknown(data),
+            },
+            "pollData" => MessageData::LowBalance(variant.newtype_variant()?),
+            _ => {
+                let node = variant.newtype_variant::<Node>()?;
+                MessageData::Unknown(UnknownData::new(name, node.into_entries()))
+            }
+        })
+    }
+}
+
+/// An element in a poll response that this library does not know
+///
+/// The deserializer does not give access to the response text, so `xml` is not the original
+/// markup but is rebuilt from the parsed element: child elements take the prefix of the
+/// namespace declared on the element, or none if it was declared on an ancestor, attribute
+/// prefixes are dropped, and comments and whitespace between tags are not kept. Where the
+/// exact bytes matter, use [`EppClient::transact_with_exchange()`], which returns the
+/// response as it was received.
+///
+/// [`EppClient::transact_with_exchange()`]: crate::client::EppClient::transact_with_exchange
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnknownData {
+    /// The local name of the element
+    pub name: String,
+    /// The namespace of the element, taken from its default or prefixed declaration
+    pub namespace: Option<String>,
+    /// The markup inside the element, rebuilt from the parsed content
+    pub xml: String,
+}
+
+impl UnknownData {
+    fn new(name: String, entries: Vec<(String, Node)>) -> Self {
+        let mut namespace = None;
+        let mut prefix = None;
+        for (key, node) in &entries {
+            let value = match node {
+                Node::Text(value) => value,
+                Node::Element(_) => continue,
+            };
+
+            if key == "xmlns" {
+                namespace = Some(value.clone());
+                prefix = None;
+                break;
+            } else if let Some(name) = key.strip_prefix("xmlns:") {
+                if namespace.is_none() {
+                    namespace = Some(value.clone());
+                    prefix = Some(name);
+                }
+            }
+        }
+
+        let mut xml = String::new();
+        write_content(&mut xml, &entries, prefix);
+        Self {
+            name,
+            namespace,
+            xml,
+        }
+    }
+}
+
+/// Element content as the deserializer presents it
+///
+/// Attributes and text are strings, where text uses the `$value` key, and child elements are
+/// maps, which is enough to rebuild the markup.
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Element(Vec<(String, Node)>),
+}
+
+impl Node {
+    fn into_entries(self) -> Vec<(String, Node)> {
+        match self {
+            Self::Text(text) => vec![("$value".to_owned(), Self::Text(text))],
+            Self::Element(entries) => entries,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+
+struct NodeVisitor;
+
+impl<'de> Visitor<'de> for NodeVisitor {
+    type Value = Node;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an XML element, attribute or text")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(Node::Text(value.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(Node::Text(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Node::Element(Vec::new()))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            entries.push((key, map.next_value()?));
+        }
+
+        Ok(Node::Element(entries))
+    }
+}
+
+/// Writes the text and child elements among `entries`, leaving out the attributes
+fn write_content(xml: &mut String, entries: &[(String, Node)], prefix: Option<&str>) {
+    for (key, node) in entries {
+        match node {
+            Node::Text(text) if key == "$value" => xml.push_str(&escape(text)),
+            Node::Text(_) => {}
+            Node::Element(children) => {
+                let name = match prefix {
+                    Some(prefix) => format!("{prefix}:{key}"),
+                    None => key.clone(),
+                };
+
+                let _ = write!(xml, "<{name}");
+                for (key, node) in children {
+                    match node {
+                        Node::Text(value) if key != "$value" => {
+                            let _ = write!(xml, r#" {}="{}""#, key, escape(value));
+                        }
+                        _ => {}
+                    }
+                }
+
+                let mut content = String::new();
+                write_content(&mut content, children, prefix);
+                if content.is_empty() {
+                    xml.push_str("/>");
+                } else {
+                    let _ = write!(xml, ">{content}</{name}>");
+                }
+            }
+        }
+    }
+}
+
+/// What was found first while looking for the namespace of an object tag
+enum Lookahead {
+    /// A namespace declaration, as the attribute name and the namespace
+    Namespace(String, String),
+    /// The first child element of an object, reached without finding a declaration
+    Child(String),
+}
+
+/// Returns the next namespace declaration among the attributes of a tag
+///
+/// The deserializer only sees the local name of a tag, which is the same for all objects, so
+/// the object type is told apart by the namespace. Attributes come before child elements, so
+/// the declaration is found before any of the object data is consumed. Everything passed
+/// over is kept in `skipped`, in case the tag turns out to be unknown.
+///
+/// The prefix of the tag is not available either, so when the namespace is declared on an
+/// ancestor, such as &lt;epp&gt; or &lt;resData&gt;, the search stops at the &lt;name&gt; or
+/// &lt;id&gt; element that starts the data of every object, and the caller tells the object
+/// apart with `read_rest()` and `from_entries()`.
+fn next_namespace<'de, A: MapAccess<'de>>(
+    map: &mut A,
+    skipped: &mut Vec<(String, Node)>,
+) -> Result<Option<Lookahead>, A::Error> {
+    while let Some(key) = map.next_key::<String>()? {
+        if is_declaration(&key) {
+            return map
+                .next_value()
+                .map(|value| Some(Lookahead::Namespace(key, value)));
+        } else if key == "name" || key == "id" {
+            return Ok(Some(Lookahead::Child(key)));
+        }
+
+        skipped.push((key, map.next_value()?));
+    }
+
+    Ok(None)
+}
+
+fn is_declaration(key: &str) -> bool {
+    key == "xmlns" || key.starts_with("xmlns:")
+}
+
+/// Deserializes the rest of an object tag, after `next_namespace()` found its namespace
+fn resume<'de, A: MapAccess<'de>, T: Deserialize<'de>>(map: A) -> Result<T, A::Error> {
+    T::deserialize(MapAccessDeserializer::new(map))
+}
+
+/// Reads the rest of an object tag, from the `child` element `next_namespace()` stopped at,
+/// after the attributes in `entries`
+fn read_rest<'de, A: MapAccess<'de>>(
+    child: String,
+    mut map: A,
+    mut entries: Vec<(String, Node)>,
+) -> Result<Vec<(String, Node)>, A::Error> {
+    entries.push((child, map.next_value()?));
+    while let Some(key) = map.next_key::<String>()? {
+        entries.push((key, map.next_value()?));
+    }
+
+    Ok(entries)
+}
+
+/// Whether any of the child elements among `entries` has one of the given `names`
+fn has_child(entries: &[(String, Node)], names: &[&str]) -> bool {
+    entries
+        .iter()
+        .any(|(key, node)| matches!(node, Node::Element(_)) && names.contains(&key.as_str()))
+}
+
+/// Deserializes object data read by `read_rest()`, or returns `None` if it does not fit `T`
+///
+/// The data is deserialized from rebuilt markup, which works because element prefixes are
+/// not seen by the deserializer anyway.
+fn from_entries<T: DeserializeOwned>(entries: &[(String, Node)]) -> Option<T> {
+    let mut xml = "<data>".to_owned();
+    write_content(&mut xml, entries, None);
+    xml.push_str("</data>");
+    quick_xml::de::from_str(&xml).ok()
+}
+
+/// Object transfer data, told apart by the namespace of the &lt;trnData&gt; tag
+///
+/// Without a declaration on the tag, transfer data with an &lt;id&gt; is for a contact, and
+/// any other is for a domain; data that does not fit is kept as [`UnknownData`].
+#[derive(Debug)]
+enum TransferData {
+    Domain(DomainTransferResponseData),
+    Contact(ContactTransferResponseData),
+    Unknown(UnknownData),
+}
+
+impl<'de> Deserialize<'de> for TransferData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(TransferDataVisitor)
+    }
+}
+
+struct TransferDataVisitor;
+
+impl<'de> Visitor<'de> for TransferDataVisitor {
+    type Value = TransferData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a <trnData> tag")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut skipped = Vec::new();
+        while let Some(found) = next_namespace(&mut map, &mut skipped)? {
+            return match found {
+                Lookahead::Namespace(_, xmlns) if xmlns == crate::domain::XMLNS => {
+                    resume(map).map(TransferData::Domain)
+                }
+                Lookahead::Namespace(_, xmlns) if xmlns == crate::contact::XMLNS => {
+                    resume(map).map(TransferData::Contact)
+                }
+                Lookahead::Namespace(key, xmlns) => {
+                    skipped.push((key, Node::Text(xmlns)));
+                    continue;
+                }
+                Lookahead::Child(name) => {
+                    let entries = read_rest(name, map, skipped)?;
+                    let data = match has_child(&entries, &["id"]) {
+                        true => from_entries(&entries).map(TransferData::Contact),
+                        false => from_entries(&entries).map(TransferData::Domain),
+                    };
+                    Ok(data.unwrap_or_else(|| {
+                        TransferData::Unknown(UnknownData::new("trnData".to_owned(), entries))
+                    }))
+                }
+            };
+        }
+
+        let data = UnknownData::new("trnData".to_owned(), skipped);
+        Ok(TransferData::Unknown(data))
+    }
+}
+
+/// Object info data, told apart by the namespace of the &lt;infData&gt; tag
+///
+/// Without a declaration on the tag, info data with an &lt;id&gt; is for a contact, and info
+/// data with any of the elements only domains have, such as &lt;registrant&gt;, &lt;ns&gt; or
+/// &lt;exDate&gt;, is for a domain. Any other is for a host; data that does not fit is kept
+/// as [`UnknownData`].
+#[derive(Debug)]
+enum InfoData {
+    Domain(Box<DomainInfoResponseData>),
+    Contact(Box<ContactInfoData<'static>>),
+    Host(HostInfoResponseData),
+    Unknown(UnknownData),
+}
+
+impl<'de> Deserialize<'de> for InfoData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(InfoDataVisitor)
+    }
+}
+
+struct InfoDataVisitor;
+
+/// Elements of domain info data that host info data does not have
+const DOMAIN_INFO_ONLY: &[&str] = &["registrant", "contact", "ns", "host", "exDate", "authInfo"];
+
+impl<'de> Visitor<'de> for InfoDataVisitor {
+    type Value = InfoData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an <infData> tag")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut skipped = Vec::new();
+        while let Some(found) = next_namespace(&mut map, &mut skipped)? {
+            return match found {
+                Lookahead::Namespace(_, xmlns) if xmlns == crate::domain::XMLNS => {
+                    resume(map).map(|data| InfoData::Domain(Box::new(data)))
+                }
+                Lookahead::Namespace(_, xmlns) if xmlns == crate::contact::XMLNS => {
+                    resume(map).map(|data| InfoData::Contact(Box::new(data)))
+                }
+                Lookahead::Namespace(_, xmlns) if xmlns == crate::host::XMLNS => {
+                    resume(map).map(InfoData::Host)
+                }
+                Lookahead::Namespace(key, xmlns) => {
+                    skipped.push((key, Node::Text(xmlns)));
+                    continue;
+                }
+                Lookahead::Child(name) => {
+                    let entries = read_rest(name, map, skipped)?;
+                    let data = if has_child(&entries, &["id"]) {
+                        from_entries(&entries).map(|data| InfoData::Contact(Box::new(data)))
+                    } else if has_child(&entries, DOMAIN_INFO_ONLY) {
+                        from_entries(&entries).map(|data| InfoData::Domain(Box::new(data)))
+                    } else {
+                        from_entries(&entries).map(InfoData::Host)
+                    };
+                    Ok(data.unwrap_or_else(|| {
+                        InfoData::Unknown(UnknownData::new("infData".to_owned(), entries))
+                    }))
+                }
+            };
+        }
+
+        let data = UnknownData::new("infData".to_owned(), skipped);
+        Ok(InfoData::Unknown(data))
+    }
+}
+
+/// Type that represents the &lt;panData&gt; tag, the outcome of an action that was pending
+///
+/// Registries that do not complete a command right away answer it with result code 1001,
+/// and queue one of these once the action has been approved or rejected.
+#[derive(Deserialize, Debug)]
+pub struct PendingActionData {
+    /// The domain or host name, or the contact id, the action was for
+    #[serde(rename = "name", alias = "id")]
+    pub object: PendingActionResult,
+    /// The transaction IDs of the command that requested the action
+    #[serde(rename = "paTRID")]
+    pub tr_ids: ResponseTRID,
+    /// The date the action was completed or rejected
+    #[serde(rename = "paDate")]
+    pub date: DateTime<Utc>,
+}
+
+/// Type that represents the object tag in &lt;panData&gt;
+#[derive(Deserialize, Debug)]
+pub struct PendingActionResult {
+    /// The object name or id
+    #[serde(rename = "$value")]
+    pub id: StringValue<'static>,
+    /// Whether the action was executed successfully
+    #[serde(rename = "paResult")]
+    pub success: bool,
+}
+
+/// Pending action data, told apart by the namespace of the &lt;panData&gt; tag
+///
+/// Without a declaration on the tag, pending action data with an &lt;id&gt; is for a contact,
+/// and any other is taken to be for a domain, as host actions are rarely pending; data that
+/// does not fit is kept as [`UnknownData`].
+#[derive(Debug)]
+enum PanData {
+    Domain(PendingActionData),
+    Contact(PendingActionData),
+    Host(PendingActionData),
+    Unknown(UnknownData),
+}
+
+impl<'de> Deserialize<'de> for PanData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(PanDataVisitor)
+    }
+}
+
+struct PanDataVisitor;
+
+impl<'de> Visitor<'de> for PanDataVisitor {
+    type Value = PanData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a <panData> tag")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut skipped = Vec::new();
+        while let Some(found) = next_namespace(&mut map, &mut skipped)? {
+            let object: fn(PendingActionData) -> PanData = match found {
+                Lookahead::Namespace(_, xmlns) if xmlns == crate::domain::XMLNS => PanData::Domain,
+                Lookahead::Namespace(_, xmlns) if xmlns == crate::contact::XMLNS => {
+                    PanData::Contact
+                }
+                Lookahead::Namespace(_, xmlns) if xmlns == crate::host::XMLNS => PanData::Host,
+                Lookahead::Namespace(key, xmlns) => {
+                    skipped.push((key, Node::Text(xmlns)));
+                    continue;
+                }
+                Lookahead::Child(name) => {
+                    let entries = read_rest(name, map, skipped)?;
+                    let object = match has_child(&entries, &["id"]) {
+                        true => PanData::Contact,
+                        false => PanData::Domain,
+                    };
+                    return Ok(match from_entries(&entries) {
+                        Some(data) => object(data),
+                        None => PanData::Unknown(UnknownData::new("panData".to_owned(), entries)),
+                    });
+                }
+            };
+
+            return resume(map).map(object);
+        }
+
+        let data = UnknownData::new("panData".to_owned(), skipped);
+        Ok(PanData::Unknown(data))
+    }
 }
 
 /// Type that represents the &lt;resData&gt; tag for message poll response
 #[derive(Deserialize, Debug)]
 pub struct MessagePollResponse {
-    /// Data under the &lt;trnData&gt; tag
-    #[serde(rename = "trnData", alias = "infData", alias = "pollData")]
+    /// Data under the &lt;resData&gt; tag
+    #[serde(rename = "$value")]
     pub message_data: MessageData,
 }
 
+/// The blocks under the &lt;extension&gt; tag of a poll response, see [`UnknownExtension`]
+#[derive(Debug)]
+pub struct UnknownExtensionData {
+    pub blocks: Vec<UnknownData>,
+}
+
+impl<'de> Deserialize<'de> for UnknownExtensionData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(UnknownExtensionDataVisitor)
+    }
+}
+
+struct UnknownExtensionDataVisitor;
+
+impl<'de> Visitor<'de> for UnknownExtensionDataVisitor {
+    type Value = UnknownExtensionData;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an <extension> tag")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut blocks = Vec::new();
+        while let Some(name) = map.next_key::<String>()? {
+            let node = map.next_value::<Node>()?;
+            if let Node::Element(entries) = node {
+                blocks.push(UnknownData::new(name, entries));
+            }
+        }
+
+        Ok(UnknownExtensionData { blocks })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::MessagePoll;
+    use super::{MessagePoll, UnknownData, UnknownExtension};
+    use crate::common::{ContactStatus, HostStatus};
     use crate::message::poll::MessageData;
     use crate::response::ResultCode;
-    use crate::tests::{assert_serialized, response_from_file, CLTRID, SVTRID};
+    use crate::tests::{
+        assert_serialized, response_from_file, response_from_file_with_ext, CLTRID, SVTRID,
+    };
 
     use chrono::{TimeZone, Utc};
     use std::net::IpAddr;
@@ -119,6 +688,56 @@ mod tests {
         assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
     }
 
+    #[test]
+    fn domain_transfer_response_ancestor_namespace() {
+        let object = response_from_file::<MessagePoll>(
+            "response/message/poll_domain_transfer_ancestor_ns.xml",
+        );
+
+        match &object.res_data().unwrap().message_data {
+            MessageData::DomainTransfer(tr) => {
+                assert_eq!(tr.name, "eppdev-transfer.com".into());
+                assert_eq!(tr.ack_id, "ClientY".into());
+                assert_eq!(
+                    tr.expiring_at,
+                    Utc.with_ymd_and_hms(2022, 7, 2, 14, 53, 19).single()
+                );
+            }
+            data => panic!("unexpected message data: {data:?}"),
+        }
+    }
+
+    #[test]
+    fn contact_transfer_response() {
+        let object =
+            response_from_file::<MessagePoll>("response/message/poll_contact_transfer.xml");
+        let result = object.res_data().unwrap();
+        let msg = object.message_queue().unwrap();
+
+        assert_eq!(
+            object.result.code,
+            ResultCode::CommandCompletedSuccessfullyAckToDequeue
+        );
+        assert_eq!(msg.count, 3);
+        assert_eq!(msg.id, "12347".to_string());
+
+        if let MessageData::ContactTransfer(tr) = &result.message_data {
+            assert_eq!(tr.id, "eppdev-contact-3".into());
+            assert_eq!(tr.transfer_status, "pending".into());
+            assert_eq!(tr.requester_id, "eppdev".into());
+            assert_eq!(tr.ack_id, "ClientY".into());
+            assert_eq!(
+                tr.ack_by,
+                Utc.with_ymd_and_hms(2021, 7, 28, 15, 31, 21).unwrap()
+            );
+        } else {
+            panic!("Wrong type");
+        }
+
+        assert_eq!(object.tr_ids.client_tr_id.unwrap(), CLTRID.into());
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+
     #[test]
     fn host_info_response() {
         let object = response_from_file::<MessagePoll>("response/message/poll_host_info.xml");
@@ -148,7 +767,7 @@ mod tests {
             assert_eq!(host.name, "ns.test.com".into());
 
             assert_eq!(host.roid, "1234".into());
-            assert!(host.statuses.iter().any(|s| s.status == "ok"));
+            assert!(host.statuses.iter().any(|s| s.status == HostStatus::Ok));
             assert!(host
                 .addresses
                 .iter()
@@ -172,6 +791,80 @@ mod tests {
         assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
     }
 
+    #[test]
+    fn contact_info_response() {
+        let object = response_from_file::<MessagePoll>("response/message/poll_contact_info.xml");
+        let result = object.res_data().unwrap();
+
+        assert_eq!(
+            object.result.code,
+            ResultCode::CommandCompletedSuccessfullyAckToDequeue
+        );
+
+        if let MessageData::ContactInfo(contact) = &result.message_data {
+            assert_eq!(contact.id, "eppdev-contact-3".into());
+            assert_eq!(contact.email, "contact@eppdev.net".into());
+            assert!(contact
+                .statuses
+                .iter()
+                .any(|s| s.status == ContactStatus::Ok));
+        } else {
+            panic!("Wrong type");
+        }
+
+        assert_eq!(object.tr_ids.client_tr_id.unwrap(), CLTRID.into());
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+
+    #[test]
+    fn host_info_response_ancestor_namespace() {
+        let object =
+            response_from_file::<MessagePoll>("response/message/poll_host_info_ancestor_ns.xml");
+
+        match &object.res_data().unwrap().message_data {
+            MessageData::HostInfo(host) => {
+                assert_eq!(host.name, "ns.test.com".into());
+                assert!(host
+                    .addresses
+                    .iter()
+                    .any(|a| a == &IpAddr::from([1, 1, 1, 1])));
+            }
+            data => panic!("unexpected message data: {data:?}"),
+        }
+    }
+
+    #[test]
+    fn domain_info_response_ancestor_namespace() {
+        let object =
+            response_from_file::<MessagePoll>("response/message/poll_domain_info_ancestor_ns.xml");
+
+        match &object.res_data().unwrap().message_data {
+            MessageData::DomainInfo(domain) => {
+                assert_eq!(domain.name, "eppdev-1.com".into());
+                assert_eq!(domain.registrant, Some("eppdev-contact-2".into()));
+                assert_eq!(
+                    domain.expiring_at,
+                    Utc.with_ymd_and_hms(2023, 7, 23, 15, 31, 20).single()
+                );
+            }
+            data => panic!("unexpected message data: {data:?}"),
+        }
+    }
+
+    #[test]
+    fn contact_info_response_ancestor_namespace() {
+        let object =
+            response_from_file::<MessagePoll>("response/message/poll_contact_info_ancestor_ns.xml");
+
+        match &object.res_data().unwrap().message_data {
+            MessageData::ContactInfo(contact) => {
+                assert_eq!(contact.id, "eppdev-contact-3".into());
+                assert_eq!(contact.email, "contact@eppdev.net".into());
+            }
+            data => panic!("unexpected message data: {data:?}"),
+        }
+    }
+
     #[test]
     fn message_only_response() {
         let object = response_from_file::<MessagePoll>("response/message/poll_message_only.xml");
@@ -201,6 +894,127 @@ mod tests {
         assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
     }
 
+    #[test]
+    fn domain_pending_action_response() {
+        let object =
+            response_from_file::<MessagePoll>("response/message/poll_domain_pending_action.xml");
+        let result = object.res_data().unwrap();
+
+        if let MessageData::DomainPendingAction(pan) = &result.message_data {
+            assert_eq!(pan.object.id, "example.com".into());
+            assert!(pan.object.success);
+            assert_eq!(pan.tr_ids.client_tr_id, Some("ABC-12345".into()));
+            assert_eq!(pan.tr_ids.server_tr_id, "54321-XYZ".into());
+            assert_eq!(
+                pan.date,
+                Utc.with_ymd_and_hms(1999, 4, 4, 22, 0, 0).unwrap()
+            );
+        } else {
+            panic!("Wrong type");
+        }
+
+        assert_eq!(object.tr_ids.client_tr_id.unwrap(), CLTRID.into());
+        assert_eq!(object.tr_ids.server_tr_id, SVTRID.into());
+    }
+
+    #[test]
+    fn contact_pending_action_response() {
+        let object =
+            response_from_file::<MessagePoll>("response/message/poll_contact_pending_action.xml");
+        let result = object.res_data().unwrap();
+
+        if let MessageData::ContactPendingAction(pan) = &result.message_data {
+            assert_eq!(pan.object.id, "sh8013".into());
+            assert!(!pan.object.success);
+            assert_eq!(pan.tr_ids.client_tr_id, None);
+            assert_eq!(pan.tr_ids.server_tr_id, "54321-XYZ".into());
+        } else {
+            panic!("Wrong type");
+        }
+    }
+
+    #[test]
+    fn contact_pending_action_response_ancestor_namespace() {
+        let object = response_from_file::<MessagePoll>(
+            "response/message/poll_contact_pending_action_ancestor_ns.xml",
+        );
+
+        match &object.res_data().unwrap().message_data {
+            MessageData::ContactPendingAction(pan) => {
+                assert_eq!(pan.object.id, "sh8013".into());
+                assert!(!pan.object.success);
+                assert_eq!(pan.tr_ids.server_tr_id, "54321-XYZ".into());
+            }
+            data => panic!("unexpected message data: {data:?}"),
+        }
+    }
+
+    #[test]
+    fn host_pending_action_response() {
+        let object =
+            response_from_file::<MessagePoll>("response/message/poll_host_pending_action.xml");
+        let result = object.res_data().unwrap();
+
+        if let MessageData::HostPendingAction(pan) = &result.message_data {
+            assert_eq!(pan.object.id, "ns1.example.com".into());
+            assert!(pan.object.success);
+        } else {
+            panic!("Wrong type");
+        }
+    }
+
+    #[test]
+    fn unknown_response() {
+        let object = response_from_file_with_ext::<MessagePoll, UnknownExtension>(
+            "response/message/poll_unknown.xml",
+        );
+        let result = object.res_data().unwrap();
+
+        let expected = UnknownData {
+            name: "creData".into(),
+            namespace: Some("urn:example:params:xml:ns:org-1.0".into()),
+            xml: concat!(
+                r#"<org:id result="1">org-1</org:id>"#,
+                r#"<org:name lang="en">Example &amp; Co</org:name>"#,
+                "<org:verified/>",
+            )
+            .into(),
+        };
+        match &result.message_data {
+            MessageData::Unknown(data) => assert_eq!(data, &expected),
+            data => panic!("unexpected message data: {data:?}"),
+        }
+
+        let extension = object.extension.as_ref().unwrap();
+        assert_eq!(
+            extension.blocks,
+            [UnknownData {
+                name: "data".into(),
+                namespace: Some("urn:example:params:xml:ns:notice-1.0".into()),
+                xml: "<notice:level>high</notice:level>".into(),
+            }]
+        );
+        assert_eq!(object.message_queue().unwrap().id, "12346");
+    }
+
+    #[test]
+    fn unknown_info_response() {
+        let object = response_from_file::<MessagePoll>("response/message/poll_unknown_info.xml");
+        let result = object.res_data().unwrap();
+
+        match &result.message_data {
+            MessageData::Unknown(data) => {
+                assert_eq!(data.name, "infData");
+                assert_eq!(
+                    data.namespace.as_deref(),
+                    Some("urn:example:params:xml:ns:org-1.0")
+                );
+                assert_eq!(data.xml, r#"<org:id>org-1</org:id><org:status s="ok"/>"#);
+            }
+            data => panic!("unexpected message data: {data:?}"),
+        }
+    }
+
     #[test]
     fn empty_queue_response() {
         let object = response_from_file::<MessagePoll>("response/message/poll_empty_queue.xml");
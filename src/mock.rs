@@ -0,0 +1,325 @@
+//! An in-memory EPP registry for testing clients offline
+//!
+//! The [`MockRegistry`] speaks the framing described in
+//! [RFC 5734](https://tools.ietf.org/html/rfc5734) over an in-process stream, and keeps the
+//! domains, contacts and hosts it is sent in memory. It answers the check, create, info,
+//! update, delete, renew, transfer and poll commands with the result codes of RFC 5730 to
+//! 5733, and queues service messages for transfers.
+//!
+//! This module is only available with the `mock` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use epp_client::domain::DomainCheck;
+//! use epp_client::login::Login;
+//! use epp_client::mock::MockRegistry;
+//! use epp_client::EppClient;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let registry = MockRegistry::new();
+//! registry.add_account("username", "password");
+//!
+//! let timeout = Duration::from_secs(5);
+//! let mut client = EppClient::new(registry.clone(), "mock".into(), timeout).await.unwrap();
+//! let login = Login::new("username", "password", None, None);
+//! client.transact(&login, "transaction-id").await.unwrap();
+//!
+//! let domain_check = DomainCheck { domains: &["eppdev.com"] };
+//! let response = client.transact(&domain_check, "transaction-id").await.unwrap();
+//! assert!(response.res_data.unwrap().list[0].available);
+//! # }
+//! ```
+
+use std::fmt::Write;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use quick_xml::escape::escape;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, DuplexStream};
+use tracing::{debug, warn};
+
+use crate::common::EPP_XMLNS;
+use crate::connection::Connector;
+use crate::error::Error;
+use crate::response::ResultCode;
+use crate::xml::EPP_XML_HEADER;
+use crate::{contact, domain, host};
+
+mod dom;
+use dom::Element;
+
+mod store;
+use store::Store;
+
+/// An in-memory EPP registry
+///
+/// Cloning the registry is cheap and yields a handle to the same objects. As a [`Connector`],
+/// every connection starts a new session on a background task.
+#[derive(Clone, Default)]
+pub struct MockRegistry {
+    store: Arc<Mutex<Store>>,
+}
+
+impl MockRegistry {
+    /// Create an empty registry, which accepts any login until an account is added
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a client account; once there are accounts, only their credentials are accepted
+    pub fn add_account(&self, client_id: &str, password: &str) {
+        let mut store = self.store.lock().unwrap();
+        store.add_account(client_id.to_owned(), password.to_owned());
+    }
+
+    /// Queue a service message for the client with id `client_id`
+    pub fn queue_message(&self, client_id: &str, text: &str) {
+        let mut store = self.store.lock().unwrap();
+        store.queue_message(client_id, text.to_owned());
+    }
+
+    /// Run a session on `stream` until the client logs out or disconnects
+    pub async fn serve(
+        &self,
+        mut stream: impl AsyncRead + AsyncWrite + Unpin,
+    ) -> Result<(), Error> {
+        write_frame(&mut stream, &greeting()).await?;
+
+        let mut client_id = None;
+        while let Some(request) = read_frame(&mut stream).await? {
+            let (response, close) = self.respond(&request, &mut client_id);
+            write_frame(&mut stream, &response).await?;
+            if close {
+                stream.shutdown().await?;
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Answer a request, returning the response and whether to end the session
+    fn respond(&self, request: &str, client_id: &mut Option<String>) -> (String, bool) {
+        let epp = match Element::parse(request) {
+            Ok(epp) => epp,
+            Err(e) => {
+                warn!("mock registry received invalid XML: {}", e);
+                let mut store = self.store.lock().unwrap();
+                let reply = Reply::new(ResultCode::CommandSyntaxError);
+                return (reply.render(None, &store.transaction_id()), false);
+            }
+        };
+
+        if epp.child("hello").is_some() {
+            return (greeting(), false);
+        }
+
+        let command = epp.child("command");
+        let client_transaction_id = command.and_then(|command| command.child_text("clTRID"));
+        let verb = command.and_then(|command| {
+            command
+                .children
+                .iter()
+                .find(|child| child.name != "extension" && child.name != "clTRID")
+        });
+
+        let mut store = self.store.lock().unwrap();
+        let mut close = false;
+        let result = match (verb, client_id.as_deref()) {
+            (None, _) => Err(ResultCode::CommandSyntaxError),
+            (Some(login), None) if login.name == "login" => store.login(login).map(|id| {
+                debug!("mock registry: {} logged in", id);
+                *client_id = Some(id);
+                Reply::new(ResultCode::CommandCompletedSuccessfully)
+            }),
+            (Some(logout), Some(_)) if logout.name == "logout" => {
+                close = true;
+                Ok(Reply::new(
+                    ResultCode::CommandCompletedSuccessfullyEndingSession,
+                ))
+            }
+            (Some(poll), Some(id)) if poll.name == "poll" => store.poll(id, poll),
+            (Some(verb), Some(id)) if verb.name != "login" => store.execute(id, verb),
+            (Some(_), _) => Err(ResultCode::CommandUseError),
+        };
+
+        let reply = result.unwrap_or_else(Reply::new);
+        let response = reply.render(client_transaction_id, &store.transaction_id());
+        (response, close)
+    }
+}
+
+#[async_trait]
+impl Connector for MockRegistry {
+    type Connection = DuplexStream;
+
+    async fn connect(&self, _: Duration) -> Result<Self::Connection, Error> {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let registry = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = registry.serve(server).await {
+                debug!("mock registry session ended: {}", e);
+            }
+        });
+
+        Ok(client)
+    }
+}
+
+/// The result of a command, along with the `<msgQ>` and `<resData>` contents
+struct Reply {
+    code: ResultCode,
+    msg_q: Option<String>,
+    res_data: Option<String>,
+}
+
+impl Reply {
+    fn new(code: ResultCode) -> Self {
+        Self {
+            code,
+            msg_q: None,
+            res_data: None,
+        }
+    }
+
+    /// A successful result with response data
+    fn data(res_data: String) -> Self {
+        Self {
+            code: ResultCode::CommandCompletedSuccessfully,
+            msg_q: None,
+            res_data: Some(res_data),
+        }
+    }
+
+    fn render(&self, client_transaction_id: Option<&str>, server_transaction_id: &str) -> String {
+        let mut xml = format!(
+            r#"{EPP_XML_HEADER}<epp xmlns="{EPP_XMLNS}"><response><result code="{}"><msg>{}</msg></result>"#,
+            self.code as u16,
+            message(self.code)
+        );
+        if let Some(msg_q) = &self.msg_q {
+            xml.push_str(msg_q);
+        }
+        if let Some(res_data) = &self.res_data {
+            let _ = write!(xml, "<resData>{res_data}</resData>");
+        }
+
+        xml.push_str("<trID>");
+        if let Some(id) = client_transaction_id {
+            let _ = write!(xml, "<clTRID>{}</clTRID>", escape(id));
+        }
+        let _ = write!(
+            xml,
+            "<svTRID>{}</svTRID></trID></response></epp>",
+            escape(server_transaction_id)
+        );
+        xml
+    }
+}
+
+fn greeting() -> String {
+    let mut xml = format!(
+        r#"{EPP_XML_HEADER}<epp xmlns="{EPP_XMLNS}"><greeting><svID>Mock EPP Registry</svID><svDate>{}</svDate>"#,
+        Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+    xml.push_str("<svcMenu><version>1.0</version><lang>en</lang>");
+    for uri in [host::XMLNS, domain::XMLNS, contact::XMLNS] {
+        let _ = write!(xml, "<objURI>{uri}</objURI>");
+    }
+    xml.push_str("</svcMenu>");
+    xml.push_str(concat!(
+        "<dcp><access><all/></access><statement><purpose><admin/><prov/></purpose>",
+        "<recipient><ours/><public/></recipient><retention><stated/></retention></statement></dcp>",
+        "</greeting></epp>"
+    ));
+    xml
+}
+
+/// The result message for `code`, as given in section 3 of RFC 5730
+fn message(code: ResultCode) -> &'static str {
+    use ResultCode::*;
+    match code {
+        CommandCompletedSuccessfully => "Command completed successfully",
+        CommandCompletedSuccessfullyActionPending => {
+            "Command completed successfully; action pending"
+        }
+        CommandCompletedSuccessfullyNoMessages => "Command completed successfully; no messages",
+        CommandCompletedSuccessfullyAckToDequeue => {
+            "Command completed successfully; ack to dequeue"
+        }
+        CommandCompletedSuccessfullyEndingSession => {
+            "Command completed successfully; ending session"
+        }
+        UnknownCommand => "Unknown command",
+        CommandSyntaxError => "Command syntax error",
+        CommandUseError => "Command use error",
+        RequiredParameterMissing => "Required parameter missing",
+        ParameterValueRangeError => "Parameter value range error",
+        ParameterValueSyntaxError => "Parameter value syntax error",
+        UnimplementedProtocolVersion => "Unimplemented protocol version",
+        UnimplementedCommand => "Unimplemented command",
+        UnimplementedOption => "Unimplemented option",
+        UnimplementedExtension => "Unimplemented extension",
+        BillingFailure => "Billing failure",
+        ObjectIsNotEligibleForRenewal => "Object is not eligible for renewal",
+        ObjectIsNotEligibleForTransfer => "Object is not eligible for transfer",
+        AuthenticationError => "Authentication error",
+        AuthorizationError => "Authorization error",
+        InvalidAuthorizationInformation => "Invalid authorization information",
+        ObjectPendingTransfer => "Object pending transfer",
+        ObjectNotPendingTransfer => "Object not pending transfer",
+        ObjectExists => "Object exists",
+        ObjectDoesNotExist => "Object does not exist",
+        ObjectStatusProhibitsOperation => "Object status prohibits operation",
+        ObjectAssociationProhibitsOperation => "Object association prohibits operation",
+        ParameterValuePolicyError => "Parameter value policy error",
+        UnimplementedObjectService => "Unimplemented object service",
+        DataManagementPolicyViolation => "Data management policy violation",
+        CommandFailed => "Command failed",
+        CommandFailedServerClosingConnection => "Command failed; server closing connection",
+        AuthenticationErrorServerClosingConnection => {
+            "Authentication error; server closing connection"
+        }
+        SessionLimitExceededServerClosingConnection => {
+            "Session limit exceeded; server closing connection"
+        }
+    }
+}
+
+/// Read a frame, or `None` if the client disconnected
+async fn read_frame(stream: &mut (impl AsyncRead + Unpin)) -> Result<Option<String>, Error> {
+    let len = match stream.read_u32().await {
+        Ok(len) => len as usize,
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let len = len.checked_sub(4).ok_or_else(|| {
+        Error::Io(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid frame length {len}"),
+        ))
+    })?;
+    let mut buf = vec![0; len];
+    stream.read_exact(&mut buf).await?;
+    String::from_utf8(buf)
+        .map(Some)
+        .map_err(|e| Error::Xml(e.into()))
+}
+
+async fn write_frame(stream: &mut (impl AsyncWrite + Unpin), xml: &str) -> Result<(), Error> {
+    let len = u32::try_from(xml.len() + 4).map_err(|e| Error::Other(e.into()))?;
+    let mut buf = Vec::with_capacity(xml.len() + 4);
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(xml.as_bytes());
+    stream.write_all(&buf).await?;
+    stream.flush().await?;
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! A minimal XML tree for reading requests in the mock registry
+//!
+//! The request types in this crate only implement `Serialize`, so the mock registry reads
+//! requests into this tree instead. Namespace prefixes are dropped from element and attribute
+//! names in favor of the resolved namespace, and namespace declarations are skipped.
+
+use std::fmt::Write;
+
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::ResolveResult;
+use quick_xml::NsReader;
+
+use crate::error::Error;
+
+#[derive(Clone, Debug, Default)]
+pub(super) struct Element {
+    pub(super) namespace: String,
+    pub(super) name: String,
+    pub(super) attrs: Vec<(String, String)>,
+    pub(super) children: Vec<Element>,
+    pub(super) text: String,
+}
+
+impl Element {
+    /// Parse a document, returning its root element
+    pub(super) fn parse(xml: &str) -> Result<Self, Error> {
+        let mut reader = NsReader::from_str(xml);
+        let mut stack: Vec<Element> = Vec::new();
+        let mut root = None;
+        loop {
+            let (namespace, event) = reader
+                .read_resolved_event()
+                .map_err(|e| Error::Xml(e.into()))?;
+            let element = match event {
+                Event::Start(start) => {
+                    stack.push(Self::from_start(namespace, &start)?);
+                    continue;
+                }
+                Event::Empty(start) => Self::from_start(namespace, &start)?,
+                Event::End(_) => match stack.pop() {
+                    Some(element) => element,
+                    None => return Err(Error::Xml("unexpected end tag".into())),
+                },
+                Event::Text(text) => {
+                    if let Some(top) = stack.last_mut() {
+                        let text = text.unescape().map_err(|e| Error::Xml(e.into()))?;
+                        top.text.push_str(&text);
+                    }
+                    continue;
+                }
+                Event::CData(data) => {
+                    if let Some(top) = stack.last_mut() {
+                        top.text.push_str(&String::from_utf8_lossy(&data));
+                    }
+                    continue;
+                }
+                Event::Eof => break,
+                _ => continue,
+            };
+
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => root = Some(element),
+            }
+        }
+
+        root.ok_or_else(|| Error::Xml("no root element".into()))
+    }
+
+    fn from_start(namespace: ResolveResult, start: &BytesStart) -> Result<Self, Error> {
+        let namespace = match namespace {
+            ResolveResult::Bound(namespace) => String::from_utf8_lossy(namespace.as_ref()).into(),
+            _ => String::new(),
+        };
+
+        let mut attrs = Vec::new();
+        for attr in start.attributes() {
+            let attr = attr.map_err(|e| Error::Xml(e.into()))?;
+            if attr.key.as_ref().starts_with(b"xmlns") {
+                continue;
+            }
+
+            let name = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+            let value = attr.unescape_value().map_err(|e| Error::Xml(e.into()))?;
+            attrs.push((name, value.into_owned()));
+        }
+
+        Ok(Self {
+            namespace,
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            attrs,
+            children: Vec::new(),
+            text: String::new(),
+        })
+    }
+
+    /// The first child element called `name`
+    pub(super) fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// All child elements called `name`
+    pub(super) fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// The trimmed text of the first child element called `name`
+    pub(super) fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|child| child.text.trim())
+    }
+
+    /// The value of the attribute called `name`
+    pub(super) fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Write the element back as XML, with `prefix` on all element names
+    pub(super) fn write(&self, prefix: &str, out: &mut String) {
+        let _ = write!(out, "<{}:{}", prefix, self.name);
+        for (key, value) in &self.attrs {
+            let _ = write!(out, " {}=\"{}\"", key, escape(value));
+        }
+
+        if self.children.is_empty() && self.text.trim().is_empty() {
+            out.push_str("/>");
+            return;
+        }
+
+        out.push('>');
+        out.push_str(&escape(self.text.trim()));
+        for child in &self.children {
+            child.write(prefix, out);
+        }
+        let _ = write!(out, "</{}:{}>", prefix, self.name);
+    }
+}
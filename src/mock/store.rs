@@ -0,0 +1,1508 @@
+//! The objects kept by the mock registry and the commands that act on them
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Write;
+use std::net::IpAddr;
+
+use chrono::{DateTime, Duration, Months, NaiveDate, SecondsFormat, Utc};
+use quick_xml::escape::escape;
+
+use super::dom::Element;
+use super::Reply;
+use crate::response::ResultCode;
+use crate::{contact, domain, host};
+
+/// How long the sponsoring client has to act on a transfer request
+const TRANSFER_PERIOD_DAYS: i64 = 5;
+
+#[derive(Default)]
+pub(super) struct Store {
+    /// Passwords by client id; any login is accepted while this is empty
+    accounts: HashMap<String, String>,
+    /// Domains by lowercase name
+    domains: BTreeMap<String, Domain>,
+    /// Contacts by id
+    contacts: BTreeMap<String, Contact>,
+    /// Hosts by lowercase name
+    hosts: BTreeMap<String, Host>,
+    /// Service messages by client id
+    queues: HashMap<String, VecDeque<Message>>,
+    counter: u64,
+}
+
+impl Store {
+    pub(super) fn add_account(&mut self, client_id: String, password: String) {
+        self.accounts.insert(client_id, password);
+    }
+
+    /// Queue a service message without response data for `client_id`
+    pub(super) fn queue_message(&mut self, client_id: &str, text: String) {
+        self.notify(client_id, text, None);
+    }
+
+    /// A new server transaction id
+    pub(super) fn transaction_id(&mut self) -> String {
+        format!("MOCK-{}", self.next_id())
+    }
+
+    /// Check the credentials in `login`, returning the client id on success
+    pub(super) fn login(&mut self, login: &Element) -> Result<String, ResultCode> {
+        let client_id = required(login, "clID")?;
+        let password = required(login, "pw")?;
+        if !self.accounts.is_empty() {
+            match self.accounts.get_mut(client_id) {
+                Some(stored) if stored == password => {
+                    if let Some(new) = login.child_text("newPW") {
+                        *stored = new.to_owned();
+                    }
+                }
+                _ => return Err(ResultCode::AuthenticationError),
+            }
+        }
+
+        Ok(client_id.to_owned())
+    }
+
+    pub(super) fn poll(&mut self, client_id: &str, poll: &Element) -> Result<Reply, ResultCode> {
+        let queue = self.queues.entry(client_id.to_owned()).or_default();
+        match poll.attr("op") {
+            Some("req") => {
+                let message = match queue.front() {
+                    Some(message) => message,
+                    None => {
+                        return Ok(Reply::new(
+                            ResultCode::CommandCompletedSuccessfullyNoMessages,
+                        ))
+                    }
+                };
+
+                let msg_q = format!(
+                    r#"<msgQ count="{}" id="{}"><qDate>{}</qDate><msg>{}</msg></msgQ>"#,
+                    queue.len(),
+                    message.id,
+                    date(&message.date),
+                    escape(&message.text)
+                );
+                Ok(Reply {
+                    code: ResultCode::CommandCompletedSuccessfullyAckToDequeue,
+                    msg_q: Some(msg_q),
+                    res_data: message.data.clone(),
+                })
+            }
+            Some("ack") => {
+                let id = poll
+                    .attr("msgID")
+                    .ok_or(ResultCode::RequiredParameterMissing)?;
+                let position = queue
+                    .iter()
+                    .position(|message| message.id.to_string() == id)
+                    .ok_or(ResultCode::ObjectDoesNotExist)?;
+                queue.remove(position);
+
+                let msg_q = format!(r#"<msgQ count="{}" id="{}"/>"#, queue.len(), escape(id));
+                Ok(Reply {
+                    code: ResultCode::CommandCompletedSuccessfully,
+                    msg_q: Some(msg_q),
+                    res_data: None,
+                })
+            }
+            Some(_) => Err(ResultCode::ParameterValueSyntaxError),
+            None => Err(ResultCode::RequiredParameterMissing),
+        }
+    }
+
+    /// Execute an object command, such as `<check>` or `<transfer>`, for `client_id`
+    pub(super) fn execute(
+        &mut self,
+        client_id: &str,
+        command: &Element,
+    ) -> Result<Reply, ResultCode> {
+        let object = command
+            .children
+            .first()
+            .ok_or(ResultCode::CommandSyntaxError)?;
+
+        match (command.name.as_str(), object.namespace.as_str()) {
+            ("check", domain::XMLNS) => {
+                Ok(check(domain::XMLNS, "domain", "name", object, |name| {
+                    self.domains.contains_key(&name.to_ascii_lowercase())
+                }))
+            }
+            ("check", contact::XMLNS) => Ok(check(contact::XMLNS, "contact", "id", object, |id| {
+                self.contacts.contains_key(id)
+            })),
+            ("check", host::XMLNS) => Ok(check(host::XMLNS, "host", "name", object, |name| {
+                self.hosts.contains_key(&name.to_ascii_lowercase())
+            })),
+            ("create", domain::XMLNS) => self.create_domain(client_id, object),
+            ("create", contact::XMLNS) => self.create_contact(client_id, object),
+            ("create", host::XMLNS) => self.create_host(client_id, object),
+            ("info", domain::XMLNS) => self.info_domain(client_id, object),
+            ("info", contact::XMLNS) => self.info_contact(client_id, object),
+            ("info", host::XMLNS) => self.info_host(object),
+            ("update", domain::XMLNS) => self.update_domain(client_id, object),
+            ("update", contact::XMLNS) => self.update_contact(client_id, object),
+            ("update", host::XMLNS) => self.update_host(client_id, object),
+            ("delete", domain::XMLNS) => self.delete_domain(client_id, object),
+            ("delete", contact::XMLNS) => self.delete_contact(client_id, object),
+            ("delete", host::XMLNS) => self.delete_host(client_id, object),
+            ("renew", domain::XMLNS) => self.renew_domain(client_id, object),
+            ("transfer", domain::XMLNS) => {
+                let op = command
+                    .attr("op")
+                    .ok_or(ResultCode::RequiredParameterMissing)?;
+                self.transfer_domain(client_id, op, object)
+            }
+            ("transfer", contact::XMLNS) => {
+                let op = command
+                    .attr("op")
+                    .ok_or(ResultCode::RequiredParameterMissing)?;
+                self.transfer_contact(client_id, op, object)
+            }
+            ("check" | "create" | "info" | "update" | "delete" | "renew" | "transfer", _) => {
+                Err(ResultCode::UnimplementedObjectService)
+            }
+            _ => Err(ResultCode::UnknownCommand),
+        }
+    }
+
+    fn create_domain(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        if !name.contains('.') || name.starts_with('.') || name.ends_with('.') {
+            return Err(ResultCode::ParameterValueSyntaxError);
+        }
+
+        let key = name.to_ascii_lowercase();
+        if self.domains.contains_key(&key) {
+            return Err(ResultCode::ObjectExists);
+        }
+
+        let months = period(object)?;
+        let auth = auth_info(object).ok_or(ResultCode::RequiredParameterMissing)?;
+        let registrant = object.child_text("registrant").map(str::to_owned);
+        let contacts = contacts(object);
+        let ns = name_servers(object.child("ns"))?;
+        self.check_references(
+            registrant.iter().chain(contacts.iter().map(|(_, id)| id)),
+            &ns,
+        )?;
+
+        let now = Utc::now();
+        let expiring_at = now
+            .checked_add_months(Months::new(months))
+            .ok_or(ResultCode::ParameterValueRangeError)?;
+        let roid = format!("D{}-MOCK", self.next_id());
+        self.domains.insert(
+            key,
+            Domain {
+                name: name.to_owned(),
+                meta: Meta::new(roid, client_id, now),
+                registrant,
+                contacts,
+                ns,
+                expiring_at,
+                auth: auth.to_owned(),
+                transfer: None,
+            },
+        );
+
+        let mut data = open_data("creData", "domain", domain::XMLNS);
+        tag(&mut data, "domain", "name", name);
+        tag(&mut data, "domain", "crDate", &date(&now));
+        tag(&mut data, "domain", "exDate", &date(&expiring_at));
+        data.push_str("</domain:creData>");
+        Ok(Reply::data(data))
+    }
+
+    fn info_domain(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        let key = name.to_ascii_lowercase();
+        let domain = self
+            .domains
+            .get(&key)
+            .ok_or(ResultCode::ObjectDoesNotExist)?;
+        let authorized = authorize(client_id, object, &domain.meta, &domain.auth)?;
+
+        let mut data = open_data("infData", "domain", domain::XMLNS);
+        tag(&mut data, "domain", "name", &domain.name);
+        tag(&mut data, "domain", "roid", &domain.meta.roid);
+        domain.meta.write_statuses("domain", false, &mut data);
+        if let Some(registrant) = &domain.registrant {
+            tag(&mut data, "domain", "registrant", registrant);
+        }
+        for (ty, id) in &domain.contacts {
+            let _ = write!(
+                data,
+                r#"<domain:contact type="{}">{}</domain:contact>"#,
+                escape(ty),
+                escape(id)
+            );
+        }
+        if !domain.ns.is_empty() {
+            data.push_str("<domain:ns>");
+            for host in &domain.ns {
+                tag(&mut data, "domain", "hostObj", host);
+            }
+            data.push_str("</domain:ns>");
+        }
+        let suffix = format!(".{key}");
+        for host in self
+            .hosts
+            .values()
+            .filter(|host| host.key().ends_with(&suffix))
+        {
+            tag(&mut data, "domain", "host", &host.name);
+        }
+        domain.meta.write_clients("domain", &mut data);
+        tag(&mut data, "domain", "exDate", &date(&domain.expiring_at));
+        if let Some(transferred_at) = &domain.meta.transferred_at {
+            tag(&mut data, "domain", "trDate", &date(transferred_at));
+        }
+        if authorized {
+            data.push_str("<domain:authInfo>");
+            tag(&mut data, "domain", "pw", &domain.auth);
+            data.push_str("</domain:authInfo>");
+        }
+        data.push_str("</domain:infData>");
+        Ok(Reply::data(data))
+    }
+
+    fn update_domain(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        let key = name.to_ascii_lowercase();
+        let mut domain = self
+            .domains
+            .get(&key)
+            .ok_or(ResultCode::ObjectDoesNotExist)?
+            .clone();
+        sponsor(client_id, &domain.meta)?;
+        domain.meta.check_update(object)?;
+
+        let mut contacts = Vec::new();
+        let mut hosts = Vec::new();
+        if let Some(rem) = object.child("rem") {
+            domain.meta.remove_statuses(rem);
+            for contact in self::contacts(rem) {
+                domain.contacts.retain(|existing| *existing != contact);
+            }
+            for host in name_servers(rem.child("ns"))? {
+                domain
+                    .ns
+                    .retain(|existing| !existing.eq_ignore_ascii_case(&host));
+            }
+        }
+        if let Some(add) = object.child("add") {
+            domain.meta.add_statuses(add)?;
+            for contact in self::contacts(add) {
+                if domain.contacts.contains(&contact) {
+                    return Err(ResultCode::ParameterValuePolicyError);
+                }
+                contacts.push(contact.1.clone());
+                domain.contacts.push(contact);
+            }
+            for host in name_servers(add.child("ns"))? {
+                if domain
+                    .ns
+                    .iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(&host))
+                {
+                    return Err(ResultCode::ParameterValuePolicyError);
+                }
+                hosts.push(host.clone());
+                domain.ns.push(host);
+            }
+        }
+        if let Some(chg) = object.child("chg") {
+            if let Some(registrant) = chg.child_text("registrant") {
+                contacts.push(registrant.to_owned());
+                domain.registrant = Some(registrant.to_owned());
+            }
+            if let Some(auth) = auth_info(chg) {
+                domain.auth = auth.to_owned();
+            }
+        }
+
+        self.check_references(contacts.iter(), &hosts)?;
+        domain.meta.touch(client_id, Utc::now());
+        self.domains.insert(key, domain);
+        Ok(Reply::new(ResultCode::CommandCompletedSuccessfully))
+    }
+
+    fn delete_domain(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        let key = name.to_ascii_lowercase();
+        let domain = self
+            .domains
+            .get(&key)
+            .ok_or(ResultCode::ObjectDoesNotExist)?;
+        sponsor(client_id, &domain.meta)?;
+        domain.meta.check_status(&DELETE_PROHIBITED)?;
+
+        // Hosts under the domain go with it, unless other domains still use them
+        let suffix = format!(".{key}");
+        let subordinates = self
+            .hosts
+            .keys()
+            .filter(|host| host.ends_with(&suffix))
+            .cloned()
+            .collect::<Vec<_>>();
+        for host in &subordinates {
+            if self.host_linked(host, Some(&key)) {
+                return Err(ResultCode::ObjectAssociationProhibitsOperation);
+            }
+        }
+
+        for host in &subordinates {
+            self.hosts.remove(host);
+        }
+        self.domains.remove(&key);
+        Ok(Reply::new(ResultCode::CommandCompletedSuccessfully))
+    }
+
+    fn renew_domain(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        let current = required(object, "curExpDate")?
+            .parse::<NaiveDate>()
+            .map_err(|_| ResultCode::ParameterValueSyntaxError)?;
+        let months = period(object)?;
+
+        let domain = self
+            .domains
+            .get_mut(&name.to_ascii_lowercase())
+            .ok_or(ResultCode::ObjectDoesNotExist)?;
+        sponsor(client_id, &domain.meta)?;
+        domain.meta.check_status(&RENEW_PROHIBITED)?;
+        if domain.expiring_at.date_naive() != current {
+            return Err(ResultCode::ParameterValuePolicyError);
+        }
+
+        domain.expiring_at = domain
+            .expiring_at
+            .checked_add_months(Months::new(months))
+            .ok_or(ResultCode::ParameterValueRangeError)?;
+
+        let mut data = open_data("renData", "domain", domain::XMLNS);
+        tag(&mut data, "domain", "name", &domain.name);
+        tag(&mut data, "domain", "exDate", &date(&domain.expiring_at));
+        data.push_str("</domain:renData>");
+        Ok(Reply::data(data))
+    }
+
+    fn transfer_domain(
+        &mut self,
+        client_id: &str,
+        op: &str,
+        object: &Element,
+    ) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        let months = period(object)?;
+        let domain = self
+            .domains
+            .get_mut(&name.to_ascii_lowercase())
+            .ok_or(ResultCode::ObjectDoesNotExist)?;
+
+        let now = Utc::now();
+        let expiring_at = domain
+            .expiring_at
+            .checked_add_months(Months::new(months))
+            .ok_or(ResultCode::ParameterValueRangeError)?;
+        let (code, notify) = transfer(
+            client_id,
+            op,
+            object,
+            &mut domain.meta,
+            &domain.auth,
+            &mut domain.transfer,
+            Some(expiring_at),
+            now,
+        )?;
+
+        let transfer = domain.transfer.as_ref().ok_or(ResultCode::CommandFailed)?;
+        if transfer.status == "clientApproved" && notify.is_some() {
+            domain.expiring_at = transfer.expiring_at.unwrap_or(domain.expiring_at);
+        }
+
+        let data = transfer_data("domain", domain::XMLNS, "name", &domain.name, transfer);
+        if let Some((recipient, text)) = notify {
+            self.notify(&recipient, text, Some(data.clone()));
+        }
+        Ok(Reply {
+            code,
+            msg_q: None,
+            res_data: Some(data),
+        })
+    }
+
+    fn create_contact(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let id = required(object, "id")?;
+        if self.contacts.contains_key(id) {
+            return Err(ResultCode::ObjectExists);
+        }
+
+        let postal_info = object
+            .child("postalInfo")
+            .ok_or(ResultCode::RequiredParameterMissing)?;
+        let email = required(object, "email")?;
+        let auth = auth_info(object).ok_or(ResultCode::RequiredParameterMissing)?;
+
+        let now = Utc::now();
+        let roid = format!("C{}-MOCK", self.next_id());
+        self.contacts.insert(
+            id.to_owned(),
+            Contact {
+                id: id.to_owned(),
+                meta: Meta::new(roid, client_id, now),
+                postal_info: postal_info.clone(),
+                voice: object.child("voice").cloned(),
+                fax: object.child("fax").cloned(),
+                email: email.to_owned(),
+                auth: auth.to_owned(),
+                disclose: object.child("disclose").cloned(),
+                transfer: None,
+            },
+        );
+
+        let mut data = open_data("creData", "contact", contact::XMLNS);
+        tag(&mut data, "contact", "id", id);
+        tag(&mut data, "contact", "crDate", &date(&now));
+        data.push_str("</contact:creData>");
+        Ok(Reply::data(data))
+    }
+
+    fn info_contact(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let id = required(object, "id")?;
+        let contact = self
+            .contacts
+            .get(id)
+            .ok_or(ResultCode::ObjectDoesNotExist)?;
+        let authorized = authorize(client_id, object, &contact.meta, &contact.auth)?;
+
+        let mut data = open_data("infData", "contact", contact::XMLNS);
+        tag(&mut data, "contact", "id", &contact.id);
+        tag(&mut data, "contact", "roid", &contact.meta.roid);
+        contact
+            .meta
+            .write_statuses("contact", self.contact_linked(id), &mut data);
+        contact.postal_info.write("contact", &mut data);
+        match &contact.voice {
+            Some(voice) => voice.write("contact", &mut data),
+            None => data.push_str("<contact:voice/>"),
+        }
+        if let Some(fax) = &contact.fax {
+            fax.write("contact", &mut data);
+        }
+        tag(&mut data, "contact", "email", &contact.email);
+        contact.meta.write_clients("contact", &mut data);
+        if let Some(transferred_at) = &contact.meta.transferred_at {
+            tag(&mut data, "contact", "trDate", &date(transferred_at));
+        }
+        if authorized {
+            data.push_str("<contact:authInfo>");
+            tag(&mut data, "contact", "pw", &contact.auth);
+            data.push_str("</contact:authInfo>");
+        }
+        if let Some(disclose) = &contact.disclose {
+            disclose.write("contact", &mut data);
+        }
+        data.push_str("</contact:infData>");
+        Ok(Reply::data(data))
+    }
+
+    fn update_contact(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let id = required(object, "id")?;
+        let mut contact = self
+            .contacts
+            .get(id)
+            .ok_or(ResultCode::ObjectDoesNotExist)?
+            .clone();
+        sponsor(client_id, &contact.meta)?;
+        contact.meta.check_update(object)?;
+
+        if let Some(rem) = object.child("rem") {
+            contact.meta.remove_statuses(rem);
+        }
+        if let Some(add) = object.child("add") {
+            contact.meta.add_statuses(add)?;
+        }
+        if let Some(chg) = object.child("chg") {
+            if let Some(postal_info) = chg.child("postalInfo") {
+                contact.postal_info = postal_info.clone();
+            }
+            if let Some(voice) = chg.child("voice") {
+                contact.voice = Some(voice.clone());
+            }
+            if let Some(fax) = chg.child("fax") {
+                contact.fax = Some(fax.clone());
+            }
+            if let Some(email) = chg.child_text("email") {
+                contact.email = email.to_owned();
+            }
+            if let Some(auth) = auth_info(chg) {
+                contact.auth = auth.to_owned();
+            }
+            if let Some(disclose) = chg.child("disclose") {
+                contact.disclose = Some(disclose.clone());
+            }
+        }
+
+        contact.meta.touch(client_id, Utc::now());
+        self.contacts.insert(id.to_owned(), contact);
+        Ok(Reply::new(ResultCode::CommandCompletedSuccessfully))
+    }
+
+    fn delete_contact(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let id = required(object, "id")?;
+        let contact = self
+            .contacts
+            .get(id)
+            .ok_or(ResultCode::ObjectDoesNotExist)?;
+        sponsor(client_id, &contact.meta)?;
+        contact.meta.check_status(&DELETE_PROHIBITED)?;
+        if self.contact_linked(id) {
+            return Err(ResultCode::ObjectAssociationProhibitsOperation);
+        }
+
+        self.contacts.remove(id);
+        Ok(Reply::new(ResultCode::CommandCompletedSuccessfully))
+    }
+
+    fn transfer_contact(
+        &mut self,
+        client_id: &str,
+        op: &str,
+        object: &Element,
+    ) -> Result<Reply, ResultCode> {
+        let id = required(object, "id")?;
+        let contact = self
+            .contacts
+            .get_mut(id)
+            .ok_or(ResultCode::ObjectDoesNotExist)?;
+
+        let (code, notify) = transfer(
+            client_id,
+            op,
+            object,
+            &mut contact.meta,
+            &contact.auth,
+            &mut contact.transfer,
+            None,
+            Utc::now(),
+        )?;
+
+        let transfer = contact.transfer.as_ref().ok_or(ResultCode::CommandFailed)?;
+        let data = transfer_data("contact", contact::XMLNS, "id", &contact.id, transfer);
+        if let Some((recipient, text)) = notify {
+            self.notify(&recipient, text, Some(data.clone()));
+        }
+        Ok(Reply {
+            code,
+            msg_q: None,
+            res_data: Some(data),
+        })
+    }
+
+    fn create_host(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        let key = name.to_ascii_lowercase();
+        if self.hosts.contains_key(&key) {
+            return Err(ResultCode::ObjectExists);
+        }
+
+        let addresses = addresses(object)?;
+        match self.superordinate(&key) {
+            // Hosts under a domain in the registry need glue records, and only the sponsor of
+            // that domain may create them
+            Some(domain) => {
+                sponsor(client_id, &domain.meta)?;
+                if addresses.is_empty() {
+                    return Err(ResultCode::RequiredParameterMissing);
+                }
+            }
+            None if !addresses.is_empty() => return Err(ResultCode::ParameterValuePolicyError),
+            None => {}
+        }
+
+        let now = Utc::now();
+        let roid = format!("H{}-MOCK", self.next_id());
+        self.hosts.insert(
+            key,
+            Host {
+                name: name.to_owned(),
+                meta: Meta::new(roid, client_id, now),
+                addresses,
+            },
+        );
+
+        let mut data = open_data("creData", "host", host::XMLNS);
+        tag(&mut data, "host", "name", name);
+        tag(&mut data, "host", "crDate", &date(&now));
+        data.push_str("</host:creData>");
+        Ok(Reply::data(data))
+    }
+
+    fn info_host(&mut self, object: &Element) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        let key = name.to_ascii_lowercase();
+        let host = self.hosts.get(&key).ok_or(ResultCode::ObjectDoesNotExist)?;
+
+        let mut data = open_data("infData", "host", host::XMLNS);
+        tag(&mut data, "host", "name", &host.name);
+        tag(&mut data, "host", "roid", &host.meta.roid);
+        host.meta
+            .write_statuses("host", self.host_linked(&key, None), &mut data);
+        for addr in &host.addresses {
+            let ip = match addr {
+                IpAddr::V4(_) => "v4",
+                IpAddr::V6(_) => "v6",
+            };
+            let _ = write!(data, r#"<host:addr ip="{ip}">{addr}</host:addr>"#);
+        }
+        host.meta.write_clients("host", &mut data);
+        if let Some(transferred_at) = &host.meta.transferred_at {
+            tag(&mut data, "host", "trDate", &date(transferred_at));
+        }
+        data.push_str("</host:infData>");
+        Ok(Reply::data(data))
+    }
+
+    fn update_host(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        let key = name.to_ascii_lowercase();
+        let mut host = self
+            .hosts
+            .get(&key)
+            .ok_or(ResultCode::ObjectDoesNotExist)?
+            .clone();
+        sponsor(client_id, &host.meta)?;
+        host.meta.check_update(object)?;
+
+        if let Some(rem) = object.child("rem") {
+            host.meta.remove_statuses(rem);
+            for addr in addresses(rem)? {
+                host.addresses.retain(|existing| *existing != addr);
+            }
+        }
+        if let Some(add) = object.child("add") {
+            host.meta.add_statuses(add)?;
+            for addr in addresses(add)? {
+                if host.addresses.contains(&addr) {
+                    return Err(ResultCode::ParameterValuePolicyError);
+                }
+                host.addresses.push(addr);
+            }
+        }
+
+        let new_name = object.child("chg").and_then(|chg| chg.child_text("name"));
+        let new_key = match new_name {
+            Some(new_name) => {
+                let new_key = new_name.to_ascii_lowercase();
+                if new_key != key && self.hosts.contains_key(&new_key) {
+                    return Err(ResultCode::ObjectExists);
+                }
+                host.name = new_name.to_owned();
+                new_key
+            }
+            None => key.clone(),
+        };
+
+        let internal = self.superordinate(&new_key).is_some();
+        if internal == host.addresses.is_empty() {
+            return Err(ResultCode::ParameterValuePolicyError);
+        }
+
+        host.meta.touch(client_id, Utc::now());
+        if new_key != key {
+            self.hosts.remove(&key);
+            for domain in self.domains.values_mut() {
+                for ns in domain.ns.iter_mut() {
+                    if ns.eq_ignore_ascii_case(&key) {
+                        *ns = host.name.clone();
+                    }
+                }
+            }
+        }
+        self.hosts.insert(new_key, host);
+        Ok(Reply::new(ResultCode::CommandCompletedSuccessfully))
+    }
+
+    fn delete_host(&mut self, client_id: &str, object: &Element) -> Result<Reply, ResultCode> {
+        let name = required(object, "name")?;
+        let key = name.to_ascii_lowercase();
+        let host = self.hosts.get(&key).ok_or(ResultCode::ObjectDoesNotExist)?;
+        sponsor(client_id, &host.meta)?;
+        host.meta.check_status(&DELETE_PROHIBITED)?;
+        if self.host_linked(&key, None) {
+            return Err(ResultCode::ObjectAssociationProhibitsOperation);
+        }
+
+        self.hosts.remove(&key);
+        Ok(Reply::new(ResultCode::CommandCompletedSuccessfully))
+    }
+
+    /// Check that the contacts and hosts a domain refers to exist
+    fn check_references<'a>(
+        &self,
+        mut contacts: impl Iterator<Item = &'a String>,
+        hosts: &[String],
+    ) -> Result<(), ResultCode> {
+        let contacts_exist = contacts.all(|id| self.contacts.contains_key(id));
+        let hosts_exist = hosts
+            .iter()
+            .all(|host| self.hosts.contains_key(&host.to_ascii_lowercase()));
+        match contacts_exist && hosts_exist {
+            true => Ok(()),
+            false => Err(ResultCode::ObjectDoesNotExist),
+        }
+    }
+
+    /// The domain in the registry that the host called `key` is subordinate to
+    fn superordinate(&self, key: &str) -> Option<&Domain> {
+        let mut rest = key;
+        while let Some((_, parent)) = rest.split_once('.') {
+            if let Some(domain) = self.domains.get(parent) {
+                return Some(domain);
+            }
+            rest = parent;
+        }
+        None
+    }
+
+    fn contact_linked(&self, id: &str) -> bool {
+        self.domains.values().any(|domain| {
+            domain.registrant.as_deref() == Some(id)
+                || domain.contacts.iter().any(|(_, contact)| contact == id)
+        })
+    }
+
+    /// Whether any domain, other than the one called `except`, uses the host as a name server
+    fn host_linked(&self, key: &str, except: Option<&str>) -> bool {
+        self.domains
+            .iter()
+            .filter(|(name, _)| Some(name.as_str()) != except)
+            .any(|(_, domain)| domain.ns.iter().any(|ns| ns.eq_ignore_ascii_case(key)))
+    }
+
+    fn notify(&mut self, client_id: &str, text: String, data: Option<String>) {
+        let id = self.next_id();
+        self.queues
+            .entry(client_id.to_owned())
+            .or_default()
+            .push_back(Message {
+                id,
+                date: Utc::now(),
+                text,
+                data,
+            });
+    }
+
+    fn next_id(&mut self) -> u64 {
+        self.counter += 1;
+        self.counter
+    }
+}
+
+#[derive(Clone)]
+struct Domain {
+    name: String,
+    meta: Meta,
+    registrant: Option<String>,
+    /// Pairs of contact type and contact id
+    contacts: Vec<(String, String)>,
+    ns: Vec<String>,
+    expiring_at: DateTime<Utc>,
+    auth: String,
+    transfer: Option<Transfer>,
+}
+
+/// A contact, with the elements that are echoed back unchanged kept as they were sent
+#[derive(Clone)]
+struct Contact {
+    id: String,
+    meta: Meta,
+    postal_info: Element,
+    voice: Option<Element>,
+    fax: Option<Element>,
+    email: String,
+    auth: String,
+    disclose: Option<Element>,
+    transfer: Option<Transfer>,
+}
+
+#[derive(Clone)]
+struct Host {
+    name: String,
+    meta: Meta,
+    addresses: Vec<IpAddr>,
+}
+
+impl Host {
+    fn key(&self) -> String {
+        self.name.to_ascii_lowercase()
+    }
+}
+
+/// Data shared by all objects
+#[derive(Clone)]
+struct Meta {
+    roid: String,
+    /// Statuses other than `ok` and `linked`, which are derived
+    statuses: Vec<String>,
+    client_id: String,
+    creator_id: String,
+    created_at: DateTime<Utc>,
+    updater_id: Option<String>,
+    updated_at: Option<DateTime<Utc>>,
+    transferred_at: Option<DateTime<Utc>>,
+}
+
+impl Meta {
+    fn new(roid: String, client_id: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            roid,
+            statuses: Vec::new(),
+            client_id: client_id.to_owned(),
+            creator_id: client_id.to_owned(),
+            created_at: now,
+            updater_id: None,
+            updated_at: None,
+            transferred_at: None,
+        }
+    }
+
+    /// Fail with 2304 if the object has any of the `prohibited` statuses
+    fn check_status(&self, prohibited: &[&str]) -> Result<(), ResultCode> {
+        match self
+            .statuses
+            .iter()
+            .any(|s| prohibited.contains(&s.as_str()))
+        {
+            true => Err(ResultCode::ObjectStatusProhibitsOperation),
+            false => Ok(()),
+        }
+    }
+
+    /// Check the statuses for an update, which may lift `clientUpdateProhibited` itself
+    fn check_update(&self, update: &Element) -> Result<(), ResultCode> {
+        let lifted = update
+            .child("rem")
+            .into_iter()
+            .flat_map(|rem| rem.children("status"))
+            .any(|status| status.attr("s") == Some("clientUpdateProhibited"));
+        match lifted {
+            true => self.check_status(&UPDATE_PROHIBITED[1..]),
+            false => self.check_status(&UPDATE_PROHIBITED),
+        }
+    }
+
+    /// Add the statuses under `add`; clients may only set the `client*` statuses
+    fn add_statuses(&mut self, add: &Element) -> Result<(), ResultCode> {
+        for status in add.children("status") {
+            let status = status
+                .attr("s")
+                .ok_or(ResultCode::RequiredParameterMissing)?;
+            if !status.starts_with("client") {
+                return Err(ResultCode::ParameterValuePolicyError);
+            }
+            if !self.statuses.iter().any(|s| s == status) {
+                self.statuses.push(status.to_owned());
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_statuses(&mut self, rem: &Element) {
+        for status in rem.children("status") {
+            if let Some(status) = status.attr("s") {
+                if status.starts_with("client") {
+                    self.statuses.retain(|s| s != status);
+                }
+            }
+        }
+    }
+
+    fn touch(&mut self, client_id: &str, now: DateTime<Utc>) {
+        self.updater_id = Some(client_id.to_owned());
+        self.updated_at = Some(now);
+    }
+
+    fn write_statuses(&self, prefix: &str, linked: bool, out: &mut String) {
+        if self.statuses.is_empty() {
+            let _ = write!(out, r#"<{prefix}:status s="ok"/>"#);
+        }
+        for status in &self.statuses {
+            let _ = write!(out, r#"<{prefix}:status s="{}"/>"#, escape(status));
+        }
+        if linked {
+            let _ = write!(out, r#"<{prefix}:status s="linked"/>"#);
+        }
+    }
+
+    /// Write the sponsoring, creating and updating clients and dates
+    fn write_clients(&self, prefix: &str, out: &mut String) {
+        tag(out, prefix, "clID", &self.client_id);
+        tag(out, prefix, "crID", &self.creator_id);
+        tag(out, prefix, "crDate", &date(&self.created_at));
+        if let Some(updater_id) = &self.updater_id {
+            tag(out, prefix, "upID", updater_id);
+        }
+        if let Some(updated_at) = &self.updated_at {
+            tag(out, prefix, "upDate", &date(updated_at));
+        }
+    }
+}
+
+const UPDATE_PROHIBITED: [&str; 4] = [
+    "clientUpdateProhibited",
+    "serverUpdateProhibited",
+    "pendingTransfer",
+    "pendingDelete",
+];
+
+const DELETE_PROHIBITED: [&str; 4] = [
+    "clientDeleteProhibited",
+    "serverDeleteProhibited",
+    "pendingTransfer",
+    "pendingDelete",
+];
+
+const RENEW_PROHIBITED: [&str; 4] = [
+    "clientRenewProhibited",
+    "serverRenewProhibited",
+    "pendingTransfer",
+    "pendingDelete",
+];
+
+const TRANSFER_PROHIBITED: [&str; 3] = [
+    "clientTransferProhibited",
+    "serverTransferProhibited",
+    "pendingDelete",
+];
+
+/// The most recent transfer of an object
+#[derive(Clone)]
+struct Transfer {
+    status: &'static str,
+    requester: String,
+    requested_at: DateTime<Utc>,
+    /// The client that sponsored the object when the transfer was requested
+    acting: String,
+    act_by: DateTime<Utc>,
+    /// The expiry date of a domain after the transfer
+    expiring_at: Option<DateTime<Utc>>,
+}
+
+/// Apply a transfer operation to an object
+///
+/// Returns the result code, and the client to notify with the text of its service message.
+#[allow(clippy::too_many_arguments)]
+fn transfer(
+    client_id: &str,
+    op: &str,
+    object: &Element,
+    meta: &mut Meta,
+    auth: &str,
+    transfer: &mut Option<Transfer>,
+    expiring_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<(ResultCode, Option<(String, String)>), ResultCode> {
+    let pending = matches!(transfer, Some(transfer) if transfer.status == "pending");
+    if op == "request" {
+        if meta.client_id == client_id {
+            return Err(ResultCode::ObjectIsNotEligibleForTransfer);
+        }
+        if pending {
+            return Err(ResultCode::ObjectPendingTransfer);
+        }
+        meta.check_status(&TRANSFER_PROHIBITED)?;
+        if auth_info(object) != Some(auth) {
+            return Err(ResultCode::InvalidAuthorizationInformation);
+        }
+
+        *transfer = Some(Transfer {
+            status: "pending",
+            requester: client_id.to_owned(),
+            requested_at: now,
+            acting: meta.client_id.clone(),
+            act_by: now + Duration::days(TRANSFER_PERIOD_DAYS),
+            expiring_at,
+        });
+        meta.statuses.push("pendingTransfer".to_owned());
+        let notify = (meta.client_id.clone(), "Transfer requested.".to_owned());
+        return Ok((
+            ResultCode::CommandCompletedSuccessfullyActionPending,
+            Some(notify),
+        ));
+    }
+
+    let current = transfer
+        .as_mut()
+        .ok_or(ResultCode::ObjectNotPendingTransfer)?;
+    if op == "query" {
+        let involved = client_id == meta.client_id || client_id == current.requester;
+        if !involved && auth_info(object) != Some(auth) {
+            return Err(ResultCode::AuthorizationError);
+        }
+        return Ok((ResultCode::CommandCompletedSuccessfully, None));
+    }
+
+    if !pending {
+        return Err(ResultCode::ObjectNotPendingTransfer);
+    }
+
+    let (status, recipient, text) = match op {
+        "approve" | "reject" if client_id != meta.client_id => {
+            return Err(ResultCode::AuthorizationError)
+        }
+        "approve" => ("clientApproved", &current.requester, "Transfer approved."),
+        "reject" => ("clientRejected", &current.requester, "Transfer rejected."),
+        "cancel" if client_id != current.requester => return Err(ResultCode::AuthorizationError),
+        "cancel" => ("clientCancelled", &current.acting, "Transfer cancelled."),
+        _ => return Err(ResultCode::ParameterValueSyntaxError),
+    };
+
+    let recipient = recipient.clone();
+    current.status = status;
+    current.act_by = now;
+    meta.statuses.retain(|s| s != "pendingTransfer");
+    if status == "clientApproved" {
+        meta.client_id = current.requester.clone();
+        meta.transferred_at = Some(now);
+    }
+
+    Ok((
+        ResultCode::CommandCompletedSuccessfully,
+        Some((recipient, text.to_owned())),
+    ))
+}
+
+fn transfer_data(prefix: &str, xmlns: &str, key: &str, id: &str, transfer: &Transfer) -> String {
+    let mut data = open_data("trnData", prefix, xmlns);
+    tag(&mut data, prefix, key, id);
+    tag(&mut data, prefix, "trStatus", transfer.status);
+    tag(&mut data, prefix, "reID", &transfer.requester);
+    tag(&mut data, prefix, "reDate", &date(&transfer.requested_at));
+    tag(&mut data, prefix, "acID", &transfer.acting);
+    tag(&mut data, prefix, "acDate", &date(&transfer.act_by));
+    if let Some(expiring_at) = &transfer.expiring_at {
+        tag(&mut data, prefix, "exDate", &date(expiring_at));
+    }
+    let _ = write!(data, "</{prefix}:trnData>");
+    data
+}
+
+struct Message {
+    id: u64,
+    date: DateTime<Utc>,
+    text: String,
+    data: Option<String>,
+}
+
+/// Answer a check command, using `exists` to look up the objects
+fn check(
+    xmlns: &str,
+    prefix: &str,
+    key: &str,
+    object: &Element,
+    exists: impl Fn(&str) -> bool,
+) -> Reply {
+    let mut data = open_data("chkData", prefix, xmlns);
+    for id in object.children(key) {
+        let id = id.text.trim();
+        let _ = write!(
+            data,
+            r#"<{prefix}:cd><{prefix}:{key} avail="{}">{}</{prefix}:{key}></{prefix}:cd>"#,
+            !exists(id) as u8,
+            escape(id)
+        );
+    }
+    let _ = write!(data, "</{prefix}:chkData>");
+    Reply::data(data)
+}
+
+/// Whether `client_id` may see the authorization information of an object
+///
+/// Other clients may only see it when they send the correct authorization information, and
+/// are refused when they send incorrect authorization information.
+fn authorize(
+    client_id: &str,
+    object: &Element,
+    meta: &Meta,
+    auth: &str,
+) -> Result<bool, ResultCode> {
+    if client_id == meta.client_id {
+        return Ok(true);
+    }
+
+    match auth_info(object) {
+        Some(given) if given == auth => Ok(true),
+        Some(_) => Err(ResultCode::InvalidAuthorizationInformation),
+        None => Ok(false),
+    }
+}
+
+fn sponsor(client_id: &str, meta: &Meta) -> Result<(), ResultCode> {
+    match client_id == meta.client_id {
+        true => Ok(()),
+        false => Err(ResultCode::AuthorizationError),
+    }
+}
+
+fn required<'a>(element: &'a Element, name: &str) -> Result<&'a str, ResultCode> {
+    match element.child_text(name) {
+        Some(text) if !text.is_empty() => Ok(text),
+        _ => Err(ResultCode::RequiredParameterMissing),
+    }
+}
+
+fn auth_info(element: &Element) -> Option<&str> {
+    element.child("authInfo")?.child_text("pw")
+}
+
+/// The registration period in months, which defaults to one year
+fn period(object: &Element) -> Result<u32, ResultCode> {
+    let period = match object.child("period") {
+        Some(period) => period,
+        None => return Ok(12),
+    };
+
+    let value = period
+        .text
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| ResultCode::ParameterValueSyntaxError)?;
+    if !(1..=99).contains(&value) {
+        return Err(ResultCode::ParameterValueRangeError);
+    }
+
+    match period.attr("unit") {
+        Some("y") => Ok(value * 12),
+        Some("m") => Ok(value),
+        _ => Err(ResultCode::ParameterValueSyntaxError),
+    }
+}
+
+fn contacts(element: &Element) -> Vec<(String, String)> {
+    element
+        .children("contact")
+        .map(|contact| {
+            let ty = contact.attr("type").unwrap_or_default().to_owned();
+            (ty, contact.text.trim().to_owned())
+        })
+        .collect()
+}
+
+/// The host names under `<ns>`; host attributes are not supported
+fn name_servers(ns: Option<&Element>) -> Result<Vec<String>, ResultCode> {
+    let ns = match ns {
+        Some(ns) => ns,
+        None => return Ok(Vec::new()),
+    };
+
+    if ns.child("hostAttr").is_some() {
+        return Err(ResultCode::UnimplementedOption);
+    }
+
+    Ok(ns
+        .children("hostObj")
+        .map(|host| host.text.trim().to_owned())
+        .collect())
+}
+
+fn addresses(element: &Element) -> Result<Vec<IpAddr>, ResultCode> {
+    element
+        .children("addr")
+        .map(|addr| {
+            let parsed = addr
+                .text
+                .trim()
+                .parse::<IpAddr>()
+                .map_err(|_| ResultCode::ParameterValueSyntaxError)?;
+            match (addr.attr("ip").unwrap_or("v4"), parsed) {
+                ("v4", IpAddr::V4(_)) | ("v6", IpAddr::V6(_)) => Ok(parsed),
+                _ => Err(ResultCode::ParameterValueSyntaxError),
+            }
+        })
+        .collect()
+}
+
+fn open_data(name: &str, prefix: &str, xmlns: &str) -> String {
+    format!(r#"<{prefix}:{name} xmlns:{prefix}="{xmlns}">"#)
+}
+
+fn tag(out: &mut String, prefix: &str, name: &str, value: &str) {
+    let _ = write!(out, "<{prefix}:{name}>{}</{prefix}:{name}>", escape(value));
+}
+
+fn date(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::dom::Element;
+    use super::Store;
+    use crate::response::ResultCode;
+    use crate::{contact, domain};
+
+    fn execute(store: &mut Store, client_id: &str, command: &str) -> Result<String, ResultCode> {
+        let command = Element::parse(command).unwrap();
+        store
+            .execute(client_id, &command)
+            .map(|reply| reply.res_data.unwrap_or_default())
+    }
+
+    fn create_contact(store: &mut Store, client_id: &str, id: &str) {
+        let command = format!(
+            r#"<create><contact:create xmlns:contact="{}"><contact:id>{id}</contact:id><contact:postalInfo type="int"><contact:name>John Doe</contact:name></contact:postalInfo><contact:email>john@eppdev.net</contact:email><contact:authInfo><contact:pw>contact-pw</contact:pw></contact:authInfo></contact:create></create>"#,
+            contact::XMLNS
+        );
+        execute(store, client_id, &command).unwrap();
+    }
+
+    fn domain_command(name: &str, op: Option<&str>, body: &str) -> String {
+        let op = op.map(|op| format!(r#" op="{op}""#)).unwrap_or_default();
+        format!(
+            r#"<{name}{op}><domain:{name} xmlns:domain="{}"><domain:name>eppdev.com</domain:name>{body}</domain:{name}></{name}>"#,
+            domain::XMLNS
+        )
+    }
+
+    fn create_domain(store: &mut Store, client_id: &str) -> Result<String, ResultCode> {
+        let body = "<domain:registrant>eppdev-contact</domain:registrant><domain:authInfo><domain:pw>domain-pw</domain:pw></domain:authInfo>";
+        execute(store, client_id, &domain_command("create", None, body))
+    }
+
+    fn update_status(op: &str, status: &str) -> String {
+        let body = format!(r#"<domain:{op}><domain:status s="{status}"/></domain:{op}>"#);
+        domain_command("update", None, &body)
+    }
+
+    #[test]
+    fn login() {
+        let mut store = Store::default();
+        let login = |id: &str, pw: &str, new_pw: &str| {
+            Element::parse(&format!(
+                "<login><clID>{id}</clID><pw>{pw}</pw>{new_pw}</login>"
+            ))
+            .unwrap()
+        };
+
+        // Any login is accepted until accounts are added
+        assert_eq!(store.login(&login("anyone", "pw", "")).unwrap(), "anyone");
+
+        store.add_account("registrar".into(), "secret".into());
+        assert_eq!(
+            store.login(&login("anyone", "pw", "")),
+            Err(ResultCode::AuthenticationError)
+        );
+        assert_eq!(
+            store.login(&login("registrar", "", "")),
+            Err(ResultCode::RequiredParameterMissing)
+        );
+
+        let change = "<newPW>changed</newPW>";
+        store.login(&login("registrar", "secret", change)).unwrap();
+        assert_eq!(
+            store.login(&login("registrar", "secret", "")),
+            Err(ResultCode::AuthenticationError)
+        );
+        store.login(&login("registrar", "changed", "")).unwrap();
+    }
+
+    #[test]
+    fn malformed_commands() {
+        let mut store = Store::default();
+        let missing_name = format!(
+            r#"<info><domain:info xmlns:domain="{}"/></info>"#,
+            domain::XMLNS
+        );
+
+        for (command, code) in [
+            ("<check/>".to_owned(), ResultCode::CommandSyntaxError),
+            (missing_name, ResultCode::RequiredParameterMissing),
+            (
+                domain_command("transfer", None, ""),
+                ResultCode::RequiredParameterMissing,
+            ),
+            (
+                domain_command("renew", None, "<domain:curExpDate>soon</domain:curExpDate>"),
+                ResultCode::ParameterValueSyntaxError,
+            ),
+            (
+                domain_command(
+                    "create",
+                    None,
+                    r#"<domain:period unit="y">100</domain:period>"#,
+                ),
+                ResultCode::ParameterValueRangeError,
+            ),
+            (
+                r#"<create><other:create xmlns:other="urn:other"/></create>"#.to_owned(),
+                ResultCode::UnimplementedObjectService,
+            ),
+        ] {
+            assert_eq!(
+                execute(&mut store, "registrar", &command),
+                Err(code),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn domain_lifecycle() {
+        let mut store = Store::default();
+        assert_eq!(
+            create_domain(&mut store, "registrar"),
+            Err(ResultCode::ObjectDoesNotExist)
+        );
+
+        create_contact(&mut store, "registrar", "eppdev-contact");
+        create_domain(&mut store, "registrar").unwrap();
+        assert_eq!(
+            create_domain(&mut store, "registrar"),
+            Err(ResultCode::ObjectExists)
+        );
+
+        // The contact is in use by the domain
+        let delete_contact = format!(
+            r#"<delete><contact:delete xmlns:contact="{}"><contact:id>eppdev-contact</contact:id></contact:delete></delete>"#,
+            contact::XMLNS
+        );
+        assert_eq!(
+            execute(&mut store, "registrar", &delete_contact),
+            Err(ResultCode::ObjectAssociationProhibitsOperation)
+        );
+
+        // Only client statuses can be set, and they are escaped when echoed back
+        assert_eq!(
+            execute(&mut store, "registrar", &update_status("add", "serverHold")),
+            Err(ResultCode::ParameterValuePolicyError)
+        );
+        let odd = "client&quot;&lt;odd&gt;";
+        execute(&mut store, "registrar", &update_status("add", odd)).unwrap();
+        let info = execute(&mut store, "registrar", &domain_command("info", None, "")).unwrap();
+        assert!(info.contains(r#"s="client&quot;&lt;odd&gt;""#), "{info}");
+        Element::parse(&info).unwrap();
+
+        let add = update_status("add", "clientDeleteProhibited");
+        execute(&mut store, "registrar", &add).unwrap();
+        let delete = domain_command("delete", None, "");
+        assert_eq!(
+            execute(&mut store, "registrar", &delete),
+            Err(ResultCode::ObjectStatusProhibitsOperation)
+        );
+        assert_eq!(
+            execute(&mut store, "other", &delete),
+            Err(ResultCode::AuthorizationError)
+        );
+
+        let rem = update_status("rem", "clientDeleteProhibited");
+        execute(&mut store, "registrar", &rem).unwrap();
+        execute(&mut store, "registrar", &delete).unwrap();
+        assert_eq!(
+            execute(&mut store, "registrar", &domain_command("info", None, "")),
+            Err(ResultCode::ObjectDoesNotExist)
+        );
+        execute(&mut store, "registrar", &delete_contact).unwrap();
+    }
+
+    #[test]
+    fn domain_transfer() {
+        let mut store = Store::default();
+        create_contact(&mut store, "losing", "eppdev-contact");
+        create_domain(&mut store, "losing").unwrap();
+
+        let auth =
+            |pw: &str| format!("<domain:authInfo><domain:pw>{pw}</domain:pw></domain:authInfo>");
+        let request = |pw: &str| domain_command("transfer", Some("request"), &auth(pw));
+        let approve = domain_command("transfer", Some("approve"), "");
+        let query = domain_command("transfer", Some("query"), "");
+
+        assert_eq!(
+            execute(&mut store, "gaining", &query),
+            Err(ResultCode::ObjectNotPendingTransfer)
+        );
+        assert_eq!(
+            execute(&mut store, "losing", &request("domain-pw")),
+            Err(ResultCode::ObjectIsNotEligibleForTransfer)
+        );
+        assert_eq!(
+            execute(&mut store, "gaining", &request("wrong")),
+            Err(ResultCode::InvalidAuthorizationInformation)
+        );
+
+        let data = execute(&mut store, "gaining", &request("domain-pw")).unwrap();
+        assert!(data.contains("<domain:trStatus>pending</domain:trStatus>"));
+        assert_eq!(
+            execute(&mut store, "gaining", &request("domain-pw")),
+            Err(ResultCode::ObjectPendingTransfer)
+        );
+        assert_eq!(
+            execute(&mut store, "losing", &update_status("add", "clientHold")),
+            Err(ResultCode::ObjectStatusProhibitsOperation)
+        );
+        assert_eq!(
+            execute(&mut store, "gaining", &approve),
+            Err(ResultCode::AuthorizationError)
+        );
+        assert_eq!(
+            execute(&mut store, "third", &query),
+            Err(ResultCode::AuthorizationError)
+        );
+
+        // The losing registrar is told about the request
+        let poll = Element::parse(r#"<poll op="req"/>"#).unwrap();
+        let reply = store.poll("losing", &poll).unwrap();
+        assert_eq!(
+            reply.code,
+            ResultCode::CommandCompletedSuccessfullyAckToDequeue
+        );
+
+        let data = execute(&mut store, "losing", &approve).unwrap();
+        assert!(data.contains("<domain:trStatus>clientApproved</domain:trStatus>"));
+        assert_eq!(
+            execute(&mut store, "losing", &approve),
+            Err(ResultCode::ObjectNotPendingTransfer)
+        );
+
+        // The domain now belongs to the gaining registrar
+        assert_eq!(
+            execute(&mut store, "losing", &update_status("add", "clientHold")),
+            Err(ResultCode::AuthorizationError)
+        );
+        execute(&mut store, "gaining", &update_status("add", "clientHold")).unwrap();
+        let reply = store.poll("gaining", &poll).unwrap();
+        assert_eq!(
+            reply.code,
+            ResultCode::CommandCompletedSuccessfullyAckToDequeue
+        );
+    }
+
+    #[test]
+    fn poll_ack() {
+        let mut store = Store::default();
+        store.queue_message("registrar", "Hello".into());
+
+        let ack = |id: &str| Element::parse(&format!(r#"<poll op="ack" msgID="{id}"/>"#)).unwrap();
+        assert_eq!(
+            store.poll("registrar", &ack("42")).map(|_| ()),
+            Err(ResultCode::ObjectDoesNotExist)
+        );
+        assert_eq!(
+            store
+                .poll("registrar", &Element::parse("<poll/>").unwrap())
+                .map(|_| ()),
+            Err(ResultCode::RequiredParameterMissing)
+        );
+
+        store.poll("registrar", &ack("1")).unwrap();
+        let reply = store
+            .poll("registrar", &Element::parse(r#"<poll op="req"/>"#).unwrap())
+            .unwrap();
+        assert_eq!(
+            reply.code,
+            ResultCode::CommandCompletedSuccessfullyNoMessages
+        );
+    }
+}
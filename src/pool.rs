@@ -0,0 +1,445 @@
+//! A pool of logged-in EPP sessions that can be shared between tasks
+//!
+//! An [`EppClient`] only has one request in flight at a time. The [`EppPool`] keeps up to
+//! `max_sessions` clients connected and logged in to the same registry, and hands them out
+//! to concurrent tasks as [`PooledSession`]s. Each of them is an [`EppSession`], which logs
+//! in again after its connection has been replaced, and retries commands according to the
+//! pool's [`RetryPolicy`].
+
+use std::collections::VecDeque;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::client::{EppClient, RequestData};
+use crate::connection::Connector;
+use crate::error::Error;
+use crate::extensions::login_sec::{self, LoginSec, PASSWORD_PLACEHOLDER};
+use crate::hello::Greeting;
+use crate::login::Login;
+use crate::redact::Redactor;
+use crate::request::{Command, Extension, Transaction};
+use crate::response::Response;
+use crate::session::{EppSession, RetryPolicy};
+
+/// Settings for an [`EppPool`]
+#[derive(Clone, Debug)]
+pub struct PoolConfig {
+    /// Name of the registry, used in internal logging and transaction ids
+    pub registry: String,
+    /// The client id to log in with
+    pub username: String,
+    /// The password to log in with
+    pub password: String,
+    /// The extension URIs to announce on login, if the registry offers them
+    pub ext_uris: Vec<String>,
+    /// Send the password in the login security extension, which registries require for
+    /// passwords longer than 16 characters
+    pub login_sec: bool,
+    /// The maximum number of open sessions, which should not exceed the registry's limit
+    pub max_sessions: usize,
+    /// Limits the time spent on any underlying network operation
+    pub timeout: Duration,
+    /// Sessions that have been idle for longer than this are checked with a hello before use
+    pub health_check_interval: Duration,
+    /// How sessions retry commands with [`PooledSession::transact()`]
+    pub retry_policy: RetryPolicy,
+    /// Hides secrets in the requests and responses that are logged
+    pub redactor: Redactor,
+}
+
+impl PoolConfig {
+    /// Create a `PoolConfig` for a single session with a 5 second timeout
+    pub fn new(registry: String, username: String, password: String) -> Self {
+        Self {
+            registry,
+            username,
+            password,
+            ext_uris: Vec::new(),
+            login_sec: false,
+            max_sessions: 1,
+            timeout: Duration::from_secs(5),
+            health_check_interval: Duration::from_secs(60),
+            retry_policy: RetryPolicy::default(),
+            redactor: Redactor::new(),
+        }
+    }
+}
+
+/// A pool of logged-in EPP sessions to a single registry
+///
+/// Sessions are created lazily, using a clone of the `connector` for each of them. Cloning
+/// the pool is cheap and yields a handle to the same sessions.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use epp_client::client::RustlsConnector;
+/// use epp_client::domain::DomainCheck;
+/// use epp_client::pool::{EppPool, PoolConfig};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let connector = RustlsConnector::new(("example.com".to_owned(), 700), None).await.unwrap();
+/// let mut config = PoolConfig::new("registry_name".into(), "username".into(), "password".into());
+/// config.max_sessions = 4;
+/// let pool = EppPool::new(connector, config).unwrap();
+///
+/// let mut session = pool.get().await.unwrap();
+/// let domain_check = DomainCheck { domains: &["eppdev.com", "eppdev.net"] };
+/// let response = session.transact(&domain_check, "transaction-id").await.unwrap();
+/// # }
+/// ```
+pub struct EppPool<C: Connector + Clone> {
+    inner: Arc<PoolInner<C>>,
+}
+
+impl<C: Connector + Clone> EppPool<C> {
+    /// Create a pool; no connections are made until sessions are requested
+    ///
+    /// Fails if `max_sessions` is 0.
+    pub fn new(connector: C, config: PoolConfig) -> Result<Self, Error> {
+        if config.max_sessions == 0 {
+            return Err(Error::Other("max_sessions must be at least 1".into()));
+        }
+
+        Ok(Self {
+            inner: Arc::new(PoolInner {
+                slots: Arc::new(Semaphore::new(config.max_sessions)),
+                freed: Arc::new(Notify::new()),
+                connector,
+                config,
+                idle: Mutex::new(VecDeque::new()),
+                counter: AtomicU64::new(0),
+            }),
+        })
+    }
+
+    /// Get a logged-in session, waiting for one to become available if `max_sessions` are
+    /// open and all of them are in use
+    ///
+    /// Idle sessions are reused when possible. A session that failed in a way that left the
+    /// connection unusable is reconnected and logged in again before it is handed out.
+    pub async fn get(&self) -> Result<PooledSession<C>, Error> {
+        loop {
+            // Created before looking, so that a session returned in the meantime is not missed
+            let freed = self.inner.freed.notified();
+
+            let idle = self.inner.idle.lock().unwrap().pop_front();
+            if let Some(IdleSession {
+                mut session,
+                since,
+                broken,
+                slot,
+            }) = idle
+            {
+                self.inner.revive(&mut session, since, broken).await?;
+                return Ok(PooledSession::new(session, slot, self.inner.clone()));
+            }
+
+            if let Ok(permit) = self.inner.slots.clone().try_acquire_owned() {
+                let slot = Slot::new(permit, self.inner.freed.clone());
+                let session = self.inner.connect().await?;
+                return Ok(PooledSession::new(session, slot, self.inner.clone()));
+            }
+
+            freed.await;
+        }
+    }
+
+    /// Check all idle sessions with a hello, dropping the ones that do not respond
+    ///
+    /// Returns the number of idle sessions that are still alive.
+    pub async fn health_check(&self) -> usize {
+        let idle = std::mem::take(&mut *self.inner.idle.lock().unwrap());
+        let mut alive = VecDeque::with_capacity(idle.len());
+        for mut idle in idle {
+            if idle.broken {
+                continue;
+            }
+
+            match idle.session.client_mut().hello().await {
+                Ok(_) => {
+                    idle.since = Instant::now();
+                    alive.push_back(idle);
+                }
+                Err(e) => warn!(
+                    "{}: dropping idle session: {}",
+                    self.inner.config.registry, e
+                ),
+            }
+        }
+
+        let count = alive.len();
+        self.inner.idle.lock().unwrap().extend(alive);
+        for _ in 0..count {
+            self.inner.freed.notify_one();
+        }
+        count
+    }
+
+    /// Log out and shut down all idle sessions
+    pub async fn close(&self) -> Result<(), Error> {
+        let idle = std::mem::take(&mut *self.inner.idle.lock().unwrap());
+        for idle in idle {
+            if idle.broken {
+                continue;
+            }
+
+            let id = self.inner.transaction_id("logout");
+            idle.session.shutdown(&id).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: Connector + Clone> Clone for EppPool<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+struct PoolInner<C: Connector + Clone> {
+    connector: C,
+    config: PoolConfig,
+    /// One permit for every open session, whether it is in use or idle
+    slots: Arc<Semaphore>,
+    /// Wakes up a waiting `get()` when a session is returned or closed
+    freed: Arc<Notify>,
+    idle: Mutex<VecDeque<IdleSession<C>>>,
+    counter: AtomicU64,
+}
+
+impl<C: Connector + Clone> PoolInner<C> {
+    async fn connect(&self) -> Result<EppSession<C>, Error> {
+        info!("{}: opening pooled session", self.config.registry);
+        let mut client = EppClient::new(
+            self.connector.clone(),
+            self.config.registry.clone(),
+            self.config.timeout,
+        )
+        .await?;
+        client.set_redactor(self.config.redactor.clone());
+
+        let config = &self.config;
+        let greeting = client.greeting()?;
+        let mut ext_uris = config
+            .ext_uris
+            .iter()
+            .map(|uri| uri.as_str())
+            .collect::<Vec<_>>();
+
+        let policy = config.retry_policy;
+        match config.login_sec {
+            true => {
+                ext_uris.push(login_sec::XMLNS);
+                let login = Login::from_greeting(
+                    &greeting,
+                    &config.username,
+                    PASSWORD_PLACEHOLDER,
+                    None,
+                    &ext_uris,
+                );
+                let login_sec = LoginSec::new(None, Some(&config.password), None);
+                EppSession::with_login_sec(client, login, login_sec, policy).await
+            }
+            false => {
+                let login = Login::from_greeting(
+                    &greeting,
+                    &config.username,
+                    &config.password,
+                    None,
+                    &ext_uris,
+                );
+                EppSession::new(client, login, policy).await
+            }
+        }
+    }
+
+    /// Make an idle session ready for use, reconnecting and logging in again if needed
+    async fn revive(
+        &self,
+        session: &mut EppSession<C>,
+        since: Instant,
+        mut broken: bool,
+    ) -> Result<(), Error> {
+        if !broken && since.elapsed() >= self.config.health_check_interval {
+            debug!("{}: checking idle session", self.config.registry);
+            broken = session.client_mut().hello().await.is_err();
+        }
+
+        if broken {
+            info!("{}: reconnecting pooled session", self.config.registry);
+            session.reconnect().await?;
+        }
+
+        Ok(())
+    }
+
+    fn transaction_id(&self, command: &str) -> String {
+        let n = self.counter.fetch_add(1, Ordering::Relaxed);
+        format!("{}:{}:{}", self.config.registry, command, n)
+    }
+}
+
+struct IdleSession<C: Connector> {
+    session: EppSession<C>,
+    since: Instant,
+    broken: bool,
+    slot: Slot,
+}
+
+/// The permit of an open session, which lets a waiting `get()` open another one once dropped
+struct Slot {
+    permit: Option<OwnedSemaphorePermit>,
+    freed: Arc<Notify>,
+}
+
+impl Slot {
+    fn new(permit: OwnedSemaphorePermit, freed: Arc<Notify>) -> Self {
+        Self {
+            permit: Some(permit),
+            freed,
+        }
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        // Release the permit before waking anyone up, so that they can take it
+        drop(self.permit.take());
+        self.freed.notify_one();
+    }
+}
+
+/// A logged-in session checked out from an [`EppPool`]
+///
+/// The session is returned to the pool when dropped. If a request failed in a way that
+/// leaves the connection unusable, it is reconnected the next time it is checked out.
+pub struct PooledSession<C: Connector + Clone> {
+    session: Option<EppSession<C>>,
+    slot: Option<Slot>,
+    broken: bool,
+    pool: Arc<PoolInner<C>>,
+}
+
+impl<C: Connector + Clone> PooledSession<C> {
+    fn new(session: EppSession<C>, slot: Slot, pool: Arc<PoolInner<C>>) -> Self {
+        Self {
+            session: Some(session),
+            slot: Some(slot),
+            broken: false,
+            pool,
+        }
+    }
+
+    /// Executes an EPP Hello call and returns the response as a `Greeting`
+    pub async fn hello(&mut self) -> Result<Greeting, Error> {
+        let result = self.client_mut().hello().await;
+        self.track(result)
+    }
+
+    /// Executes an EPP command on this session, see [`EppSession::transact()`]
+    ///
+    /// The command is retried according to the pool's `retry_policy`. If an earlier request
+    /// on this session left the connection unusable, it is reconnected and logged in again
+    /// first.
+    pub async fn transact<'c, 'e, Cmd, Ext>(
+        &mut self,
+        data: impl Into<RequestData<'c, 'e, Cmd, Ext>>,
+        id: &str,
+    ) -> Result<Response<Cmd::Response, Ext::Response>, Error>
+    where
+        Cmd: Transaction<Ext> + Command + 'c,
+        Ext: Extension + 'e,
+    {
+        if std::mem::take(&mut self.broken) {
+            info!("{}: reconnecting pooled session", self.pool.config.registry);
+            let result = self.session.as_mut().unwrap().reconnect().await;
+            self.track(result)?;
+        }
+
+        let result = self.session.as_mut().unwrap().transact(data, id).await;
+        self.track(result)
+    }
+
+    /// Executes an EPP command on this session with a timeout, see
+    /// [`EppClient::transact_with_timeout()`]
+    ///
+    /// Unlike [`PooledSession::transact()`], the command is sent once, without logging in
+    /// again or retrying.
+    pub async fn transact_with_timeout<'c, 'e, Cmd, Ext>(
+        &mut self,
+        data: impl Into<RequestData<'c, 'e, Cmd, Ext>>,
+        id: &str,
+        timeout: Duration,
+    ) -> Result<Response<Cmd::Response, Ext::Response>, Error>
+    where
+        Cmd: Transaction<Ext> + Command + 'c,
+        Ext: Extension + 'e,
+    {
+        let result = self
+            .client_mut()
+            .transact_with_timeout(data, id, timeout)
+            .await;
+        self.track(result)
+    }
+
+    /// Accepts raw EPP XML and returns the raw EPP XML response to it
+    pub async fn transact_xml(&mut self, xml: &str) -> Result<String, Error> {
+        let result = self.client_mut().transact_xml(xml).await;
+        self.track(result)
+    }
+
+    fn client_mut(&mut self) -> &mut EppClient<C> {
+        self.session.as_mut().unwrap().client_mut()
+    }
+
+    fn track<T>(&mut self, result: Result<T, Error>) -> Result<T, Error> {
+        if let Err(e) = &result {
+            self.broken |= is_fatal(e);
+        }
+        result
+    }
+}
+
+impl<C: Connector + Clone> Deref for PooledSession<C> {
+    type Target = EppClient<C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.session.as_ref().unwrap().client()
+    }
+}
+
+impl<C: Connector + Clone> Drop for PooledSession<C> {
+    fn drop(&mut self) {
+        if let (Some(session), Some(slot)) = (self.session.take(), self.slot.take()) {
+            self.pool.idle.lock().unwrap().push_back(IdleSession {
+                session,
+                since: Instant::now(),
+                broken: self.broken,
+                slot,
+            });
+            self.pool.freed.notify_one();
+        }
+    }
+}
+
+/// Whether the error means the connection can no longer be used
+fn is_fatal(err: &Error) -> bool {
+    match err {
+        Error::Io(_) | Error::Timeout => true,
+        Error::Command(status) => status.result.code.closes_connection(),
+        Error::Xml(_) | Error::Other(_) => false,
+    }
+}
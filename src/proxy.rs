@@ -0,0 +1,257 @@
+//! Connecting to registries through an outbound proxy
+//!
+//! The [`ProxyConnector`] sets up a TCP tunnel through an HTTP CONNECT or SOCKS5 proxy, and
+//! hands it to a [`StreamConnector`] such as the `RustlsConnector` for the TLS handshake.
+
+use std::io;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64ct::{Base64, Encoding};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::{debug, info};
+
+use crate::connection::{self, Connector, StreamConnector};
+use crate::error::Error;
+
+/// The protocol spoken by a [`Proxy`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProxyKind {
+    /// An HTTP proxy supporting the CONNECT method
+    Http,
+    /// A SOCKS5 proxy, as described in [RFC 1928](https://tools.ietf.org/html/rfc1928)
+    Socks5,
+}
+
+/// An outbound proxy to tunnel connections through
+#[derive(Clone)]
+pub struct Proxy {
+    kind: ProxyKind,
+    server: (String, u16),
+    auth: Option<(String, String)>,
+}
+
+impl Proxy {
+    /// An HTTP CONNECT proxy listening on `server`
+    pub fn http(server: (String, u16)) -> Self {
+        Self {
+            kind: ProxyKind::Http,
+            server,
+            auth: None,
+        }
+    }
+
+    /// A SOCKS5 proxy listening on `server`
+    pub fn socks5(server: (String, u16)) -> Self {
+        Self {
+            kind: ProxyKind::Socks5,
+            server,
+            auth: None,
+        }
+    }
+
+    /// Authenticate to the proxy with a username and password
+    ///
+    /// This uses basic authentication for HTTP proxies, and username/password authentication
+    /// as described in [RFC 1929](https://tools.ietf.org/html/rfc1929) for SOCKS5 proxies.
+    pub fn auth(mut self, username: String, password: String) -> Self {
+        self.auth = Some((username, password));
+        self
+    }
+
+    /// The protocol spoken by the proxy
+    pub fn kind(&self) -> ProxyKind {
+        self.kind
+    }
+
+    /// Open a TCP stream to `target` through the proxy
+    pub async fn connect(&self, target: &(String, u16)) -> Result<TcpStream, Error> {
+        info!(
+            "Connecting to server: {}:{} through proxy {}:{}",
+            target.0, target.1, self.server.0, self.server.1
+        );
+
+        let mut stream = TcpStream::connect((self.server.0.as_str(), self.server.1)).await?;
+        match self.kind {
+            ProxyKind::Http => self.http_connect(&mut stream, target).await?,
+            ProxyKind::Socks5 => self.socks5_connect(&mut stream, target).await?,
+        }
+
+        debug!("proxy tunnel to {}:{} established", target.0, target.1);
+        Ok(stream)
+    }
+
+    async fn http_connect(
+        &self,
+        stream: &mut TcpStream,
+        target: &(String, u16),
+    ) -> Result<(), Error> {
+        let authority = match target.0.parse::<IpAddr>() {
+            Ok(IpAddr::V6(_)) => format!("[{}]:{}", target.0, target.1),
+            _ => format!("{}:{}", target.0, target.1),
+        };
+
+        let mut request = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n");
+        if let Some((username, password)) = &self.auth {
+            let credentials = Base64::encode_string(format!("{username}:{password}").as_bytes());
+            request.push_str(&format!("Proxy-Authorization: Basic {credentials}\r\n"));
+        }
+        request.push_str("\r\n");
+        stream.write_all(request.as_bytes()).await?;
+
+        // Read byte by byte, so that nothing after the header is consumed
+        let mut header = Vec::with_capacity(128);
+        while !header.ends_with(b"\r\n\r\n") {
+            if header.len() >= 8192 {
+                return Err(proxy_error("HTTP proxy response header too long".into()));
+            }
+            header.push(stream.read_u8().await?);
+        }
+
+        let header = String::from_utf8_lossy(&header);
+        let status = header.lines().next().unwrap_or_default();
+        match status.split_whitespace().nth(1) {
+            Some(code) if code.starts_with('2') => Ok(()),
+            _ => Err(proxy_error(format!("HTTP proxy refused CONNECT: {status}"))),
+        }
+    }
+
+    async fn socks5_connect(
+        &self,
+        stream: &mut TcpStream,
+        target: &(String, u16),
+    ) -> Result<(), Error> {
+        let methods: &[u8] = match self.auth {
+            Some(_) => &[SOCKS_NO_AUTH, SOCKS_PASSWORD_AUTH],
+            None => &[SOCKS_NO_AUTH],
+        };
+
+        let mut greeting = vec![SOCKS_VERSION, methods.len() as u8];
+        greeting.extend_from_slice(methods);
+        stream.write_all(&greeting).await?;
+
+        let mut choice = [0; 2];
+        stream.read_exact(&mut choice).await?;
+        match (choice, &self.auth) {
+            ([SOCKS_VERSION, SOCKS_NO_AUTH], _) => {}
+            ([SOCKS_VERSION, SOCKS_PASSWORD_AUTH], Some((username, password))) => {
+                let mut auth = vec![1];
+                for value in [username, password] {
+                    let len = u8::try_from(value.len())
+                        .map_err(|_| proxy_error("SOCKS5 credentials too long".into()))?;
+                    auth.push(len);
+                    auth.extend_from_slice(value.as_bytes());
+                }
+                stream.write_all(&auth).await?;
+
+                let mut status = [0; 2];
+                stream.read_exact(&mut status).await?;
+                if status[1] != 0 {
+                    return Err(proxy_error("SOCKS5 proxy authentication failed".into()));
+                }
+            }
+            _ => {
+                return Err(proxy_error(
+                    "SOCKS5 proxy offered no acceptable authentication method".into(),
+                ))
+            }
+        }
+
+        let mut request = vec![SOCKS_VERSION, 1, 0];
+        match target.0.parse::<IpAddr>() {
+            Ok(IpAddr::V4(addr)) => {
+                request.push(1);
+                request.extend_from_slice(&addr.octets());
+            }
+            Ok(IpAddr::V6(addr)) => {
+                request.push(4);
+                request.extend_from_slice(&addr.octets());
+            }
+            Err(_) => {
+                let len = u8::try_from(target.0.len())
+                    .map_err(|_| proxy_error(format!("host name too long: {}", target.0)))?;
+                request.push(3);
+                request.push(len);
+                request.extend_from_slice(target.0.as_bytes());
+            }
+        }
+        request.extend_from_slice(&target.1.to_be_bytes());
+        stream.write_all(&request).await?;
+
+        let mut reply = [0; 4];
+        stream.read_exact(&mut reply).await?;
+        if reply[1] != 0 {
+            return Err(proxy_error(format!(
+                "SOCKS5 proxy refused connection with reply code {}",
+                reply[1]
+            )));
+        }
+
+        // Skip the bound address and port
+        let len = match reply[3] {
+            1 => 4,
+            3 => stream.read_u8().await? as usize,
+            4 => 16,
+            ty => return Err(proxy_error(format!("invalid SOCKS5 address type {ty}"))),
+        };
+        let mut bound = vec![0; len + 2];
+        stream.read_exact(&mut bound).await?;
+        Ok(())
+    }
+}
+
+const SOCKS_VERSION: u8 = 5;
+const SOCKS_NO_AUTH: u8 = 0;
+const SOCKS_PASSWORD_AUTH: u8 = 2;
+
+fn proxy_error(msg: String) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::Other, msg))
+}
+
+/// A [`Connector`] that tunnels the connection of another connector through a [`Proxy`]
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use epp_client::client::RustlsConnector;
+/// use epp_client::proxy::{Proxy, ProxyConnector};
+/// use epp_client::EppClient;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let tls = RustlsConnector::new(("example.com".to_owned(), 700), None).await.unwrap();
+/// let proxy = Proxy::http(("proxy.internal".to_owned(), 3128))
+///     .auth("username".to_owned(), "password".to_owned());
+/// let connector = ProxyConnector::new(proxy, tls);
+///
+/// let timeout = Duration::from_secs(5);
+/// let client = EppClient::new(connector, "registry_name".to_owned(), timeout).await.unwrap();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ProxyConnector<C> {
+    proxy: Proxy,
+    inner: C,
+}
+
+impl<C: StreamConnector> ProxyConnector<C> {
+    /// Tunnel the connections of `inner` through `proxy`
+    pub fn new(proxy: Proxy, inner: C) -> Self {
+        Self { proxy, inner }
+    }
+}
+
+#[async_trait]
+impl<C: StreamConnector + Send + Sync> Connector for ProxyConnector<C> {
+    type Connection = C::Connection;
+
+    async fn connect(&self, timeout: Duration) -> Result<Self::Connection, Error> {
+        let future = self.proxy.connect(self.inner.server());
+        let stream = connection::timeout(timeout, future).await?;
+        self.inner.connect_stream(stream, timeout).await
+    }
+}
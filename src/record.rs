@@ -0,0 +1,549 @@
+//! Recording EPP sessions and replaying them in tests
+//!
+//! The [`RecordingConnector`] wraps another [`Connector`] and keeps a copy of every frame
+//! exchanged with the registry. The resulting [`Recording`] can be saved to a file, and later
+//! be served by a [`ReplayConnector`], which checks that the client sends the same requests.
+//!
+//! Frames are passed through masks before they are recorded and compared. The default
+//! [`Redactor`] always comes first, so that passwords are not written to disk, and more masks
+//! can be added so that values that differ between runs, such as client transaction ids, do
+//! not cause mismatches.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use epp_client::client::RustlsConnector;
+//! use epp_client::record::{Recording, RecordingConnector, ReplayConnector};
+//! use epp_client::redact::Redactor;
+//! use epp_client::EppClient;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! // Record a session against the registry
+//! let tls = RustlsConnector::new(("example.com".to_owned(), 700), None).await.unwrap();
+//! let recorder = RecordingConnector::new(tls);
+//! let timeout = Duration::from_secs(5);
+//! let client = EppClient::new(recorder.clone(), "registry_name".into(), timeout).await.unwrap();
+//! // ...
+//! recorder.recording().save("session.epp").unwrap();
+//!
+//! // Replay it later
+//! let recording = Recording::load("session.epp").unwrap();
+//! let replay = ReplayConnector::new(recording).mask(Redactor::client_transaction_ids());
+//! let client = EppClient::new(replay, "registry_name".into(), timeout).await.unwrap();
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tracing::debug;
+
+use crate::connection::Connector;
+use crate::error::Error;
+use crate::redact::Redactor;
+
+/// Whether a frame was sent to or received from the registry
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    /// A request sent by the client
+    Sent,
+    /// A greeting or response received from the registry
+    Received,
+}
+
+/// A single EPP frame, without the length header
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    /// Whether the frame was sent or received
+    pub direction: Direction,
+    /// The XML document in the frame
+    pub xml: String,
+}
+
+/// The frames exchanged over a series of connections
+///
+/// In a file, every connection starts with a `session` line. Each frame follows on a line
+/// with `>` for sent or `<` for received frames and the length of the XML in bytes, then
+/// the XML itself and a line break.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Recording {
+    /// The frames of every connection, in the order the connections were made
+    pub sessions: Vec<Vec<Frame>>,
+}
+
+impl Recording {
+    /// Read a recording from a file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Write the recording to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Parse a recording in the file format
+    pub fn parse(mut input: &str) -> Result<Self, Error> {
+        let mut sessions = Vec::new();
+        while !input.is_empty() {
+            let (line, rest) = input.split_once('\n').unwrap_or((input, ""));
+            input = rest;
+
+            let (direction, len) = match line.split_once(' ') {
+                None if line == "session" => {
+                    sessions.push(Vec::new());
+                    continue;
+                }
+                Some((">", len)) => (Direction::Sent, len),
+                Some(("<", len)) => (Direction::Received, len),
+                _ => return Err(invalid_recording(format!("unexpected line: {line}"))),
+            };
+
+            let session = sessions
+                .last_mut()
+                .ok_or_else(|| invalid_recording("frame outside of a session".into()))?;
+            let len = len
+                .parse::<usize>()
+                .map_err(|_| invalid_recording(format!("invalid frame length: {len}")))?;
+            let xml = input
+                .get(..len)
+                .ok_or_else(|| invalid_recording("truncated frame".into()))?;
+            input = input[len..].strip_prefix('\n').unwrap_or(&input[len..]);
+
+            session.push(Frame {
+                direction,
+                xml: xml.to_owned(),
+            });
+        }
+
+        Ok(Self { sessions })
+    }
+}
+
+impl fmt::Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for session in &self.sessions {
+            writeln!(f, "session")?;
+            for frame in session {
+                let marker = match frame.direction {
+                    Direction::Sent => '>',
+                    Direction::Received => '<',
+                };
+                writeln!(f, "{} {}", marker, frame.xml.len())?;
+                writeln!(f, "{}", frame.xml)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid_recording(msg: String) -> Error {
+    Error::Other(format!("invalid recording: {msg}").into())
+}
+
+/// Rewrites frames before they are recorded or compared
+///
+/// This is implemented for [`Redactor`], and for closures taking and returning the XML of a
+/// frame.
+pub trait Mask: Send + Sync {
+    fn mask(&self, xml: &str) -> String;
+}
+
+impl<F: Fn(&str) -> String + Send + Sync> Mask for F {
+    fn mask(&self, xml: &str) -> String {
+        self(xml)
+    }
+}
+
+fn default_masks() -> Vec<Arc<dyn Mask>> {
+    vec![Arc::new(Redactor::new())]
+}
+
+fn apply(masks: &[Arc<dyn Mask>], xml: &str) -> String {
+    masks
+        .iter()
+        .fold(xml.to_owned(), |xml, mask| mask.mask(&xml))
+}
+
+/// Collects the frames in a byte stream as they come in
+#[derive(Default)]
+struct Frames {
+    buf: Vec<u8>,
+}
+
+impl Frames {
+    /// Add bytes from the stream, returning the frames that are now complete
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buf.extend_from_slice(bytes);
+        let mut frames = Vec::new();
+        while self.buf.len() >= 4 {
+            let len = u32::from_be_bytes(self.buf[..4].try_into().unwrap()) as usize;
+            let len = len.max(4);
+            if self.buf.len() < len {
+                break;
+            }
+
+            frames.push(String::from_utf8_lossy(&self.buf[4..len]).into_owned());
+            self.buf.drain(..len);
+        }
+        frames
+    }
+}
+
+/// A [`Connector`] that records the frames exchanged over the connections of another connector
+///
+/// Cloning the connector is cheap and yields a handle to the same recording.
+#[derive(Clone)]
+pub struct RecordingConnector<C> {
+    inner: C,
+    recording: Arc<Mutex<Recording>>,
+    masks: Vec<Arc<dyn Mask>>,
+}
+
+impl<C: Connector> RecordingConnector<C> {
+    /// Record the connections made by `inner`, hiding passwords with the default [`Redactor`]
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            recording: Arc::default(),
+            masks: default_masks(),
+        }
+    }
+
+    /// Also pass all frames through `mask` before recording them
+    ///
+    /// Received frames are masked too, so the mask should leave them in a form that the client
+    /// can still parse when they are replayed.
+    pub fn mask(mut self, mask: impl Mask + 'static) -> Self {
+        self.masks.push(Arc::new(mask));
+        self
+    }
+
+    /// The frames recorded so far
+    pub fn recording(&self) -> Recording {
+        self.recording.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl<C: Connector + Send + Sync> Connector for RecordingConnector<C> {
+    type Connection = RecordingStream<C::Connection>;
+
+    async fn connect(&self, timeout: Duration) -> Result<Self::Connection, Error> {
+        let inner = self.inner.connect(timeout).await?;
+        let mut recording = self.recording.lock().unwrap();
+        recording.sessions.push(Vec::new());
+        Ok(RecordingStream {
+            inner,
+            session: recording.sessions.len() - 1,
+            recording: self.recording.clone(),
+            masks: self.masks.clone(),
+            sent: Frames::default(),
+            received: Frames::default(),
+        })
+    }
+}
+
+/// A connection made by a [`RecordingConnector`]
+pub struct RecordingStream<S> {
+    inner: S,
+    session: usize,
+    recording: Arc<Mutex<Recording>>,
+    masks: Vec<Arc<dyn Mask>>,
+    sent: Frames,
+    received: Frames,
+}
+
+impl<S> RecordingStream<S> {
+    fn record(&self, direction: Direction, frames: Vec<String>) {
+        if frames.is_empty() {
+            return;
+        }
+
+        let mut recording = self.recording.lock().unwrap();
+        let session = &mut recording.sessions[self.session];
+        for xml in frames {
+            session.push(Frame {
+                direction,
+                xml: apply(&self.masks, &xml),
+            });
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for RecordingStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let before = buf.filled().len();
+        let result = Pin::new(&mut this.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            let frames = this.received.push(&buf.filled()[before..]);
+            this.record(Direction::Received, frames);
+        }
+        result
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for RecordingStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let result = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = result {
+            let frames = this.sent.push(&buf[..written]);
+            this.record(Direction::Sent, frames);
+        }
+        result
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+/// A [`Connector`] that plays the registry's side of a [`Recording`]
+///
+/// Every connection replays the next recorded session. The recorded responses are served as
+/// long as the client sends the recorded requests; a request that does not match the
+/// recording, after masking both, fails with an `InvalidData` I/O error.
+pub struct ReplayConnector {
+    sessions: Mutex<VecDeque<Vec<Frame>>>,
+    masks: Vec<Arc<dyn Mask>>,
+}
+
+impl ReplayConnector {
+    /// Replay the sessions in `recording`, comparing requests with passwords hidden by the
+    /// default [`Redactor`], as they were recorded
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            sessions: Mutex::new(recording.sessions.into()),
+            masks: default_masks(),
+        }
+    }
+
+    /// Also pass recorded and actual requests through `mask` before comparing them
+    pub fn mask(mut self, mask: impl Mask + 'static) -> Self {
+        self.masks.push(Arc::new(mask));
+        self
+    }
+
+    /// The number of recorded sessions that have not been replayed yet
+    pub fn remaining_sessions(&self) -> usize {
+        self.sessions.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl Connector for ReplayConnector {
+    type Connection = ReplayStream;
+
+    async fn connect(&self, _: Duration) -> Result<Self::Connection, Error> {
+        let frames = self
+            .sessions
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| Error::Other("no more recorded sessions to replay".into()))?;
+
+        debug!("replaying session of {} frames", frames.len());
+        Ok(ReplayStream {
+            frames: frames.into(),
+            masks: self.masks.clone(),
+            sent: Frames::default(),
+            sent_count: 0,
+            pending: Vec::new(),
+            read_waker: None,
+        })
+    }
+}
+
+/// A connection made by a [`ReplayConnector`]
+pub struct ReplayStream {
+    frames: VecDeque<Frame>,
+    masks: Vec<Arc<dyn Mask>>,
+    sent: Frames,
+    sent_count: usize,
+    /// Bytes of received frames that have not been read yet
+    pending: Vec<u8>,
+    read_waker: Option<Waker>,
+}
+
+impl ReplayStream {
+    /// The frames that have not been replayed yet
+    pub fn remaining(&self) -> impl Iterator<Item = &Frame> {
+        self.frames.iter()
+    }
+
+    fn check(&mut self, xml: &str) -> io::Result<()> {
+        self.sent_count += 1;
+        let expected = match self.frames.pop_front() {
+            Some(frame) if frame.direction == Direction::Sent => frame.xml,
+            Some(frame) => {
+                self.frames.push_front(frame);
+                return Err(mismatch(format!(
+                    "request {} was sent while the recording expects a response",
+                    self.sent_count
+                )));
+            }
+            None => {
+                return Err(mismatch(format!(
+                    "request {} was sent after the end of the recording",
+                    self.sent_count
+                )))
+            }
+        };
+
+        let expected = apply(&self.masks, &expected);
+        let actual = apply(&self.masks, xml);
+        if expected != actual {
+            return Err(mismatch(format!(
+                "request {} does not match the recording\nexpected: {}\n  actual: {}",
+                self.sent_count, expected, actual
+            )));
+        }
+
+        if let Some(waker) = self.read_waker.take() {
+            waker.wake();
+        }
+        Ok(())
+    }
+}
+
+fn mismatch(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl AsyncRead for ReplayStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.pending.is_empty() {
+            match this.frames.front() {
+                Some(frame) if frame.direction == Direction::Received => {
+                    let frame = this.frames.pop_front().unwrap();
+                    let len = (frame.xml.len() + 4) as u32;
+                    this.pending.extend_from_slice(&len.to_be_bytes());
+                    this.pending.extend_from_slice(frame.xml.as_bytes());
+                }
+                // Wait for the client to send the next request
+                Some(_) => {
+                    this.read_waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+                // The end of the recording closes the connection
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+
+        let len = this.pending.len().min(buf.remaining());
+        buf.put_slice(&this.pending[..len]);
+        this.pending.drain(..len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for ReplayStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        for xml in this.sent.push(buf) {
+            this.check(&xml)?;
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{apply, default_masks, Direction, Frame, Mask, Recording};
+    use crate::redact::Redactor;
+
+    #[test]
+    fn file_format() {
+        let recording = Recording {
+            sessions: vec![
+                vec![
+                    Frame {
+                        direction: Direction::Received,
+                        xml: "<epp>\n<greeting/>\n</epp>".into(),
+                    },
+                    Frame {
+                        direction: Direction::Sent,
+                        xml: "<epp><hello/></epp>".into(),
+                    },
+                ],
+                vec![],
+            ],
+        };
+
+        let serialized = recording.to_string();
+        assert_eq!(
+            serialized,
+            "session\n< 24\n<epp>\n<greeting/>\n</epp>\n> 19\n<epp><hello/></epp>\nsession\n"
+        );
+        assert_eq!(Recording::parse(&serialized).unwrap(), recording);
+        assert!(Recording::parse("> 10\n<epp/>").is_err());
+        assert!(Recording::parse("session\n> 10\n<epp/>").is_err());
+    }
+
+    #[test]
+    fn masks() {
+        let xml = r#"<?xml version="1.0"?><epp><command><login><clID>user</clID><pw>secret</pw><newPW/></login><clTRID>abc-1</clTRID></command></epp>"#;
+        assert_eq!(
+            apply(&default_masks(), xml),
+            r#"<?xml version="1.0"?><epp><command><login><clID>user</clID><pw>****</pw><newPW/></login><clTRID>abc-1</clTRID></command></epp>"#
+        );
+
+        let mut masks = default_masks();
+        masks.push(Arc::new(
+            Redactor::client_transaction_ids().replacement("x"),
+        ));
+        assert_eq!(
+            apply(&masks, xml),
+            r#"<?xml version="1.0"?><epp><command><login><clID>user</clID><pw>****</pw><newPW/></login><clTRID>x</clTRID></command></epp>"#
+        );
+
+        let upper = |xml: &str| xml.to_uppercase();
+        assert_eq!(upper.mask("<epp/>"), "<EPP/>");
+    }
+}
@@ -0,0 +1,340 @@
+//! Redacting secrets from XML before it is logged
+//!
+//! The [`EppClient`](crate::EppClient) logs requests and responses at debug level. Before they
+//! are logged, they pass through a [`Redactor`], which by default hides passwords and
+//! authorization information. Registry-specific secrets can be added as paths:
+//!
+//! ```
+//! use epp_client::redact::Redactor;
+//!
+//! let redactor = Redactor::new().path("extension/kv/@value");
+//! let xml = r#"<epp><extension><kv key="token" value="secret"/></extension></epp>"#;
+//! assert_eq!(
+//!     redactor.redact(xml),
+//!     r#"<epp><extension><kv key="token" value="****"/></extension></epp>"#
+//! );
+//! ```
+//!
+//! A `Redactor` is also a [`Mask`], and the recorders in [`record`](crate::record) use the
+//! default one unless told otherwise, so passwords are not written to recordings either.
+
+use std::fmt;
+
+use quick_xml::escape::escape;
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+
+use crate::record::Mask;
+
+/// Replaces the content of selected elements and attributes
+///
+/// Paths are local element names separated by `/`, ignoring namespace prefixes, and match
+/// elements whose enclosing elements end with the given names. A path starting with `/` only
+/// matches from the root element, `*` matches any element, and a last step like `@name`
+/// selects an attribute instead of the element content.
+#[derive(Clone, Debug)]
+pub struct Redactor {
+    paths: Vec<RedactPath>,
+    replacement: String,
+}
+
+impl Redactor {
+    /// A redactor for passwords, including the ones in `<authInfo>`
+    pub fn new() -> Self {
+        Self::empty().path("pw").path("newPW").path("authInfo")
+    }
+
+    /// A redactor for client transaction IDs, which differ between runs of the same session
+    pub fn client_transaction_ids() -> Self {
+        Self::empty().path("clTRID")
+    }
+
+    /// A redactor for dates and timestamps, such as the greeting's `<svDate>` and object dates
+    ///
+    /// Redacted timestamps can no longer be parsed, so this is meant for comparing requests
+    /// rather than for responses that are replayed.
+    pub fn timestamps() -> Self {
+        [
+            "svDate",
+            "qDate",
+            "crDate",
+            "upDate",
+            "exDate",
+            "trDate",
+            "reDate",
+            "acDate",
+            "paDate",
+            "curExpDate",
+            "delTime",
+            "resTime",
+            "changeData/date",
+        ]
+        .iter()
+        .fold(Self::empty(), |redactor, path| redactor.path(path))
+    }
+
+    /// A redactor that leaves everything as it is, until paths are added
+    pub fn empty() -> Self {
+        Self {
+            paths: Vec::new(),
+            replacement: "****".into(),
+        }
+    }
+
+    /// Also redact the elements or attributes at `path`
+    pub fn path(mut self, path: &str) -> Self {
+        self.paths.push(RedactPath::parse(path));
+        self
+    }
+
+    /// Use `replacement` instead of the redacted content
+    pub fn replacement(mut self, replacement: &str) -> Self {
+        self.replacement = replacement.to_owned();
+        self
+    }
+
+    /// Returns `xml` with the selected content replaced
+    ///
+    /// XML that cannot be parsed is replaced as a whole, as it may hold secrets that the
+    /// paths would have matched.
+    pub fn redact(&self, xml: &str) -> String {
+        match self.paths.is_empty() {
+            true => xml.to_owned(),
+            false => self
+                .try_redact(xml)
+                .unwrap_or_else(|_| format!("<!-- {} bytes of malformed XML -->", xml.len())),
+        }
+    }
+
+    /// Wraps `xml` to be redacted only when it is formatted, for use in log messages
+    pub(crate) fn display<'a>(&'a self, xml: &'a str) -> impl fmt::Display + 'a {
+        Redacted {
+            redactor: self,
+            xml,
+        }
+    }
+
+    fn try_redact(&self, xml: &str) -> Result<String, quick_xml::Error> {
+        let mut reader = Reader::from_str(xml);
+        let mut writer = Writer::new(Vec::with_capacity(xml.len()));
+        let mut stack = Vec::new();
+        loop {
+            match reader.read_event()? {
+                Event::Eof => break,
+                Event::Start(start) => {
+                    stack.push(String::from_utf8_lossy(start.local_name().as_ref()).into_owned());
+                    let start = self.redact_attributes(start, &stack)?;
+                    if !self.paths.iter().any(|path| path.matches_element(&stack)) {
+                        writer.write_event(Event::Start(start))?;
+                        continue;
+                    }
+
+                    reader.read_to_end(start.name())?;
+                    let name = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+                    writer.write_event(Event::Start(start))?;
+                    writer.write_event(Event::Text(BytesText::new(&self.replacement)))?;
+                    writer.write_event(Event::End(BytesEnd::new(name)))?;
+                    stack.pop();
+                }
+                Event::Empty(start) => {
+                    stack.push(String::from_utf8_lossy(start.local_name().as_ref()).into_owned());
+                    let start = self.redact_attributes(start, &stack)?;
+                    writer.write_event(Event::Empty(start))?;
+                    stack.pop();
+                }
+                Event::End(end) => {
+                    stack.pop();
+                    writer.write_event(Event::End(end))?;
+                }
+                event => writer.write_event(event)?,
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&writer.into_inner()).into_owned())
+    }
+
+    /// Replaces the values of the attributes selected for the element at the top of `stack`
+    fn redact_attributes<'a>(
+        &self,
+        start: BytesStart<'a>,
+        stack: &[String],
+    ) -> Result<BytesStart<'a>, quick_xml::Error> {
+        let names = self
+            .paths
+            .iter()
+            .filter_map(|path| path.matches_attribute(stack))
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            return Ok(start);
+        }
+
+        let replacement = escape(&self.replacement);
+        let mut redacted = start.to_owned();
+        redacted.clear_attributes();
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(quick_xml::Error::from)?;
+            let local_name = attribute.key.local_name();
+            match names
+                .iter()
+                .any(|&name| name.as_bytes() == local_name.as_ref())
+            {
+                true => redacted.push_attribute(Attribute {
+                    key: attribute.key,
+                    value: replacement.as_bytes().into(),
+                }),
+                false => redacted.push_attribute(attribute),
+            }
+        }
+
+        Ok(redacted)
+    }
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mask for Redactor {
+    fn mask(&self, xml: &str) -> String {
+        self.redact(xml)
+    }
+}
+
+struct Redacted<'a> {
+    redactor: &'a Redactor,
+    xml: &'a str,
+}
+
+impl fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.redactor.redact(self.xml))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct RedactPath {
+    anchored: bool,
+    elements: Vec<String>,
+    attribute: Option<String>,
+}
+
+impl RedactPath {
+    fn parse(path: &str) -> Self {
+        let anchored = path.starts_with('/') && !path.starts_with("//");
+        let mut elements = path
+            .split('/')
+            .filter(|step| !step.is_empty())
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        let attribute = match elements.last() {
+            Some(step) if step.starts_with('@') => elements.pop().map(|step| step[1..].to_owned()),
+            _ => None,
+        };
+
+        Self {
+            anchored,
+            elements,
+            attribute,
+        }
+    }
+
+    fn matches_element(&self, stack: &[String]) -> bool {
+        self.attribute.is_none() && self.matches(stack)
+    }
+
+    /// Returns the name of the selected attribute, if this selects one on the element
+    fn matches_attribute(&self, stack: &[String]) -> Option<&str> {
+        let attribute = self.attribute.as_deref()?;
+        self.matches(stack).then(|| attribute)
+    }
+
+    fn matches(&self, stack: &[String]) -> bool {
+        if stack.len() < self.elements.len()
+            || (self.anchored && stack.len() != self.elements.len())
+        {
+            return false;
+        }
+
+        let tail = &stack[stack.len() - self.elements.len()..];
+        self.elements
+            .iter()
+            .zip(tail)
+            .all(|(step, name)| step == "*" || step == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Redactor;
+    use crate::tests::get_xml;
+
+    #[test]
+    fn passwords() {
+        let redacted = Redactor::new().redact(&get_xml("request/login.xml").unwrap());
+        assert!(redacted.contains("<pw>****</pw>"));
+        assert!(redacted.contains("<newPW>****</newPW>"));
+        assert!(redacted.contains("<clID>username</clID>"));
+        assert!(!redacted.contains("password"));
+
+        let xml = get_xml("request/domain/create.xml").unwrap();
+        let redacted = Redactor::new().replacement("x").redact(&xml);
+        assert!(redacted.contains("<domain:authInfo>x</domain:authInfo>"));
+        assert!(!redacted.contains("epP4uthd#v"));
+    }
+
+    #[test]
+    fn paths() {
+        let xml = concat!(
+            r#"<epp><command><create><secret>a</secret><obj><secret>b</secret></obj></create>"#,
+            r#"<extension><kv key="token" value="c"/><kv:kv xmlns:kv="urn:kv" value="d">e</kv:kv></extension>"#,
+            "</command></epp>"
+        );
+
+        let redactor = Redactor::empty().path("/epp/command/create/secret");
+        assert_eq!(
+            redactor.redact(xml),
+            xml.replace("<secret>a</secret>", "<secret>****</secret>")
+        );
+
+        let redactor = Redactor::empty().path("create/*/secret");
+        assert_eq!(
+            redactor.redact(xml),
+            xml.replace("<secret>b</secret>", "<secret>****</secret>")
+        );
+
+        let redactor = Redactor::empty().path("extension/kv/@value");
+        assert_eq!(
+            redactor.redact(xml),
+            xml.replace(r#"value="c""#, r#"value="****""#)
+                .replace(r#"value="d""#, r#"value="****""#)
+        );
+
+        assert_eq!(Redactor::empty().redact(xml), xml);
+        assert_eq!(
+            Redactor::client_transaction_ids().redact("<epp><clTRID>abc-1</clTRID></epp>"),
+            "<epp><clTRID>****</clTRID></epp>"
+        );
+        assert_eq!(
+            Redactor::new().redact("<epp><pw>a</epp>"),
+            "<!-- 16 bytes of malformed XML -->"
+        );
+    }
+
+    #[test]
+    fn timestamps() {
+        let xml = get_xml("response/message/poll_domain_pending_action.xml").unwrap();
+        let redacted = Redactor::timestamps().redact(&xml);
+        assert!(redacted.contains("<qDate>****</qDate>"));
+        assert!(redacted.contains("<domain:paDate>****</domain:paDate>"));
+        assert!(!redacted.contains("1999-04-04"));
+
+        let xml = get_xml("response/message/poll_domain_info_change.xml").unwrap();
+        let redacted = Redactor::timestamps().redact(&xml);
+        assert!(redacted.contains("<changePoll:date>****</changePoll:date>"));
+        assert!(redacted.contains("<domain:name>"));
+    }
+}
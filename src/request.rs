@@ -4,6 +4,7 @@ use serde::{de::DeserializeOwned, ser::SerializeStruct, ser::Serializer, Seriali
 use std::fmt::Debug;
 
 use crate::common::{StringValue, EPP_XMLNS};
+use crate::Error;
 
 pub const EPP_VERSION: &str = "1.0";
 pub const EPP_LANG: &str = "en";
@@ -14,10 +15,33 @@ pub trait Transaction<Ext: Extension>: Command + Sized {}
 pub trait Command: Serialize + Debug {
     type Response: DeserializeOwned + Debug;
     const COMMAND: &'static str;
+
+    /// The extension URIs this command negotiates for the rest of the session
+    ///
+    /// Only [`Login`](crate::login::Login) negotiates extensions; the client uses them to
+    /// check the extensions of later commands.
+    fn negotiated_extensions(&self) -> Option<Vec<&str>> {
+        None
+    }
 }
 
 pub trait Extension: Serialize + Debug {
     type Response: DeserializeOwned + Debug;
+
+    /// The XML namespaces used by this extension, which must have been negotiated at login
+    ///
+    /// An empty list, the default, skips the check.
+    fn namespaces(&self) -> Vec<&str> {
+        Vec::new()
+    }
+}
+
+/// Checks a required value when building an owned command
+pub(crate) fn required(field: &str, value: &str) -> Result<(), Error> {
+    match value.is_empty() {
+        true => Err(Error::Other(format!("{field} must not be empty").into())),
+        false => Ok(()),
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -1,11 +1,13 @@
 //! Types for EPP responses
 
 use std::fmt::{self, Debug};
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 use crate::common::StringValue;
+use crate::redact::Redactor;
 
 /// Type corresponding to the <undef> tag an EPP response XML
 #[derive(Deserialize, Debug, Eq, PartialEq)]
@@ -134,6 +136,17 @@ impl ResultCode {
                 | CommandCompletedSuccessfullyEndingSession
         )
     }
+
+    /// Whether the server closes the connection after sending this result
+    pub fn closes_connection(&self) -> bool {
+        use ResultCode::*;
+        matches!(
+            self,
+            CommandFailedServerClosingConnection
+                | AuthenticationErrorServerClosingConnection
+                | SessionLimitExceededServerClosingConnection
+        )
+    }
 }
 
 impl<'de> Deserialize<'de> for ResultCode {
@@ -233,6 +246,55 @@ pub struct ResponseStatus {
     #[serde(rename = "trID")]
     /// Data under the <trID> tag
     pub tr_ids: ResponseTRID,
+    /// The XML exchanged with the registry, for a status returned in `Error::Command`
+    #[serde(skip)]
+    pub exchange: Option<Box<Exchange>>,
+}
+
+/// The XML exchanged with the registry for a single command
+///
+/// Returned by [`EppClient::transact_with_exchange()`](crate::EppClient::transact_with_exchange),
+/// and kept in the [`ResponseStatus`] of failed commands. As errors are easily logged with
+/// `{:?}`, the `Debug` output leaves out the XML and only gives its length.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Exchange {
+    /// The request, as sent to the registry
+    pub request: String,
+    /// The response, as received from the registry
+    pub response: String,
+    /// When the request was sent
+    pub sent_at: DateTime<Utc>,
+    /// How long it took for the response to arrive
+    pub elapsed: Duration,
+    /// The client transaction ID sent with the request
+    pub client_tr_id: String,
+    /// The server transaction ID from the response
+    pub server_tr_id: String,
+}
+
+impl Exchange {
+    /// Returns a copy with the request and response passed through `redactor`, for keeping
+    /// the exchange without the passwords it holds
+    pub fn redacted(&self, redactor: &Redactor) -> Self {
+        Self {
+            request: redactor.redact(&self.request),
+            response: redactor.redact(&self.response),
+            ..self.clone()
+        }
+    }
+}
+
+impl Debug for Exchange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Exchange")
+            .field("request", &format_args!("{} bytes", self.request.len()))
+            .field("response", &format_args!("{} bytes", self.response.len()))
+            .field("sent_at", &self.sent_at)
+            .field("elapsed", &self.elapsed)
+            .field("client_tr_id", &self.client_tr_id)
+            .field("server_tr_id", &self.server_tr_id)
+            .finish()
+    }
 }
 
 impl<T, E> Response<T, E> {
@@ -254,7 +316,12 @@ impl<T, E> Response<T, E> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ResultCode, ResultDocument};
+    use std::time::Duration;
+
+    use chrono::Utc;
+
+    use super::{Exchange, ResultCode, ResultDocument};
+    use crate::redact::Redactor;
     use crate::tests::{get_xml, CLTRID, SVTRID};
     use crate::xml;
 
@@ -272,4 +339,28 @@ mod tests {
         assert_eq!(object.data.tr_ids.client_tr_id.unwrap(), CLTRID.into());
         assert_eq!(object.data.tr_ids.server_tr_id, SVTRID.into());
     }
+
+    #[test]
+    fn exchange_debug() {
+        let exchange = Exchange {
+            request: get_xml("request/login.xml").unwrap(),
+            response: get_xml("response/error.xml").unwrap(),
+            sent_at: Utc::now(),
+            elapsed: Duration::from_millis(20),
+            client_tr_id: CLTRID.into(),
+            server_tr_id: SVTRID.into(),
+        };
+
+        let debug = format!("{exchange:?}");
+        assert!(debug.contains(&format!(r#"request: {} bytes"#, exchange.request.len())));
+        assert!(debug.contains(CLTRID));
+        assert!(!debug.contains("password"));
+        assert!(!debug.contains("<epp"));
+
+        let redacted = exchange.redacted(&Redactor::new());
+        assert!(redacted.request.contains("<pw>****</pw>"));
+        assert!(!redacted.request.contains("password"));
+        assert_eq!(redacted.response, exchange.response);
+        assert_eq!(redacted.client_tr_id, CLTRID);
+    }
 }
@@ -0,0 +1,279 @@
+//! A client session that stays logged in across reconnects
+//!
+//! An [`EppClient`] only manages the connection; after a reconnect the registry expects a new
+//! login. An [`EppSession`] remembers the [`Login`] it was created with, and its
+//! [`LoginSec`] extension if any, and logs in again whenever the connection is replaced.
+
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::client::{EppClient, RequestData};
+use crate::connection::Connector;
+use crate::error::Error;
+use crate::extensions::login_sec::LoginSec;
+use crate::login::Login;
+use crate::logout::Logout;
+use crate::redact::Redactor;
+use crate::request::{Command, Extension, Transaction};
+use crate::response::Response;
+
+/// How an [`EppSession`] retries commands when the registry closes the connection
+///
+/// Applies to the 2500, 2501 and 2502 result codes, after which the session reconnects, logs
+/// in again and resends the command. I/O errors and timeouts are only retried when
+/// `retry_io` is set: the registry may already have executed the command, which would then
+/// run twice.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// How many times a command is retried
+    pub max_retries: u32,
+    /// How long to wait before the first retry; doubled for every following retry
+    pub delay: Duration,
+    /// Whether to also retry after I/O errors and timeouts
+    pub retry_io: bool,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            delay: Duration::ZERO,
+            retry_io: false,
+        }
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        self.delay.saturating_mul(1 << attempt.min(16))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            delay: Duration::from_secs(1),
+            retry_io: false,
+        }
+    }
+}
+
+/// An EPP session that logs in automatically after connecting and reconnecting
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use epp_client::client::RustlsConnector;
+/// use epp_client::domain::DomainCheck;
+/// use epp_client::login::Login;
+/// use epp_client::session::{EppSession, RetryPolicy};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let connector = RustlsConnector::new(("example.com".to_owned(), 700), None).await.unwrap();
+/// let login = Login::new("username", "password", None, None);
+/// let timeout = Duration::from_secs(5);
+/// let mut session = EppSession::connect(connector, "registry_name".into(), timeout, login, RetryPolicy::default())
+///     .await
+///     .unwrap();
+///
+/// let domain_check = DomainCheck { domains: &["eppdev.com", "eppdev.net"] };
+/// let response = session.transact(&domain_check, "transaction-id").await.unwrap();
+/// # }
+/// ```
+pub struct EppSession<C: Connector> {
+    client: EppClient<C>,
+    login: Login<'static>,
+    login_sec: Option<LoginSec<'static>>,
+    authenticated: bool,
+    policy: RetryPolicy,
+    counter: u64,
+}
+
+impl<C: Connector> EppSession<C> {
+    /// Connect with the given `connector` and log in, see [`EppClient::new()`]
+    pub async fn connect(
+        connector: C,
+        registry: String,
+        timeout: Duration,
+        login: Login<'_>,
+        policy: RetryPolicy,
+    ) -> Result<Self, Error> {
+        let client = EppClient::new(connector, registry, timeout).await?;
+        Self::new(client, login, policy).await
+    }
+
+    /// Log in on a connected client
+    ///
+    /// The `login` is kept to log in again after reconnecting. If it changes the password,
+    /// the new password is used for those later logins.
+    pub async fn new(
+        client: EppClient<C>,
+        login: Login<'_>,
+        policy: RetryPolicy,
+    ) -> Result<Self, Error> {
+        Self::start(client, login, None, policy).await
+    }
+
+    /// Log in on a connected client with the login security extension
+    ///
+    /// Both `login` and `login_sec` are kept to log in again after reconnecting, with the new
+    /// password if `login_sec` changes it.
+    pub async fn with_login_sec(
+        client: EppClient<C>,
+        login: Login<'_>,
+        login_sec: LoginSec<'_>,
+        policy: RetryPolicy,
+    ) -> Result<Self, Error> {
+        Self::start(client, login, Some(login_sec), policy).await
+    }
+
+    async fn start(
+        client: EppClient<C>,
+        login: Login<'_>,
+        login_sec: Option<LoginSec<'_>>,
+        policy: RetryPolicy,
+    ) -> Result<Self, Error> {
+        let mut session = Self {
+            client,
+            login: login.into_owned(),
+            login_sec: login_sec.map(LoginSec::into_owned),
+            authenticated: false,
+            policy,
+            counter: 0,
+        };
+
+        session.login().await?;
+        session.login = session.login.into_relogin();
+        session.login_sec = session.login_sec.take().map(LoginSec::into_relogin);
+        Ok(session)
+    }
+
+    /// Whether the session is currently logged in
+    ///
+    /// This is false after the session has been logged out, or after an error that closed
+    /// the connection. The session then reconnects and logs in before the next command.
+    pub fn is_authenticated(&self) -> bool {
+        self.authenticated
+    }
+
+    /// Executes an EPP command, retrying according to the `RetryPolicy` if the registry closes
+    /// the connection
+    pub async fn transact<'c, 'e, Cmd, Ext>(
+        &mut self,
+        data: impl Into<RequestData<'c, 'e, Cmd, Ext>>,
+        id: &str,
+    ) -> Result<Response<Cmd::Response, Ext::Response>, Error>
+    where
+        Cmd: Transaction<Ext> + Command + 'c,
+        Ext: Extension + 'e,
+    {
+        let data = data.into();
+        let mut attempt = 0;
+        loop {
+            let result = match self.authenticated {
+                true => self.client.transact(data, id).await,
+                false => match self.reconnect().await {
+                    Ok(()) => self.client.transact(data, id).await,
+                    Err(e) => Err(e),
+                },
+            };
+
+            let err = match result {
+                Ok(rsp) => return Ok(rsp),
+                Err(err) => err,
+            };
+
+            match &err {
+                Error::Command(status) if status.result.code.closes_connection() => {
+                    self.authenticated = false;
+                }
+                // The next command reconnects, but this one may already have been executed
+                Error::Io(_) | Error::Timeout => {
+                    self.authenticated = false;
+                    if !self.policy.retry_io {
+                        return Err(err);
+                    }
+                }
+                _ => return Err(err),
+            }
+
+            if attempt >= self.policy.max_retries {
+                return Err(err);
+            }
+
+            let delay = self.policy.delay(attempt);
+            warn!(
+                "{}: retrying in {:?} after: {}",
+                self.client.registry(),
+                delay,
+                err
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Reconnects and logs in again
+    pub async fn reconnect(&mut self) -> Result<(), Error> {
+        self.authenticated = false;
+        self.client.reconnect().await?;
+
+        info!("{}: logging in again", self.client.registry());
+        self.login().await
+    }
+
+    async fn login(&mut self) -> Result<(), Error> {
+        let id = format!("{}:login:{}", self.client.registry(), self.counter);
+        self.counter += 1;
+        match &self.login_sec {
+            Some(login_sec) => self
+                .client
+                .transact((&self.login, login_sec), &id)
+                .await
+                .map(|_| ())?,
+            None => self.client.transact(&self.login, &id).await.map(|_| ())?,
+        }
+        self.authenticated = true;
+        Ok(())
+    }
+
+    /// Logs out; the next command will reconnect and log in again
+    pub async fn logout(&mut self, id: &str) -> Result<(), Error> {
+        self.authenticated = false;
+        self.client.transact(&Logout, id).await?;
+        Ok(())
+    }
+
+    /// Keep the session alive with hellos, see [`EppClient::set_keepalive()`]
+    pub fn set_keepalive(&mut self, interval: Duration) {
+        let login_sec = self.login_sec.clone();
+        self.client
+            .set_keepalive_login(interval, self.login.clone(), login_sec);
+    }
+
+    /// Hide secrets in logged requests and responses, see [`EppClient::set_redactor()`]
+    pub fn set_redactor(&mut self, redactor: Redactor) {
+        self.client.set_redactor(redactor);
+    }
+
+    /// The underlying client
+    pub fn client(&self) -> &EppClient<C> {
+        &self.client
+    }
+
+    pub(crate) fn client_mut(&mut self) -> &mut EppClient<C> {
+        &mut self.client
+    }
+
+    /// Logs out and closes the connection
+    pub async fn shutdown(mut self, id: &str) -> Result<(), Error> {
+        if self.authenticated {
+            self.logout(id).await?;
+        }
+        self.client.shutdown().await
+    }
+}
@@ -0,0 +1,484 @@
+use std::time::Duration;
+
+use epp_client::common::{ContactStatus, DomainStatus, ObjectStatus};
+use epp_client::contact::{
+    Address, ContactCreate, ContactDelete, ContactInfo, OwnedContactCreate, Phone, PostalInfo,
+};
+use epp_client::domain::update::DomainAddRemove;
+use epp_client::domain::{
+    DomainCheck, DomainContact, DomainCreate, DomainDelete, DomainInfo, DomainRenew,
+    DomainTransfer, DomainUpdate, HostList, HostObjList, OwnedDomainCreate, OwnedDomainInfo,
+    OwnedDomainUpdate, Period,
+};
+use epp_client::host::{HostCreate, HostDelete, HostInfo};
+use epp_client::login::Login;
+use epp_client::message::consumer::{ConsumerConfig, MessageConsumer, RunError};
+use epp_client::message::poll::MessageData;
+use epp_client::message::{MessageAck, MessagePoll};
+use epp_client::mock::MockRegistry;
+use epp_client::redact::Redactor;
+use epp_client::response::ResultCode;
+use epp_client::{EppClient, Error};
+
+const CLTRID: &str = "cltrid:1626454866";
+
+async fn login(registry: &MockRegistry, username: &str) -> EppClient<MockRegistry> {
+    let timeout = Duration::from_secs(5);
+    let mut client = EppClient::new(registry.clone(), "mock".into(), timeout)
+        .await
+        .unwrap();
+    let login = Login::new(username, "password", None, None);
+    client.transact(&login, CLTRID).await.unwrap();
+    client
+}
+
+fn result_code<T>(result: Result<T, Error>) -> ResultCode {
+    match result {
+        Err(Error::Command(status)) => status.result.code,
+        Err(e) => panic!("unexpected error: {e}"),
+        Ok(_) => panic!("expected the command to fail"),
+    }
+}
+
+#[tokio::test]
+async fn login_required() {
+    let registry = MockRegistry::new();
+    registry.add_account("registrar-a", "password");
+
+    let timeout = Duration::from_secs(5);
+    let mut client = EppClient::new(registry.clone(), "mock".into(), timeout)
+        .await
+        .unwrap();
+    assert_eq!(client.greeting().unwrap().service_id, "Mock EPP Registry");
+
+    let check = DomainCheck {
+        domains: &["eppdev.com"],
+    };
+    let result = client.transact(&check, CLTRID).await;
+    assert_eq!(result_code(result), ResultCode::CommandUseError);
+
+    let login = Login::new("registrar-a", "wrong", None, None);
+    let result = client.transact(&login, CLTRID).await;
+    assert_eq!(result_code(result), ResultCode::AuthenticationError);
+
+    let login = Login::new("registrar-a", "password", None, None);
+    client.transact(&login, CLTRID).await.unwrap();
+    let rsp = client.transact(&check, CLTRID).await.unwrap();
+    assert!(rsp.res_data.unwrap().list[0].available);
+    assert_eq!(rsp.tr_ids.client_tr_id.unwrap(), CLTRID.into());
+}
+
+#[tokio::test]
+async fn objects() {
+    let registry = MockRegistry::new();
+    let mut client = login(&registry, "registrar-a").await;
+
+    let address = Address::new(&["58"], "Paris", "Paris", "392374", "FR".parse().unwrap());
+    let postal_info = PostalInfo::new("int", "John Doe", "Acme Widgets", address);
+    let voice = Phone::new("+33.47237942");
+    let create = ContactCreate::new("contact-1", "john@eppdev.net", postal_info, voice, "pw-1");
+    client.transact(&create, CLTRID).await.unwrap();
+    let result = client.transact(&create, CLTRID).await;
+    assert_eq!(result_code(result), ResultCode::ObjectExists);
+
+    // Hosts outside of the registry's domains take no addresses
+    let addresses = ["29.245.122.14".parse().unwrap()];
+    let create = HostCreate::new("ns1.eppdev.net", Some(&addresses));
+    let result = client.transact(&create, CLTRID).await;
+    assert_eq!(result_code(result), ResultCode::ParameterValuePolicyError);
+    let create = HostCreate::new("ns1.eppdev.net", None);
+    client.transact(&create, CLTRID).await.unwrap();
+
+    let hosts = ["ns1.eppdev.net".into()];
+    let ns = Some(HostList::HostObjList(HostObjList { hosts: &hosts }));
+    let contacts = [DomainContact {
+        id: "contact-1".into(),
+        contact_type: "admin".into(),
+    }];
+    let period = Period::years(2).unwrap();
+    let create = DomainCreate::new(
+        "eppdev.com",
+        period,
+        ns,
+        Some("contact-1"),
+        "pw-2",
+        Some(&contacts),
+    );
+    let rsp = client.transact(&create, CLTRID).await.unwrap();
+    let created = rsp.res_data.unwrap().create_data;
+    let expiring_at = created.expiring_at.unwrap();
+
+    let check = DomainCheck {
+        domains: &["eppdev.com", "eppdev.net"],
+    };
+    let rsp = client.transact(&check, CLTRID).await.unwrap();
+    let list = rsp.res_data.unwrap().list;
+    assert!(!list[0].available);
+    assert!(list[1].available);
+
+    // Glue records for hosts under a domain need addresses
+    let create = HostCreate::new("ns1.eppdev.com", Some(&addresses));
+    client.transact(&create, CLTRID).await.unwrap();
+    let rsp = client
+        .transact(&HostInfo::new("ns1.eppdev.com"), CLTRID)
+        .await
+        .unwrap();
+    assert_eq!(rsp.res_data.unwrap().info_data.addresses, addresses);
+
+    let rsp = client
+        .transact(&DomainInfo::new("eppdev.com", None), CLTRID)
+        .await
+        .unwrap();
+    let info = rsp.res_data.unwrap().info_data;
+    assert_eq!(info.registrant.unwrap(), "contact-1".into());
+    assert_eq!(info.client_id, "registrar-a".into());
+    assert_eq!(info.hosts.unwrap(), &["ns1.eppdev.com".into()]);
+    assert_eq!(info.expiring_at, Some(expiring_at));
+    assert_eq!(info.auth_info.unwrap().password, "pw-2".into());
+
+    let rsp = client
+        .transact(&ContactInfo::new("contact-1", "pw-1"), CLTRID)
+        .await
+        .unwrap();
+    let info = rsp.res_data.unwrap().info_data;
+    assert_eq!(info.postal_info.name, "John Doe".into());
+    assert_eq!(info.statuses[1].status, ContactStatus::Linked);
+
+    let result = client
+        .transact(&ContactDelete::new("contact-1"), CLTRID)
+        .await;
+    assert_eq!(
+        result_code(result),
+        ResultCode::ObjectAssociationProhibitsOperation
+    );
+    let result = client
+        .transact(&HostDelete::new("ns1.eppdev.net"), CLTRID)
+        .await;
+    assert_eq!(
+        result_code(result),
+        ResultCode::ObjectAssociationProhibitsOperation
+    );
+
+    let statuses = [ObjectStatus::new(DomainStatus::ClientDeleteProhibited)];
+    let mut update = DomainUpdate::new("eppdev.com");
+    update.add(DomainAddRemove {
+        ns: None,
+        contacts: None,
+        statuses: Some(&statuses),
+    });
+    client.transact(&update, CLTRID).await.unwrap();
+    let result = client
+        .transact(&DomainDelete::new("eppdev.com"), CLTRID)
+        .await;
+    assert_eq!(
+        result_code(result),
+        ResultCode::ObjectStatusProhibitsOperation
+    );
+
+    let one_year = Period::years(1).unwrap();
+    let renew = DomainRenew::new(
+        "eppdev.com",
+        expiring_at.date_naive().pred_opt().unwrap(),
+        one_year,
+    );
+    let result = client.transact(&renew, CLTRID).await;
+    assert_eq!(result_code(result), ResultCode::ParameterValuePolicyError);
+    let renew = DomainRenew::new("eppdev.com", expiring_at.date_naive(), one_year);
+    let rsp = client.transact(&renew, CLTRID).await.unwrap();
+    assert!(rsp.res_data.unwrap().renew_data.expiring_at.unwrap() > expiring_at);
+
+    let mut update = DomainUpdate::new("eppdev.com");
+    update.remove(DomainAddRemove {
+        ns: None,
+        contacts: None,
+        statuses: Some(&statuses),
+    });
+    client.transact(&update, CLTRID).await.unwrap();
+    client
+        .transact(&DomainDelete::new("eppdev.com"), CLTRID)
+        .await
+        .unwrap();
+
+    // The subordinate host went with the domain, and the contact is no longer linked
+    let result = client
+        .transact(&HostInfo::new("ns1.eppdev.com"), CLTRID)
+        .await;
+    assert_eq!(result_code(result), ResultCode::ObjectDoesNotExist);
+    client
+        .transact(&ContactDelete::new("contact-1"), CLTRID)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn transfer() {
+    let registry = MockRegistry::new();
+    let mut losing = login(&registry, "registrar-a").await;
+    let mut gaining = login(&registry, "registrar-b").await;
+
+    let create = DomainCreate::new(
+        "eppdev.com",
+        Period::years(1).unwrap(),
+        None,
+        None,
+        "pw",
+        None,
+    );
+    losing.transact(&create, CLTRID).await.unwrap();
+
+    let request = DomainTransfer::new("eppdev.com", None, "pw");
+    let result = losing.transact(&request, CLTRID).await;
+    assert_eq!(
+        result_code(result),
+        ResultCode::ObjectIsNotEligibleForTransfer
+    );
+
+    let result = gaining
+        .transact(&DomainTransfer::new("eppdev.com", None, "wrong"), CLTRID)
+        .await;
+    assert_eq!(
+        result_code(result),
+        ResultCode::InvalidAuthorizationInformation
+    );
+    let result = gaining
+        .transact(&DomainInfo::new("eppdev.com", None), CLTRID)
+        .await;
+    assert!(result
+        .unwrap()
+        .res_data
+        .unwrap()
+        .info_data
+        .auth_info
+        .is_none());
+
+    let rsp = gaining.transact(&request, CLTRID).await.unwrap();
+    assert_eq!(
+        rsp.result.code,
+        ResultCode::CommandCompletedSuccessfullyActionPending
+    );
+    let data = rsp.res_data.unwrap().transfer_data;
+    assert_eq!(data.transfer_status, "pending".into());
+    assert_eq!(data.ack_id, "registrar-a".into());
+
+    let result = gaining.transact(&request, CLTRID).await;
+    assert_eq!(result_code(result), ResultCode::ObjectPendingTransfer);
+    let result = gaining
+        .transact(&DomainTransfer::approve("eppdev.com"), CLTRID)
+        .await;
+    assert_eq!(result_code(result), ResultCode::AuthorizationError);
+
+    // The sponsoring registrar is told about the request
+    let rsp = losing
+        .transact(&MessagePoll::default(), CLTRID)
+        .await
+        .unwrap();
+    assert_eq!(
+        rsp.result.code,
+        ResultCode::CommandCompletedSuccessfullyAckToDequeue
+    );
+    let queue = rsp.message_queue.unwrap();
+    assert_eq!(queue.count, 1);
+    match rsp.res_data.unwrap().message_data {
+        MessageData::DomainTransfer(data) => assert_eq!(data.requester_id, "registrar-b".into()),
+        _ => panic!("expected a domain transfer message"),
+    }
+    losing
+        .transact(&MessageAck::new(&queue.id), CLTRID)
+        .await
+        .unwrap();
+    let result = losing.transact(&MessageAck::new(&queue.id), CLTRID).await;
+    assert_eq!(result_code(result), ResultCode::ObjectDoesNotExist);
+    let rsp = losing
+        .transact(&MessagePoll::default(), CLTRID)
+        .await
+        .unwrap();
+    assert_eq!(
+        rsp.result.code,
+        ResultCode::CommandCompletedSuccessfullyNoMessages
+    );
+
+    let rsp = losing
+        .transact(&DomainTransfer::approve("eppdev.com"), CLTRID)
+        .await
+        .unwrap();
+    let data = rsp.res_data.unwrap().transfer_data;
+    assert_eq!(data.transfer_status, "clientApproved".into());
+
+    let rsp = gaining
+        .transact(&MessagePoll::default(), CLTRID)
+        .await
+        .unwrap();
+    assert_eq!(
+        rsp.message_queue.unwrap().message.unwrap(),
+        "Transfer approved.".into()
+    );
+
+    let rsp = gaining
+        .transact(&DomainInfo::new("eppdev.com", None), CLTRID)
+        .await
+        .unwrap();
+    let info = rsp.res_data.unwrap().info_data;
+    assert_eq!(info.client_id, "registrar-b".into());
+    assert!(info.transferred_at.is_some());
+
+    let result = gaining
+        .transact(&DomainTransfer::cancel("eppdev.com"), CLTRID)
+        .await;
+    assert_eq!(result_code(result), ResultCode::ObjectNotPendingTransfer);
+}
+
+#[tokio::test]
+async fn messages() {
+    let registry = MockRegistry::new();
+    registry.queue_message("registrar-a", "Low balance.");
+    let mut client = login(&registry, "registrar-a").await;
+
+    let rsp = client
+        .transact(&MessagePoll::default(), CLTRID)
+        .await
+        .unwrap();
+    let queue = rsp.message_queue.unwrap();
+    assert_eq!(queue.message.unwrap(), "Low balance.".into());
+    assert!(rsp.res_data.is_none());
+}
+
+#[tokio::test]
+async fn exchange() {
+    let registry = MockRegistry::new();
+    let mut client = login(&registry, "registrar-a").await;
+
+    let info = DomainInfo::new("missing.com", None);
+    let status = match client.transact_with_exchange(&info, CLTRID).await {
+        Err(Error::Command(status)) => status,
+        _ => panic!("expected a command error"),
+    };
+    assert_eq!(status.result.code, ResultCode::ObjectDoesNotExist);
+
+    let exchange = status.exchange.as_ref().unwrap();
+    assert!(exchange.request.contains("missing.com</domain:name>"));
+    assert!(exchange.response.contains(r#"<result code="2303">"#));
+    assert_eq!(exchange.client_tr_id, CLTRID);
+    assert_eq!(exchange.server_tr_id, status.tr_ids.server_tr_id.as_ref());
+    assert!(!format!("{:?}", Error::Command(status)).contains("missing.com"));
+
+    let login = Login::new("registrar-a", "s3cret", None, None);
+    let status = match client.transact_with_exchange(&login, CLTRID).await {
+        Err(Error::Command(status)) => status,
+        _ => panic!("expected a command error"),
+    };
+    let exchange = status.exchange.unwrap();
+    assert!(exchange.request.contains("<pw>s3cret</pw>"));
+    let redacted = exchange.redacted(&Redactor::new());
+    assert!(redacted.request.contains("<pw>****</pw>"));
+    assert!(!redacted.request.contains("s3cret"));
+}
+
+#[tokio::test]
+async fn consumer() {
+    let registry = MockRegistry::new();
+    for text in ["First.", "Second.", "Third."] {
+        registry.queue_message("registrar-a", text);
+    }
+    let mut client = login(&registry, "registrar-a").await;
+
+    // A failing handler leaves its message in the queue
+    let mut consumer = MessageConsumer::new(&mut client, ConsumerConfig::batch(10));
+    let result = consumer
+        .run(|message| async move {
+            match message.text.as_deref() {
+                Some("Second.") => Err("handler failed"),
+                _ => Ok(()),
+            }
+        })
+        .await;
+    assert!(matches!(result, Err(RunError::Handler("handler failed"))));
+
+    let mut seen = Vec::new();
+    let mut consumer = MessageConsumer::new(&mut client, ConsumerConfig::batch(1));
+    while let Some(message) = consumer.next().await.unwrap() {
+        seen.push(message.text.clone().unwrap());
+        consumer.ack(&message).await.unwrap();
+    }
+    assert_eq!(seen, ["Second."]);
+
+    let mut consumer = MessageConsumer::new(&mut client, ConsumerConfig::batch(10));
+    let handled = consumer
+        .run(|_| async { Ok::<_, Error>(()) })
+        .await
+        .unwrap();
+    assert_eq!(handled, 1);
+
+    // Without a batch size, the consumer waits for new messages
+    let queue = registry.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        queue.queue_message("registrar-a", "Late.");
+    });
+
+    let config = ConsumerConfig {
+        idle_delay: Duration::from_millis(10),
+        max_idle_delay: Duration::from_millis(20),
+        batch_size: None,
+    };
+    let mut consumer = MessageConsumer::new(&mut client, config);
+    let message = consumer.next().await.unwrap().unwrap();
+    assert_eq!(message.text.as_deref(), Some("Late."));
+    assert_eq!(message.count, 1);
+}
+
+#[tokio::test]
+async fn owned_requests() {
+    let registry = MockRegistry::new();
+    let mut client = login(&registry, "registrar-a").await;
+
+    let address = Address::new(&["58"], "Paris", "Paris", "392374", "FR".parse().unwrap());
+    let create = OwnedContactCreate::builder("contact-1")
+        .email("john@eppdev.net")
+        .postal_info(PostalInfo::new("int", "John Doe", "Acme Widgets", address))
+        .voice(Phone::new("+33.47237942"))
+        .auth_password("pw-1")
+        .build()
+        .unwrap();
+    client.transact(&create, CLTRID).await.unwrap();
+
+    // Requests are built in one task and sent from another
+    let (tx, mut rx) = tokio::sync::mpsc::channel(2);
+    let producer = tokio::spawn(async move {
+        for name in ["eppdev-1.com", "eppdev-2.com"] {
+            let create = OwnedDomainCreate::builder(name)
+                .period(Period::years(1).unwrap())
+                .registrant("contact-1")
+                .contact("admin", "contact-1")
+                .auth_password("pw-2")
+                .build()
+                .unwrap();
+            tx.send(create).await.unwrap();
+        }
+    });
+
+    let consumer = tokio::spawn(async move {
+        let mut created = Vec::new();
+        while let Some(create) = rx.recv().await {
+            let rsp = client.transact(&create, CLTRID).await.unwrap();
+            created.push(rsp.res_data.unwrap().create_data.name.to_string());
+        }
+        (client, created)
+    });
+
+    producer.await.unwrap();
+    let (mut client, created) = consumer.await.unwrap();
+    assert_eq!(created, ["eppdev-1.com", "eppdev-2.com"]);
+
+    let update = OwnedDomainUpdate::builder("eppdev-1.com")
+        .add_status(DomainStatus::ClientHold)
+        .build()
+        .unwrap();
+    client.transact(&update, CLTRID).await.unwrap();
+
+    let info = OwnedDomainInfo::new("eppdev-1.com", None).unwrap();
+    let rsp = client.transact(&info, CLTRID).await.unwrap();
+    let statuses = rsp.res_data.unwrap().info_data.statuses.unwrap();
+    assert!(statuses
+        .iter()
+        .any(|status| status.status == DomainStatus::ClientHold));
+}